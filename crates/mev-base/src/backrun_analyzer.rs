@@ -3,6 +3,8 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use crate::flashblock_state::FlashblockStateSnapshot;
 use crate::gradient_descent::GradientOptimizer;
+use crate::transaction_service::WalletStrategy;
+use crate::executor_codec::ExecutorCodec;
 use alloy_consensus::Transaction;
 
 /// Token pair processor configuration
@@ -13,9 +15,10 @@ pub struct TokenPairProcessorConfig {
     pub accounts: Vec<Address>,
     pub contract_address: Address,
     pub default_value: U256,
-    pub data_format: String, // "short" or "long"
+    pub codec: ExecutorCodec, // executor calldata/value layout the contract was deployed with
     pub check_balance_of: Option<(Address, Address)>, // (erc20_token, address_to_check)
     pub priority_fee_multiplier: Option<u32>, // 5-digit multiplier: 10000 = 1x, 10100 = 1.01x, 15000 = 1.5x
+    pub wallet_strategy: Option<WalletStrategy>, // overrides BLOCK_TX_WALLET_STRATEGY for this processor
 }
 
 /// Backrun analyzer for monitoring token pair processors
@@ -26,16 +29,18 @@ pub struct BackrunAnalyzer {
 }
 
 impl BackrunAnalyzer {
+    /// Analyzer over the processors deployed on Base mainnet
     pub fn new(min_profit_threshold: U256) -> Self {
-        let mut analyzer = Self {
-            configs: HashMap::new(),
+        Self::with_processors(Self::base_mainnet_processors(), min_profit_threshold)
+    }
+
+    /// Analyzer over the given processors, usually a network's [`crate::network::AddressBook`]
+    pub fn with_processors(processors: Vec<TokenPairProcessorConfig>, min_profit_threshold: U256) -> Self {
+        Self {
+            configs: processors.into_iter().map(|config| (config.name.clone(), config)).collect(),
             gradient_optimizer: Arc::new(GradientOptimizer::new()),
             min_profit_threshold,
-        };
-        
-        // Initialize all processor configs
-        analyzer.initialize_configs();
-        analyzer
+        }
     }
     
     /// Convert an address string (e.g., "0xe5C17Deb99f15033451b63d2Acf34d840211b3bB") 
@@ -91,20 +96,21 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xe5C17Deb99f15033451b63d2Acf34d840211b3bB").unwrap()),
                 default_value: U256::from(6),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x8c54143b62cca30b0718ef8167ad5bc25881e554").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
     */
 
-    // Initialize processor configurations (ported from TypeScript)
-    fn initialize_configs(&mut self) {
+    /// Processor configurations deployed on Base mainnet (ported from TypeScript)
+    pub fn base_mainnet_processors() -> Vec<TokenPairProcessorConfig> {
         // Port all the configs from processorConfigs.ts
-        let configs = vec![
+        vec![
             TokenPairProcessorConfig {
                 name: "RdacUsdcWeth".to_string(),
                 tokens: vec![],
@@ -116,13 +122,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x3E34678B931366730E0ca7A118F776775eC05fe6").unwrap()),
                 default_value: U256::from(6),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0xD3F68c6e8AeE820569D58AdF8d85d94489315192").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x25E24011104341e099E753B1517CC0AaA3e8d49c").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdsUsdcWeth".to_string(),
@@ -135,13 +142,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x0748D5B94130D68Fd9D4DB43e323d6721bC45cf8").unwrap()),
                 default_value: U256::from(1000),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4289a0666397f517c2A761652AaebAf6A4f7A693").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: Some(11000),
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcUsdsWeth".to_string(),
@@ -154,13 +162,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x569c51A827dfEeD3303d58f3948c86F77aC25BF2").unwrap()),
                 default_value: U256::from(1000),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4289a0666397f517c2A761652AaebAf6A4f7A693").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: Some(11000),
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcRdacWeth".to_string(),
@@ -173,13 +182,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xcFAEa7C9d1e6a81d41f8D027D81DfFB373369E48").unwrap()),
                 default_value: U256::from(600),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0xD3F68c6e8AeE820569D58AdF8d85d94489315192").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x6b385c432844f47f8226DD3CFb3899242015ef37").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcHoaWxch".to_string(),
@@ -193,13 +203,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x153b77468BCE59fF94926D9C2d68A1e5E4E4fc51").unwrap()),
                 default_value: U256::from(400),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x84B0b1EE6eef971105442Eb9Ab420F3DbB774b46").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xF30574f4e867B9FEE0E3d64b09d9Afd5FF13a021").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcHoaWeth".to_string(),
@@ -212,13 +223,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xb87cA82977180a5f359cAF4CA7EDB5e5755366e2").unwrap()),
                 default_value: U256::from(250),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x84B0b1EE6eef971105442Eb9Ab420F3DbB774b46").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xF30574f4e867B9FEE0E3d64b09d9Afd5FF13a021").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcIcnt".to_string(),
@@ -231,13 +243,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x5Bb80A08A418E2aaDbCFee392f27FC5387c21DC6").unwrap()),
                 default_value: U256::from(2500),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0xE0Cd4cAcDdcBF4f36e845407CE53E87717b6601d").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x705b73427BF045e595014661CCC7f1227a175ecd").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             // TokenPairProcessorConfig {
             //     name: "UsdcCbxrpWethUsdc".to_string(),
@@ -250,13 +263,14 @@ impl BackrunAnalyzer {
             //     // Convert contract address
             //     contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x3FE9a62E427319ce875B3fF4EC0a0F896F755480").unwrap()),
             //     default_value: U256::from(1000),
-            //     data_format: "short".to_string(),
+            //     codec: ExecutorCodec::V1_SHORT,
             //     // check_balance_of: None,
             //     check_balance_of: Some((
             //         Address::from(BackrunAnalyzer::address_to_bytes("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913").unwrap()), // ERC20 token (USDC)
             //         Address::from(BackrunAnalyzer::address_to_bytes("0x8c54143b62cCa30B0718ef8167Ad5bC25881e554").unwrap()), // Address to check balance of
             //     )),
             //     priority_fee_multiplier: None,
+            //     wallet_strategy: None,
             // },
             TokenPairProcessorConfig {
                 name: "UsdcEurcMamoCbbtcWeth".to_string(),
@@ -271,13 +285,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x7d98F845E15910B7058FEbf998326624864f6bef").unwrap()),
                 default_value: U256::from(1742),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x94F64594854DF80d2DaDDb62830aF8e9Ac8654c0").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcCbbtcPromptWeth".to_string(),
@@ -291,13 +306,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x4024c2c5e27aD439b581B88A2E9945e2534C45d8").unwrap()),
                 default_value: U256::from(3749),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0xcbB7C0000aB88B473b1f5aFd9ef808440eed33Bf").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4ca59b56965385e14B3fd4d48Cd1741dC21E7343").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdbcAeroWeth".to_string(),
@@ -311,13 +327,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x178E4527AFC50A40d4036587B2b1e171780e2E10").unwrap()),
                 default_value: U256::from(723),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4C36388bE6F416A29C8d8Eee81C771cE6bE14B18").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "IPindexyPfwethWeth".to_string(),
@@ -331,13 +348,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x478ad4Aa623106fcdbe0510aC0c493686709f545").unwrap()),
                 default_value: U256::from(1294),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x87c0676255bE413399C9a205d9CbeB2A04814CCa").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcMamoCbbtcWeth".to_string(),
@@ -351,13 +369,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xb435855eF80F12B4C4C0cF275dB85bb4F3716E98").unwrap()),
                 default_value: U256::from(2521),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x7300B37DfdfAb110d83290A29DfB31B1740219fE").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x678F431DA2aBb9B5726bbf5CCDbaEEBB60dA9813").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "AukiAeroWeth".to_string(),
@@ -370,13 +389,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x6ED271c29E788Fc4e946D60b6855ac9bF81E88d3").unwrap()),
                 default_value: U256::from(2517),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x2Fa9D6085c91151200e61a3e627D35001772C0D1").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "AeroPpaeroPfusdcUsdc".to_string(),
@@ -389,13 +409,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xd5b7351c9F92C787d68c4616DA9d3845843feC99").unwrap()),
                 default_value: U256::from(1274),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x2aD54e3608F2e630D1A1226E98615FD6EbCbb996").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x025B8dD0C0E7e54A0424c1DcCFC89d6f444724F9").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "KudaiPkuadiPfusdcUsdcWeth".to_string(),
@@ -408,13 +429,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x84309D197e39f2c3d12a8099E0DaCF5a67FD0671").unwrap()),
                 default_value: U256::from(550),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x670e77c361375bE9013869CCC516027CCc90383f").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethCbbtcPrompt".to_string(),
@@ -427,13 +449,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xA61C2ce328C2346233c801C2BD02E46Be36F74Dd").unwrap()),
                 default_value: U256::from(328),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xdF4D571e55eFdc25CDD010dA9Cb35b21064DEd49").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethBenjiAero".to_string(),
@@ -446,13 +469,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x0aEafdAaB2a0e05335c18EbB2E9D04b183928eDa").unwrap()),
                 default_value: U256::from(913),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x16905890A1D02b6F824387419319Bf4188B961b0").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethUsdcZora".to_string(),
@@ -464,13 +488,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x11a40A6c3d44FF301732CB3695429b739feB4570").unwrap()),
                 default_value: U256::from(823),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x1111111111166b7FE7bd91427724B487980aFc69").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xEdc625B74537eE3a10874f53D170E9c17A906B9c").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethTibbirVirtual".to_string(),
@@ -483,13 +508,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xd81A85c18DB29452De36a0f56fFC0e60FBE62366").unwrap()),
                 default_value: U256::from(370),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x9c087Eb773291e50CF6c6a90ef0F4500e349B903").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "AeroWeth".to_string(),
@@ -501,13 +527,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xA7fe2a8D76D7e729105D52e15e3daC612B382eAA").unwrap()),
                 default_value: U256::from(2258),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xF4DFb8647C3Ef75c5A71b7B0ee9240BdccCe8697").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "AnonPanonPfwethWeth".to_string(),
@@ -521,13 +548,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xE4737263cC53D7b0a3dE4002d2C42a521280F495").unwrap()),
                 default_value: U256::from(401),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x1807af3897aA6419E770D4642dF7B8b06E542C02").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "MigglesPmigglesPeasUsdc".to_string(),
@@ -541,13 +569,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xb0a463f5E5132Df8eEe74A2b3a2F55A650DD9330").unwrap()),
                 default_value: U256::from(80),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xC16F5d5C0a2C0784EfaFEDf28B934a9F0bA21CD7").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethPfwethPtybgpTybg".to_string(),
@@ -561,13 +590,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xdd9723cE865B4d9A20774A8695c43121ADC38711").unwrap()),
                 default_value: U256::from(30),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x8d628d22d298b4a6E3DC9171d4b7aa5229e2353c").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcPzfiZfiWeth".to_string(),
@@ -582,13 +612,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xDf0F538cD6472fb82f5db3e980901C379e9633a4").unwrap()),
                 default_value: U256::from(50),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xeF32a6e5B1D363deD63e35af03fc53A637926DE0").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethRwaxPearwaxUsdc".to_string(),
@@ -601,13 +632,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x69d7d544348C52C1904D2BA5Ec3324b607983C47").unwrap()),
                 default_value: U256::from(160),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xd448670823ff9667848C821BeE829c642F67E064").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethFuegoPfuegoPeasUsdc".to_string(),
@@ -621,13 +653,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x9ab787aa734a0BbFb12EcbE891b8eeb0E1CdCD75").unwrap()),
                 default_value: U256::from(800),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x2bbFb5A2496f405d4094D4b854DAeb9CE70D0029").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcAeroWeth".to_string(),
@@ -640,13 +673,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x0E95FB13f090B309d822C1074b95D749b42e6aFe").unwrap()),
                 default_value: U256::from(526),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x20CB8f872ae894F7c9e32e621C186e5AFCe82Fd0").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcPwbltPeas".to_string(),
@@ -660,13 +694,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x0802a4408795639F10f829936E0e080F672eb6fE").unwrap()),
                 default_value: U256::from(360),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x3Dd79d6BD927615787Cc95F2c7A77C9aC1AF26F4").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x9b0025d10E824E7E2b148953009A40B0C0792F30").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcBusdBltFblpFsblpWbltBlt".to_string(),
@@ -682,13 +717,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x1Ed147c2275CF72EA364DF7Ab88ADfcC0921bdD6").unwrap()),
                 default_value: U256::from(400),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x3Dd79d6BD927615787Cc95F2c7A77C9aC1AF26F4").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x9b0025d10E824E7E2b148953009A40B0C0792F30").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcPeasPmigglesMigglesWeth".to_string(),
@@ -704,13 +740,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xBfDC66fc27370b2998ef1b73d3Fc6A6042100fc3").unwrap()),
                 default_value: U256::from(60),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x02f92800F57BCD74066F5709F1Daa1A4302Df875").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xb0a65b3A6F9DA0e5EB057e0D5327DEDDbe17309E").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcVirtualTibbirPtibbir".to_string(),
@@ -726,12 +763,13 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xD1cbA7BE955fAc0cc458aCe3A2a61E271b41053D").unwrap()),
                 default_value: U256::from(1191),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x0b3e328455c4059EEb9e3f84b5543F74E24e7E1b").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x0c3b466104545efa096b8f944c1e524E1d0D4888").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcBmxPbmxPeas".to_string(),
@@ -746,13 +784,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xAdEcFf8F5890572D352DE8A5Ec997766e9dCAF0D").unwrap()),
                 default_value: U256::from(248),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x548f93779fBC992010C07467cBaf329DD5F059B7").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x2F48C208d7Bd2b4Ff6Da005A9427eF38F035b2d8").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "KudaiPkudaiUsdcWeth".to_string(),
@@ -766,13 +805,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xaFC4814646FBf06b84761fdF1264c4Dc22fAAa5c").unwrap()),
                 default_value: U256::from(600),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xaDE5DA9C31b77a2b95c8Dd88676AFFD2c9482139").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcWethTibbirPtibbir".to_string(),
@@ -786,13 +826,14 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x5D30142a0F8527fEfbdda7E427e7b66325Fa4189").unwrap()),
                 default_value: U256::from(974),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 // check_balance_of: None,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x0f664afFB82f074937D5cFCD61b97F3F32d5dC50").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "ZfiPzfiUsdcWeth".to_string(),
@@ -806,12 +847,13 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x7065f0E05fDF46Ab98522F5930F38BAa04469B8a").unwrap()),
                 default_value: U256::from(600),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xeF32a6e5B1D363deD63e35af03fc53A637926DE0").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethUsdcUsdbc".to_string(),
@@ -824,9 +866,10 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xC1043c9c8003AFdE9127192d6bFa15A19E6017fB").unwrap()),
                 default_value: U256::from(90),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethAeroSpectre".to_string(),
@@ -839,9 +882,10 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x8C1e725EdE2301AF3Ff0Bf23c76200C0eEd4445f").unwrap()),
                 default_value: U256::from(73),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethUsdcAero".to_string(),
@@ -854,12 +898,13 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x7e2D7acA5bBB8d30c3628EEe1EE18E9626421DF2").unwrap()),
                 default_value: U256::from(200),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xE5B5f522E98B5a2baAe212d4dA66b865B781DB97").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethWgcDegen".to_string(),
@@ -872,9 +917,10 @@ impl BackrunAnalyzer {
                 // Convert contract address
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x81A0c588Bd8f7aC26884E6c46558e9323f284277").unwrap()),
                 default_value: U256::from(42),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             // TokenPairProcessorConfig {
             //     name: "WethUsdcCbxrp".to_string(),
@@ -887,7 +933,7 @@ impl BackrunAnalyzer {
             //     // Convert contract address
             //     contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x22B2158d0F07974052B48Fe2438da071b1b18518").unwrap()),
             //     default_value: U256::from(60),
-            //     data_format: "short".to_string(),
+            //     codec: ExecutorCodec::V1_SHORT,
             //     check_balance_of: Some((
             //         Address::from(BackrunAnalyzer::address_to_bytes("0x41e357ea17eed8e3ee32451f8e5cba824af58dbf").unwrap()), // ERC20 token (USDC)
             //         Address::from(BackrunAnalyzer::address_to_bytes("0x8c54143b62cca30b0718ef8167ad5bc25881e554").unwrap()), // Address to check balance of
@@ -902,9 +948,10 @@ impl BackrunAnalyzer {
                 ],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x62633312C6a9dd1DbB8a073c96AD898c6b062F0F").unwrap()),
                 default_value: U256::from(162),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcKtaWeth".to_string(),
@@ -914,12 +961,13 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x6cDa604E8D9A67497B3B2872667f7DCBdB81eF9F").unwrap()),
                 default_value: U256::from(174),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x5Ba5ED44D7262C5A0021fd5fba621C9CceA0DdAB").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0xc0634090F2Fe6c6d75e61Be2b949464aBB498973").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcSpartansWeth".to_string(),
@@ -929,9 +977,10 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x4eF2620cbC6eC0bBd7D2872ce390AB4C951feDEd").unwrap()),
                 default_value: U256::from(94),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcUsdtWeth".to_string(),
@@ -941,9 +990,10 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x91b56E95A682494A98ca36DF68d1F209D6C4B5d9").unwrap()),
                 default_value: U256::from(597),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "EurcUsdcWeth".to_string(),
@@ -953,12 +1003,13 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xAA34C38Bd8cB58077e32B611FFc8a1772b3D687A").unwrap()),
                 default_value: U256::from(644),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x4200000000000000000000000000000000000006").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x260441e9574c0633c70c5cff3a47d75dc9bc3e22").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcXccxWeth".to_string(),
@@ -968,9 +1019,10 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x9C4d9ba17FA4C0Ba2494Fdcc4EBAD9a87d428131").unwrap()),
                 default_value: U256::from(634),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdbcWethUsdc".to_string(),
@@ -980,12 +1032,13 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x81bbD0101d8bB1b138005271353137AFD36d5c9e").unwrap()),
                 default_value: U256::from(381),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: Some((
                     Address::from(BackrunAnalyzer::address_to_bytes("0x833589fcd6edb6e08f4c7c32d4f71b54bda02913").unwrap()), // ERC20 token (USDC)
                     Address::from(BackrunAnalyzer::address_to_bytes("0x88492051e18a65fe00241a93699a6082ae95c828").unwrap()), // Address to check balance of
                 )),
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcPfusdcPbasedpepePepe".to_string(),
@@ -997,9 +1050,10 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xAED9aFe16c8c03876413FA1cCBb17646A3B24266").unwrap()),
                 default_value: U256::from(1019),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethPfwethPbrianBrian".to_string(),
@@ -1011,9 +1065,10 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x1c8dDf0e958111eD1EC98dAeA2110ABCCB5a265F").unwrap()),
                 default_value: U256::from(277),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethBrianPbrianPfweth".to_string(),
@@ -1025,9 +1080,10 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x212d0CcF7368859Bf5e1A54B9E225874c591C4a6").unwrap()),
                 default_value: U256::from(692),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcPfusdcPusdpPeasUsdcWeth".to_string(),
@@ -1039,9 +1095,10 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xd01B9d8CD74F0E245158Dd5d6A8d5B3873505C83").unwrap()),
                 default_value: U256::from(157),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "UsdcPfusdcPtibbirTibbirWeth".to_string(),
@@ -1053,9 +1110,10 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x5ecAD03BA53E092db9c98E038E005f8597645F84").unwrap()),
                 default_value: U256::from(84),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "WethPfwethPpeasPeasUsdc".to_string(),
@@ -1067,9 +1125,10 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0x5817C0E2bDdDcdB3ca24f60Fe05efD74bdA0a0E6").unwrap()),
                 default_value: U256::from(6),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
             TokenPairProcessorConfig {
                 name: "TybgPtybgPfwethWeth".to_string(),
@@ -1081,15 +1140,12 @@ impl BackrunAnalyzer {
                 accounts: vec![],
                 contract_address: Address::from(BackrunAnalyzer::address_to_bytes("0xCCBF08344B87ECE24D0563C932f1A5Ca3A2Cd79A").unwrap()),
                 default_value: U256::from(1329),
-                data_format: "short".to_string(),
+                codec: ExecutorCodec::V1_SHORT,
                 check_balance_of: None,
                 priority_fee_multiplier: None,
+                wallet_strategy: None,
             },
-        ];
-        
-        for config in configs {
-            self.configs.insert(config.name.clone(), config);
-        }
+        ]
     }
     
     /// Analyze state for backrun opportunities
@@ -0,0 +1,60 @@
+//! Runs the MEV pipeline over a remote node's JSON-RPC and the flashblocks feed, without
+//! launching reth.
+//!
+//! Chain state comes from `remote_state.rpc_url` (or `--mev.remote-state-url`); every other
+//! setting is read exactly as the node binary reads it.
+
+use clap::Parser;
+use mevbase::config::{redact_url, MevArgs, MevConfig};
+use mevbase::logging;
+use mevbase::remote_state::RpcStateSource;
+use mevbase::runtime::MevRuntime;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_optimism_cli::chainspec::OpChainSpecParser;
+use tracing::info;
+
+#[derive(Debug, Parser)]
+#[command(name = "mevbase-remote", about = "mevbase over a remote node's state, without a local reth node")]
+struct Args {
+    /// Chain to run on, named as for the node's `--chain`
+    #[arg(long, value_name = "CHAIN", default_value = "base")]
+    chain: String,
+
+    #[command(flatten)]
+    mev: MevArgs,
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    logging::init_logging();
+
+    let args = Args::parse();
+    let mut config = MevConfig::from_args(&args.mev)?;
+    info!("Effective mevbase configuration:\n{}", config.to_redacted_toml());
+    let Some(remote_state) = config.remote_state_config() else {
+        return Err(eyre::eyre!(
+            "mevbase-remote reads chain state from a remote node; set remote_state.rpc_url or pass --mev.remote-state-url"
+        ));
+    };
+
+    let network = config.network(OpChainSpecParser::parse(&args.chain)?)?;
+    info!(
+        network = %network.network,
+        chain_id = network.chain_id,
+        flashblocks_url = %redact_url(&network.flashblocks_url),
+        sequencer_url = %redact_url(&network.sequencer_url),
+        backrun_processors = network.address_book.backrun_processors.len(),
+        "🌐 Network resolved"
+    );
+
+    let runtime = MevRuntime::build(&mut config, network).await?;
+    info!(url = %redact_url(&remote_state.rpc_url), "Reading chain state from remote node");
+    let pipeline = runtime.start_pipeline(RpcStateSource::new(&remote_state), &config)?;
+
+    // No node to wait on: run until interrupted, then drain and persist like the node does
+    let shutdown_pipeline = runtime.shutdown_pipeline(&pipeline, &config);
+    tokio::signal::ctrl_c().await?;
+    shutdown_pipeline.run().await;
+    pipeline.join().await;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+#[path = "../mock_sequencer.rs"]
+mod mock_sequencer;
+
+use eyre::Result;
+use mock_sequencer::MockSequencerConfig;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Initialize logging
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive("info".parse().unwrap())
+        )
+        .init();
+
+    let addr = std::env::var("MOCK_SEQUENCER_ADDR")
+        .unwrap_or_else(|_| "127.0.0.1:8547".to_string())
+        .parse()?;
+
+    // MOCK_SEQUENCER_ERROR="code:message" makes every submission fail with that JSON-RPC error
+    let submission_error = std::env::var("MOCK_SEQUENCER_ERROR").ok().and_then(|value| {
+        let (code, message) = value.split_once(':')?;
+        Some((code.trim().parse::<i32>().ok()?, message.trim().to_string()))
+    });
+
+    let config = MockSequencerConfig {
+        latency: Duration::from_millis(
+            std::env::var("MOCK_SEQUENCER_LATENCY_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+        ),
+        submission_error,
+        support_conditional: std::env::var("MOCK_SEQUENCER_CONDITIONAL")
+            .map(|s| s == "true")
+            .unwrap_or(true),
+        // MOCK_SEQUENCER_SIGNER_KEY lets the mock answer eth_signTransaction like a remote signer
+        signer: std::env::var("MOCK_SEQUENCER_SIGNER_KEY")
+            .ok()
+            .map(|key| key.trim_start_matches("0x").parse())
+            .transpose()?,
+    };
+
+    println!("Mock sequencer config: {:?}", config);
+    let (local_addr, stats) = mock_sequencer::spawn(addr, config).await?;
+    println!("Mock sequencer listening on http://{}/", local_addr);
+
+    // Periodically report counters; a warm client keeps `connections` flat while `requests` grows
+    let mut interval = tokio::time::interval(Duration::from_secs(10));
+    loop {
+        interval.tick().await;
+        println!(
+            "connections={} requests={} conditional_requests={}",
+            stats.connections.load(Ordering::Relaxed),
+            stats.requests.load(Ordering::Relaxed),
+            stats.conditional_requests.load(Ordering::Relaxed),
+        );
+    }
+}
@@ -5,6 +5,7 @@ use alloy_network::TxSigner;
 use alloy_eips::eip2718::Encodable2718;
 use eyre::Result;
 use std::str::FromStr;
+use mevbase::network::Network;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -16,11 +17,25 @@ async fn main() -> Result<()> {
         )
         .init();
 
-    // Create a test wallet (this is a well-known test private key)
-    let private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
-    let wallet = PrivateKeySigner::from_str(private_key)?;
+    // Load the test wallet from a keystore or a raw key; never hard-code keys
+    let wallet = if let Ok(keystore) = std::env::var("TEST_SEQUENCER_KEYSTORE") {
+        let password = std::env::var("TEST_SEQUENCER_KEYSTORE_PASSWORD")
+            .map_err(|_| eyre::eyre!("TEST_SEQUENCER_KEYSTORE_PASSWORD is required with TEST_SEQUENCER_KEYSTORE"))?;
+        PrivateKeySigner::decrypt_keystore(&keystore, password)?
+    } else {
+        let private_key = std::env::var("TEST_SEQUENCER_PRIVATE_KEY")
+            .map_err(|_| eyre::eyre!("Set TEST_SEQUENCER_PRIVATE_KEY or TEST_SEQUENCER_KEYSTORE"))?;
+        PrivateKeySigner::from_str(private_key.trim_start_matches("0x"))?
+    };
     let from_address = wallet.address();
     
+    // Network the test transaction is encoded and submitted for
+    let network_name = std::env::var("TEST_SEQUENCER_NETWORK").unwrap_or_else(|_| "base".to_string());
+    let (network, chain_id) = Network::from_name(&network_name)
+        .and_then(|n| Some((n, n.default_chain_id()?)))
+        .ok_or_else(|| eyre::eyre!("Unknown TEST_SEQUENCER_NETWORK {:?} (base, base-sepolia or dev)", network_name))?;
+    println!("Network: {} (chain {})", network, chain_id);
+    
     println!("Test wallet address: {}", from_address);
     
     // Create a simple transfer transaction
@@ -33,7 +48,7 @@ async fn main() -> Result<()> {
     
     // Build EIP-1559 transaction
     let mut tx = TxEip1559 {
-        chain_id: 8453, // Base mainnet
+        chain_id,
         nonce,
         gas_limit,
         max_fee_per_gas,
@@ -86,7 +101,9 @@ async fn main() -> Result<()> {
     // Create sequencer client
     let client = reqwest::Client::new();
     let sequencer_url = std::env::var("SEQUENCER_URL")
-        .unwrap_or_else(|_| "https://mainnet-sequencer.base.org/".to_string());
+        .ok()
+        .or_else(|| network.default_sequencer_url().map(str::to_string))
+        .ok_or_else(|| eyre::eyre!("SEQUENCER_URL is required on {}", network))?;
     
     println!("\nSubmitting to sequencer: {}", sequencer_url);
     
@@ -0,0 +1,251 @@
+//! Feedback controller for the quantity search's upper bound.
+//!
+//! The upper bound is `default_value × multiplier`. After every search the multiplier moves
+//! in log space on two signals: where the best quantity fell within the bounds (pinned near the
+//! upper bound widens, far below it tightens, towards a setpoint fraction of the bound) and the
+//! filtered search gas against its budget (over budget always tightens and blocks widening).
+//! A small integral term removes steady bias; it stops accumulating while the multiplier is
+//! clamped so it cannot wind up.
+
+use alloy_primitives::U256;
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use serde::{Deserialize, Serialize};
+
+/// Bound on the accumulated position error (log space)
+const INTEGRAL_LIMIT: f64 = 5.0;
+
+/// Configuration for the bounds controller
+#[derive(Debug, Clone)]
+pub struct BoundsControllerConfig {
+    /// Search gas budget per optimizer iteration
+    pub target_gas_per_iteration: u64,
+    pub min_multiplier: f64,
+    pub max_multiplier: f64,
+    /// Fraction of the upper bound the optimum should sit at (basis points)
+    pub position_setpoint_bps: u32,
+    pub position_gain: f64,
+    pub integral_gain: f64,
+    pub gas_gain: f64,
+    /// Largest change of the multiplier per update, as a factor
+    pub max_step: f64,
+}
+
+impl Default for BoundsControllerConfig {
+    fn default() -> Self {
+        Self {
+            target_gas_per_iteration: 875_000,
+            min_multiplier: 10.0,
+            max_multiplier: 1000.0,
+            position_setpoint_bps: 2_500,
+            position_gain: 0.5,
+            integral_gain: 0.05,
+            gas_gain: 0.5,
+            max_step: 2.0,
+        }
+    }
+}
+
+/// Controller state persisted per target with its gas history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoundsState {
+    /// Upper bound as a multiple of the default quantity
+    pub multiplier: f64,
+    /// Accumulated position error (log space)
+    pub integral: f64,
+    /// Best quantity over the upper bound in the last profitable search
+    pub last_position: Option<f64>,
+    /// Filtered search gas over its budget at the last cold search
+    pub last_gas_ratio: Option<f64>,
+    pub updates: u64,
+}
+
+impl BoundsState {
+    /// Start from a multiplier, e.g. one stored before the controller existed
+    pub fn initial(multiplier: f64, config: &BoundsControllerConfig) -> Self {
+        Self {
+            multiplier: multiplier.clamp(config.min_multiplier, config.max_multiplier),
+            integral: 0.0,
+            last_position: None,
+            last_gas_ratio: None,
+            updates: 0,
+        }
+    }
+
+    /// Upper bound multiplier as used for the bounds and the legacy `multiplier` field
+    pub fn whole_multiplier(&self) -> u64 {
+        self.multiplier.round() as u64
+    }
+}
+
+/// What one search revealed about the bounds
+#[derive(Debug, Clone)]
+pub struct BoundsObservation {
+    /// Filtered search gas; only meaningful after a cold search
+    pub filtered_gas: Option<u64>,
+    /// Whether the full cold range was searched
+    pub cold: bool,
+    pub iterations: usize,
+    pub best_qty: U256,
+    pub profit: i128,
+    /// Upper bound of the cold range
+    pub upper_bound: U256,
+    /// The upper bound was cut to the calldata format's maximum, so widening cannot help
+    pub upper_capped: bool,
+}
+
+/// Next controller state after a search
+pub fn update(state: &BoundsState, observation: &BoundsObservation, config: &BoundsControllerConfig) -> BoundsState {
+    // Positive when under the gas budget
+    let gas_ratio = observation.filtered_gas
+        .filter(|_| observation.cold)
+        .map(|gas| gas.max(1) as f64 / (config.target_gas_per_iteration * observation.iterations.max(1) as u64) as f64);
+    let gas_error = gas_ratio.map_or(0.0, |ratio| -ratio.ln());
+
+    // Positive when the optimum sits above the setpoint fraction of the bound
+    let position = (observation.profit > 0 && !observation.upper_bound.is_zero())
+        .then(|| ratio(observation.best_qty, observation.upper_bound).clamp(1e-6, 1.0));
+    let setpoint = config.position_setpoint_bps as f64 / 10_000.0;
+    let position_error = position.map_or(0.0, |p| (p / setpoint).ln());
+
+    let mut integral = (state.integral + position_error).clamp(-INTEGRAL_LIMIT, INTEGRAL_LIMIT);
+    let mut step = config.position_gain * position_error + config.integral_gain * integral;
+    if position.is_none() {
+        // Nothing profitable found: look wider while the gas budget allows
+        step += config.gas_gain * gas_error.max(0.0);
+    }
+    if gas_error < 0.0 {
+        // Over budget: tighten regardless of where the optimum was
+        step = step.min(0.0) + config.gas_gain * gas_error;
+    }
+    if observation.upper_capped {
+        step = step.min(0.0);
+    }
+    let max_step = config.max_step.max(1.0).ln();
+    let step = step.clamp(-max_step, max_step);
+
+    let unclamped = state.multiplier * step.exp();
+    let multiplier = unclamped.clamp(config.min_multiplier, config.max_multiplier);
+    // Anti-windup: hold the integral while the multiplier is pinned at a limit
+    if multiplier != unclamped || (observation.upper_capped && position_error > 0.0) {
+        integral = state.integral;
+    }
+
+    BoundsState {
+        multiplier,
+        integral,
+        last_position: position.or(state.last_position),
+        last_gas_ratio: gas_ratio.or(state.last_gas_ratio),
+        updates: state.updates + 1,
+    }
+}
+
+/// `a / b` for quantities that may not fit in a float exactly
+fn ratio(a: U256, b: U256) -> f64 {
+    let shift = b.bit_len().max(a.bit_len()).saturating_sub(100);
+    let a = (a >> shift).saturating_to::<u128>() as f64;
+    let b = (b >> shift).saturating_to::<u128>() as f64;
+    a / b.max(1.0)
+}
+
+/// Per-config bounds controller trajectory
+#[derive(Metrics, Clone)]
+#[metrics(scope = "mev.bounds")]
+pub struct BoundsControllerMetrics {
+    /// Current upper bound multiplier
+    pub multiplier: Gauge,
+    /// Best quantity over the upper bound in the last profitable search
+    pub best_position: Gauge,
+    /// Filtered search gas over its budget at the last cold search
+    pub gas_ratio: Gauge,
+    /// Log-space change of the multiplier per update
+    pub step: Histogram,
+    /// Updates that widened the bounds
+    pub widened_total: Counter,
+    /// Updates that tightened the bounds
+    pub tightened_total: Counter,
+}
+
+/// Publish a controller update for a config
+pub fn record_metrics(config_name: &str, previous: &BoundsState, next: &BoundsState) {
+    static METRICS: std::sync::LazyLock<dashmap::DashMap<String, BoundsControllerMetrics>> =
+        std::sync::LazyLock::new(dashmap::DashMap::new);
+
+    let metrics = METRICS
+        .entry(config_name.to_string())
+        .or_insert_with(|| BoundsControllerMetrics::new_with_labels(&[("config", config_name.to_string())]))
+        .clone();
+
+    metrics.multiplier.set(next.multiplier);
+    if let Some(position) = next.last_position {
+        metrics.best_position.set(position);
+    }
+    if let Some(gas_ratio) = next.last_gas_ratio {
+        metrics.gas_ratio.set(gas_ratio);
+    }
+    let step = (next.multiplier / previous.multiplier).ln();
+    metrics.step.record(step);
+    if step > 0.0 {
+        metrics.widened_total.increment(1);
+    } else if step < 0.0 {
+        metrics.tightened_total.increment(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(best_qty: u64, profit: i128, filtered_gas: u64) -> BoundsObservation {
+        BoundsObservation {
+            filtered_gas: Some(filtered_gas),
+            cold: true,
+            iterations: 40,
+            best_qty: U256::from(best_qty),
+            profit,
+            upper_bound: U256::from(1_000_000u64),
+            upper_capped: false,
+        }
+    }
+
+    #[test]
+    fn test_position_drives_multiplier() {
+        let config = BoundsControllerConfig::default();
+        let budget = 875_000 * 40;
+        let state = BoundsState::initial(100.0, &config);
+
+        // Pinned at the upper bound with gas to spare: widen
+        let pinned = update(&state, &observation(990_000, 1, budget / 2), &config);
+        assert!(pinned.multiplier > state.multiplier);
+
+        // Far below the bound: tighten even though gas is under budget
+        let low = update(&state, &observation(10_000, 1, budget / 2), &config);
+        assert!(low.multiplier < state.multiplier);
+
+        // Pinned but over budget: the gas constraint wins
+        let over = update(&state, &observation(990_000, 1, budget * 3), &config);
+        assert!(over.multiplier < state.multiplier);
+
+        // At the setpoint and on budget: hold
+        let held = update(&state, &observation(250_000, 1, budget), &config);
+        assert!((held.multiplier - state.multiplier).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_clamps_and_anti_windup() {
+        let config = BoundsControllerConfig::default();
+        let mut state = BoundsState::initial(1000.0, &config);
+        for _ in 0..20 {
+            state = update(&state, &observation(1_000_000, 1, 1), &config);
+        }
+        assert_eq!(state.multiplier, config.max_multiplier);
+        assert_eq!(state.integral, 0.0);
+
+        let capped = BoundsObservation { upper_capped: true, ..observation(1_000_000, 1, 1) };
+        let state = update(&BoundsState::initial(50.0, &config), &capped, &config);
+        assert_eq!(state.multiplier, 50.0);
+        assert_eq!(ratio(U256::MAX, U256::MAX), 1.0);
+    }
+}
@@ -0,0 +1,104 @@
+use alloy_consensus::TxEnvelope;
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, B256};
+use reth_evm::{ConfigureEvm, Evm};
+use reth_optimism_chainspec::OpChainSpec;
+use reth_optimism_evm::OpEvmConfig;
+use reth_optimism_node::OpRethReceiptBuilder;
+use reth_revm::db::CacheDB;
+use revm::{context_interface::result::ExecutionResult, DatabaseCommit};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::flashblock_state::FlashblockStateSnapshot;
+use crate::revm_flashblock_executor::tx_env_from_envelope;
+use crate::state_source::StateSource;
+
+/// Result of simulating one leg of a bundle
+#[derive(Debug, Clone)]
+pub struct BundleLegResult {
+    pub tx_hash: B256,
+    pub gas_used: u64,
+}
+
+/// Simulates a fully signed bundle, in order, on top of the latest flashblock state
+pub struct BundleSimulator {
+    evm_config: OpEvmConfig,
+}
+
+impl BundleSimulator {
+    pub fn new(chain_spec: Arc<OpChainSpec>) -> Self {
+        Self {
+            evm_config: OpEvmConfig::new(chain_spec, OpRethReceiptBuilder::default()),
+        }
+    }
+
+    /// Execute every leg sequentially, committing state between legs.
+    ///
+    /// Unlike the worker simulations this runs with the real sender, nonce, balance and fees,
+    /// so it fails if any leg would revert, halt or be rejected (nonce gap, insufficient funds).
+    /// `sender_nonce` starts the sender at the first leg's nonce when the bundle is queued
+    /// behind our own submissions that have not landed yet.
+    pub fn simulate<S: StateSource>(
+        &self,
+        source: &S,
+        snapshot: &FlashblockStateSnapshot,
+        legs: &[TxEnvelope],
+        sender_nonce: Option<(Address, u64)>,
+    ) -> eyre::Result<Vec<BundleLegResult>> {
+        let mut cache_db = CacheDB::new(source.latest_state()?);
+        snapshot.apply_to(&mut cache_db);
+        if let Some((sender, nonce)) = sender_nonce {
+            cache_db.load_account(sender)?.info.nonce = nonce;
+        }
+
+        let current_timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let evm_env = self.evm_config.evm_env(&alloy_consensus::Header {
+            number: snapshot.block_number,
+            timestamp: current_timestamp,
+            gas_limit: 2_000_000_000,
+            base_fee_per_gas: Some(snapshot.base_fee as u64),
+            ..Default::default()
+        });
+
+        let mut results = Vec::with_capacity(legs.len());
+
+        for (leg_index, leg) in legs.iter().enumerate() {
+            let tx_hash = *leg.tx_hash();
+            let mut op_tx = op_revm::OpTransaction::new(tx_env_from_envelope(leg)?);
+            op_tx.enveloped_tx = Some(leg.encoded_2718().into());
+
+            let mut evm = self.evm_config.evm_with_env(&mut cache_db, evm_env.clone());
+            let exec_result = evm.transact(op_tx)
+                .map_err(|e| eyre::eyre!("Bundle leg {} ({}) rejected: {:?}", leg_index, tx_hash, e))?;
+            drop(evm);
+
+            match exec_result.result {
+                ExecutionResult::Success { gas_used, .. } => {
+                    debug!(leg = leg_index, tx_hash = %tx_hash, gas_used = gas_used, "Bundle leg simulated");
+                    cache_db.commit(exec_result.state);
+                    results.push(BundleLegResult { tx_hash, gas_used });
+                }
+                ExecutionResult::Revert { output, gas_used } => {
+                    warn!(leg = leg_index, tx_hash = %tx_hash, gas_used = gas_used, "Bundle leg reverted in simulation");
+                    return Err(eyre::eyre!(
+                        "Bundle leg {} ({}) reverted: 0x{}",
+                        leg_index,
+                        tx_hash,
+                        hex::encode(&output)
+                    ));
+                }
+                ExecutionResult::Halt { reason, .. } => {
+                    warn!(leg = leg_index, tx_hash = %tx_hash, reason = ?reason, "Bundle leg halted in simulation");
+                    return Err(eyre::eyre!("Bundle leg {} ({}) halted: {:?}", leg_index, tx_hash, reason));
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,209 @@
+use alloy_consensus::{Transaction, transaction::SignerRecoverable};
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_eth::EthCallResponse;
+use dashmap::DashMap;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use std::collections::HashSet;
+use tracing::{debug, info};
+
+use crate::backrun_analyzer::BackrunAnalyzer;
+use crate::flashblocks::FlashblocksEvent;
+
+/// Maximum number of transactions allowed between a trigger and its backrun
+const DEFAULT_MAX_BACKRUN_GAP: usize = 3;
+
+/// Per-config competition metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "mev.competition")]
+pub struct CompetitionMetrics {
+    /// Backruns landed by other searchers on this config's accounts
+    pub competitor_backruns_total: Counter,
+    /// Backruns landed by us on this config's accounts
+    pub our_backruns_total: Counter,
+    /// Share of observed backruns that were ours (0.0 - 1.0)
+    pub win_rate: Gauge,
+    /// Priority fee paid by the last competitor backrun (wei per gas)
+    pub last_competitor_priority_fee: Gauge,
+}
+
+/// A backrun observed in a flashblock
+#[derive(Debug, Clone)]
+pub struct BackrunObservation {
+    pub config_name: String,
+    pub block_number: u64,
+    pub flashblock_index: u32,
+    pub trigger_hash: B256,
+    pub backrun_hash: B256,
+    pub sender: Address,
+    pub contract: Option<Address>,
+    pub priority_fee: u128,
+    /// Position of the backrun within the flashblock
+    pub position: usize,
+    /// Number of transactions between the trigger and the backrun
+    pub gap: usize,
+    pub is_ours: bool,
+}
+
+/// Watched account set derived from a processor config
+struct WatchedSet {
+    name: String,
+    addresses: HashSet<Address>,
+    our_contract: Address,
+}
+
+/// Detects other searchers' backruns on the pools our processor configs watch
+pub struct CompetitorAnalyzer {
+    watched: Vec<WatchedSet>,
+    our_wallets: HashSet<Address>,
+    max_gap: usize,
+    /// (ours, theirs) per config
+    tallies: DashMap<String, (u64, u64)>,
+    metrics: DashMap<String, CompetitionMetrics>,
+}
+
+impl CompetitorAnalyzer {
+    pub fn new(analyzer: &BackrunAnalyzer, our_wallets: Vec<Address>) -> Self {
+        let watched = analyzer.get_configs().values()
+            .map(|config| WatchedSet {
+                name: config.name.clone(),
+                addresses: config.accounts.iter().chain(config.tokens.iter()).copied().collect(),
+                our_contract: config.contract_address,
+            })
+            .filter(|set| !set.addresses.is_empty())
+            .collect();
+
+        let max_gap = std::env::var("MEV_COMPETITOR_MAX_GAP")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_MAX_BACKRUN_GAP);
+
+        Self {
+            watched,
+            our_wallets: our_wallets.into_iter().collect(),
+            max_gap,
+            tallies: DashMap::new(),
+            metrics: DashMap::new(),
+        }
+    }
+
+    /// Analyze an executed flashblock for backruns on watched accounts
+    ///
+    /// `touched` and `results` are index-aligned with `event.transactions`.
+    /// A transaction touching a watched set is a trigger; the next successful transaction
+    /// within `max_gap` positions that touches the same set from a different sender, calling
+    /// a contract outside the set, is treated as a backrun extracting value from it.
+    pub fn analyze(
+        &self,
+        event: &FlashblocksEvent,
+        touched: &[Vec<Address>],
+        results: &[EthCallResponse],
+    ) -> Vec<BackrunObservation> {
+        let mut observations = Vec::new();
+        if touched.len() != event.transactions.len() {
+            debug!(
+                touched = touched.len(),
+                transactions = event.transactions.len(),
+                "Touched account list does not match flashblock, skipping competitor analysis"
+            );
+            return observations;
+        }
+
+        for set in &self.watched {
+            let hits: Vec<usize> = touched.iter()
+                .enumerate()
+                .filter(|(_, accounts)| accounts.iter().any(|a| set.addresses.contains(a)))
+                .map(|(i, _)| i)
+                .collect();
+
+            for pair in hits.windows(2) {
+                let (trigger_idx, backrun_idx) = (pair[0], pair[1]);
+                let gap = backrun_idx - trigger_idx - 1;
+                if gap > self.max_gap {
+                    continue;
+                }
+                if results.get(backrun_idx).map(|r| r.error.is_some()).unwrap_or(true) {
+                    continue;
+                }
+
+                let trigger = &event.transactions[trigger_idx];
+                let backrun = &event.transactions[backrun_idx];
+                let contract = backrun.to();
+
+                // Direct interactions with the watched pools/tokens are user swaps, not backruns
+                if contract.map(|to| set.addresses.contains(&to)).unwrap_or(true) {
+                    continue;
+                }
+
+                let (Ok(trigger_sender), Ok(sender)) = (trigger.recover_signer(), backrun.recover_signer()) else {
+                    continue;
+                };
+                if trigger_sender == sender {
+                    continue;
+                }
+
+                let is_ours = contract == Some(set.our_contract) || self.our_wallets.contains(&sender);
+
+                observations.push(BackrunObservation {
+                    config_name: set.name.clone(),
+                    block_number: event.block_number,
+                    flashblock_index: event.index,
+                    trigger_hash: *trigger.tx_hash(),
+                    backrun_hash: *backrun.tx_hash(),
+                    sender,
+                    contract,
+                    priority_fee: backrun.priority_fee_or_price(),
+                    position: backrun_idx,
+                    gap,
+                    is_ours,
+                });
+            }
+        }
+
+        for observation in &observations {
+            self.record(observation);
+        }
+
+        observations
+    }
+
+    /// Win rate for a config, if any backruns have been observed
+    pub fn win_rate(&self, config_name: &str) -> Option<f64> {
+        self.tallies.get(config_name).and_then(|tally| {
+            let (ours, theirs) = *tally;
+            let total = ours + theirs;
+            (total > 0).then(|| ours as f64 / total as f64)
+        })
+    }
+
+    fn record(&self, observation: &BackrunObservation) {
+        let metrics = self.metrics
+            .entry(observation.config_name.clone())
+            .or_insert_with(|| CompetitionMetrics::new_with_labels(&[("config", observation.config_name.clone())]))
+            .clone();
+
+        let mut tally = self.tallies.entry(observation.config_name.clone()).or_insert((0, 0));
+        if observation.is_ours {
+            tally.0 += 1;
+            metrics.our_backruns_total.increment(1);
+        } else {
+            tally.1 += 1;
+            metrics.competitor_backruns_total.increment(1);
+            metrics.last_competitor_priority_fee.set(observation.priority_fee as f64);
+            info!(
+                config = %observation.config_name,
+                block = observation.block_number,
+                flashblock = observation.flashblock_index,
+                sender = %observation.sender,
+                contract = ?observation.contract,
+                priority_fee = observation.priority_fee,
+                position = observation.position,
+                "Competitor backrun detected"
+            );
+        }
+        let (ours, theirs) = *tally;
+        metrics.win_rate.set(ours as f64 / (ours + theirs) as f64);
+    }
+}
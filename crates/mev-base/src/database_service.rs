@@ -1,9 +1,11 @@
-use tokio::sync::mpsc;
-use deadpool_postgres::{Config, ManagerConfig, Pool, RecyclingMethod, Runtime};
+use tokio::sync::{mpsc, oneshot};
+use deadpool_postgres::{Config, ManagerConfig, Pool, PoolConfig, RecyclingMethod, Runtime};
 use tokio_postgres::NoTls;
 use tracing::{info, error, debug};
 use chrono::{DateTime, Utc};
 use alloy_primitives::TxHash;
+use crate::competitor_analyzer::BackrunObservation;
+use crate::config::PostgresSection;
 
 /// Transaction log entry to be inserted into the database
 #[derive(Debug, Clone)]
@@ -18,7 +20,9 @@ pub struct TransactionLog {
 #[derive(Debug)]
 enum DatabaseMessage {
     LogBatch(Vec<TransactionLog>),
-    Shutdown,
+    LogBackruns(Vec<BackrunObservation>),
+    /// Write everything queued, then acknowledge and stop
+    Shutdown(oneshot::Sender<()>),
 }
 
 /// Database service that runs in its own thread
@@ -29,12 +33,12 @@ pub struct DatabaseService {
 
 impl DatabaseService {
     /// Create a new database service and spawn the worker thread
-    pub async fn new() -> eyre::Result<Self> {
+    pub async fn new(config: PostgresSection) -> eyre::Result<Self> {
         // Create channel for communication
         let (tx, rx) = mpsc::channel(1000);
         
         // Spawn the database worker thread
-        tokio::spawn(database_worker(rx));
+        tokio::spawn(database_worker(rx, config));
         
         Ok(Self { sender: tx })
     }
@@ -50,22 +54,39 @@ impl DatabaseService {
         Ok(())
     }
     
-    /// Shutdown the database service
-    pub async fn shutdown(self) -> eyre::Result<()> {
-        self.sender.send(DatabaseMessage::Shutdown).await
+    /// Log observed backruns (ours and competitors') asynchronously
+    pub async fn log_backruns(&self, observations: Vec<BackrunObservation>) -> eyre::Result<()> {
+        if observations.is_empty() {
+            return Ok(());
+        }
+        
+        self.sender.send(DatabaseMessage::LogBackruns(observations)).await
+            .map_err(|_| eyre::eyre!("Database service channel closed"))?;
+        Ok(())
+    }
+    
+    /// Shutdown the database service once every queued batch is written
+    pub async fn shutdown(&self) -> eyre::Result<()> {
+        let (done_tx, done_rx) = oneshot::channel();
+        self.sender.send(DatabaseMessage::Shutdown(done_tx)).await
             .map_err(|_| eyre::eyre!("Failed to send shutdown signal"))?;
+        done_rx.await
+            .map_err(|_| eyre::eyre!("Database worker stopped before flushing"))?;
         Ok(())
     }
 }
 
 /// The main database worker that runs in its own thread
-async fn database_worker(mut rx: mpsc::Receiver<DatabaseMessage>) {
+async fn database_worker(mut rx: mpsc::Receiver<DatabaseMessage>, config: PostgresSection) {
     info!("Database worker thread started");
     
     // Try to initialize the connection pool
-    let pool = match create_pool().await {
+    let pool = match create_pool(config).await {
         Ok(pool) => {
             info!("PostgreSQL connection pool initialized");
+            if let Err(e) = ensure_backrun_table(&pool).await {
+                error!("Failed to create competitor_backruns table: {}", e);
+            }
             Some(pool)
         }
         Err(e) => {
@@ -75,6 +96,7 @@ async fn database_worker(mut rx: mpsc::Receiver<DatabaseMessage>) {
     };
     
     // Process messages
+    let mut shutdown_ack = None;
     while let Some(msg) = rx.recv().await {
         match msg {
             DatabaseMessage::LogBatch(logs) => {
@@ -84,31 +106,31 @@ async fn database_worker(mut rx: mpsc::Receiver<DatabaseMessage>) {
                     }
                 }
             }
-            DatabaseMessage::Shutdown => {
+            DatabaseMessage::LogBackruns(observations) => {
+                if let Some(ref pool) = pool {
+                    if let Err(e) = insert_backrun_batch(pool, observations).await {
+                        error!("Failed to insert backrun batch: {}", e);
+                    }
+                }
+            }
+            DatabaseMessage::Shutdown(done) => {
+                // Stop accepting batches but still write the ones already queued
                 info!("Database worker shutting down");
-                break;
+                rx.close();
+                shutdown_ack = Some(done);
             }
         }
     }
     
     info!("Database worker thread stopped");
+    if let Some(done) = shutdown_ack {
+        let _ = done.send(());
+    }
 }
 
 /// Create PostgreSQL connection pool
-async fn create_pool() -> eyre::Result<Pool> {
-    // Load configuration from environment
-    let host = std::env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".to_string());
-    let port = std::env::var("POSTGRES_PORT")
-        .unwrap_or_else(|_| "5432".to_string())
-        .parse::<u16>()
-        .unwrap_or(5432);
-    let user = std::env::var("POSTGRES_USER").unwrap_or_else(|_| "backrunner".to_string());
-    let password = std::env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "backrunner_password".to_string());
-    let dbname = std::env::var("POSTGRES_DB").unwrap_or_else(|_| "backrunner_db".to_string());
-    let pool_size = std::env::var("POSTGRES_POOL_SIZE")
-        .unwrap_or_else(|_| "10".to_string())
-        .parse::<usize>()
-        .unwrap_or(10);
+async fn create_pool(config: PostgresSection) -> eyre::Result<Pool> {
+    let PostgresSection { host, port, user, password, dbname, pool_size } = config;
     
     debug!(
         host = %host,
@@ -129,6 +151,7 @@ async fn create_pool() -> eyre::Result<Pool> {
     cfg.manager = Some(ManagerConfig {
         recycling_method: RecyclingMethod::Fast,
     });
+    cfg.pool = Some(PoolConfig::new(pool_size));
     
     // Create the pool
     let pool = cfg.create_pool(Some(Runtime::Tokio1), NoTls)?;
@@ -184,4 +207,64 @@ async fn insert_transaction_batch(pool: &Pool, logs: Vec<TransactionLog>) -> eyr
     );
     
     Ok(())
-}
\ No newline at end of file
+}
+/// Create the competitor backrun table if it does not exist yet
+async fn ensure_backrun_table(pool: &Pool) -> eyre::Result<()> {
+    let client = pool.get().await?;
+    client.batch_execute(
+        "CREATE TABLE IF NOT EXISTS competitor_backruns (
+           id BIGSERIAL PRIMARY KEY,
+           config_name VARCHAR(100) NOT NULL,
+           block_number BIGINT NOT NULL,
+           flashblock_index INTEGER NOT NULL,
+           trigger_hash VARCHAR(66) NOT NULL,
+           backrun_hash VARCHAR(66) NOT NULL UNIQUE,
+           sender VARCHAR(42) NOT NULL,
+           contract VARCHAR(42),
+           priority_fee NUMERIC NOT NULL,
+           position INTEGER NOT NULL,
+           gap INTEGER NOT NULL,
+           is_ours BOOLEAN NOT NULL,
+           observed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+         );
+         CREATE INDEX IF NOT EXISTS competitor_backruns_config_idx
+           ON competitor_backruns (config_name, block_number);"
+    ).await?;
+    Ok(())
+}
+
+/// Insert a batch of backrun observations into the database
+async fn insert_backrun_batch(pool: &Pool, observations: Vec<BackrunObservation>) -> eyre::Result<()> {
+    let batch_size = observations.len();
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+    
+    let stmt = tx.prepare(
+        "INSERT INTO competitor_backruns
+           (config_name, block_number, flashblock_index, trigger_hash, backrun_hash,
+            sender, contract, priority_fee, position, gap, is_ours)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8::TEXT::NUMERIC, $9, $10, $11)
+         ON CONFLICT (backrun_hash) DO NOTHING"
+    ).await?;
+    
+    for obs in observations {
+        tx.execute(&stmt, &[
+            &obs.config_name,
+            &(obs.block_number as i64),
+            &(obs.flashblock_index as i32),
+            &format!("{:?}", obs.trigger_hash),
+            &format!("{:?}", obs.backrun_hash),
+            &format!("{:?}", obs.sender),
+            &obs.contract.map(|c| format!("{:?}", c)),
+            &obs.priority_fee.to_string(),
+            &(obs.position as i32),
+            &(obs.gap as i32),
+            &obs.is_ours,
+        ]).await?;
+    }
+    
+    tx.commit().await?;
+    debug!(batch_size = batch_size, "Inserted backrun batch");
+    
+    Ok(())
+}
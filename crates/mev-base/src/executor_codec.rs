@@ -0,0 +1,219 @@
+//! Wire format of the on-chain executor contracts.
+//!
+//! Backrun executors take a packed quantity as calldata and read the gas estimate and bribe
+//! rate from `msg.value`. Processor configs name the codec they were deployed against, so a
+//! new executor layout can be rolled out per target.
+
+use alloy_primitives::{Bytes, U256};
+
+/// Bribe rate used when the config has no balance check or the check fails
+pub const DEFAULT_BRIBE_RATE: u16 = 500;
+
+/// Executor protocol revision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodecVersion {
+    /// Tagged quantity calldata, `(gas / 10) << 16 | bribe_rate` value
+    V1,
+}
+
+/// Calldata layout for the quantity argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalldataFormat {
+    /// `0x00 ‖ uint24 quantity` (4 bytes)
+    Short,
+    /// `0x01 ‖ uint128 quantity` (17 bytes), for quantities above the 24-bit range
+    Long,
+}
+
+impl CalldataFormat {
+    const SHORT_TAG: u8 = 0x00;
+    const LONG_TAG: u8 = 0x01;
+
+    /// Bytes used for the quantity after the tag byte
+    const fn quantity_bytes(self) -> usize {
+        match self {
+            Self::Short => 3,
+            Self::Long => 16,
+        }
+    }
+
+    const fn tag(self) -> u8 {
+        match self {
+            Self::Short => Self::SHORT_TAG,
+            Self::Long => Self::LONG_TAG,
+        }
+    }
+}
+
+/// Errors decoding executor calldata or encoding an out-of-range quantity
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CodecError {
+    #[error("quantity {quantity} exceeds the {format:?} format maximum {max}")]
+    QuantityOutOfRange { format: CalldataFormat, quantity: U256, max: U256 },
+    #[error("expected {expected} bytes of {format:?} calldata, got {actual}")]
+    InvalidLength { format: CalldataFormat, expected: usize, actual: usize },
+    #[error("unknown calldata tag 0x{0:02x}")]
+    UnknownTag(u8),
+    #[error("gas estimate {0} does not fit the value field")]
+    GasOutOfRange(u64),
+}
+
+/// Fields packed into the transaction value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedValue {
+    /// Gas the executor should account for; carried at a granularity of 10
+    pub gas_estimate: u64,
+    /// Share of profit paid as bribe, as read by the executor
+    pub bribe_rate: u16,
+}
+
+impl PackedValue {
+    /// Take the bribe rate from the low two bytes of an ERC20 balance
+    ///
+    /// Operators steer the bribe by holding a token amount whose last digits encode the rate.
+    pub fn bribe_rate_from_balance(balance: U256) -> u16 {
+        (balance.as_limbs()[0] & 0xffff) as u16
+    }
+}
+
+/// Codec a processor config targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutorCodec {
+    pub version: CodecVersion,
+    pub format: CalldataFormat,
+}
+
+impl ExecutorCodec {
+    pub const V1_SHORT: Self = Self { version: CodecVersion::V1, format: CalldataFormat::Short };
+    pub const V1_LONG: Self = Self { version: CodecVersion::V1, format: CalldataFormat::Long };
+
+    /// Codec by its config name, e.g. `v1-short`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "v1-short" => Some(Self::V1_SHORT),
+            "v1-long" => Some(Self::V1_LONG),
+            _ => None,
+        }
+    }
+
+    /// Largest quantity the calldata format can carry
+    pub fn max_quantity(&self) -> U256 {
+        (U256::from(1) << (self.format.quantity_bytes() * 8)) - U256::from(1)
+    }
+
+    /// Calldata for a quantity
+    pub fn encode_calldata(&self, quantity: U256) -> Result<Bytes, CodecError> {
+        let max = self.max_quantity();
+        if quantity > max {
+            return Err(CodecError::QuantityOutOfRange { format: self.format, quantity, max });
+        }
+        let width = self.format.quantity_bytes();
+        let quantity_bytes = quantity.to_be_bytes::<32>();
+        let mut calldata = Vec::with_capacity(1 + width);
+        calldata.push(self.format.tag());
+        calldata.extend_from_slice(&quantity_bytes[32 - width..]);
+        Ok(calldata.into())
+    }
+
+    /// Quantity carried by calldata in this codec's format
+    pub fn decode_calldata(&self, calldata: &[u8]) -> Result<U256, CodecError> {
+        let width = self.format.quantity_bytes();
+        let Some((&tag, quantity)) = calldata.split_first() else {
+            return Err(CodecError::InvalidLength { format: self.format, expected: 1 + width, actual: 0 });
+        };
+        if tag != self.format.tag() {
+            return Err(CodecError::UnknownTag(tag));
+        }
+        if quantity.len() != width {
+            return Err(CodecError::InvalidLength {
+                format: self.format,
+                expected: 1 + width,
+                actual: calldata.len(),
+            });
+        }
+        Ok(U256::from_be_slice(quantity))
+    }
+
+    /// Placeholder calldata used before a quantity is chosen
+    pub fn calldata_template(&self) -> Bytes {
+        self.encode_calldata(U256::ZERO).expect("zero fits every format")
+    }
+
+    /// Transaction value carrying the packed fields
+    pub fn encode_value(&self, value: PackedValue) -> Result<U256, CodecError> {
+        match self.version {
+            CodecVersion::V1 => {
+                let gas = value.gas_estimate / 10;
+                // Keep the packed value within 64 bits like the executor's decoder
+                if gas > (u64::MAX >> 16) {
+                    return Err(CodecError::GasOutOfRange(value.gas_estimate));
+                }
+                Ok(U256::from((gas << 16) | value.bribe_rate as u64))
+            }
+        }
+    }
+
+    /// Fields packed into a transaction value
+    pub fn decode_value(&self, value: U256) -> PackedValue {
+        match self.version {
+            CodecVersion::V1 => {
+                let raw = value.as_limbs()[0];
+                PackedValue { gas_estimate: (raw >> 16) * 10, bribe_rate: (raw & 0xffff) as u16 }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_calldata_matches_executor_layout() {
+        let codec = ExecutorCodec::V1_SHORT;
+        let calldata = codec.encode_calldata(U256::from(0x123456)).unwrap();
+        assert_eq!(calldata.as_ref(), &[0x00, 0x12, 0x34, 0x56]);
+        assert_eq!(codec.decode_calldata(&calldata).unwrap(), U256::from(0x123456));
+        assert_eq!(codec.max_quantity(), U256::from(0xffffff));
+        assert!(matches!(
+            codec.encode_calldata(U256::from(0x1000000)),
+            Err(CodecError::QuantityOutOfRange { .. })
+        ));
+        assert_eq!(codec.decode_calldata(&[0x01, 0x12, 0x34, 0x56]), Err(CodecError::UnknownTag(0x01)));
+    }
+
+    #[test]
+    fn test_long_calldata_round_trip() {
+        let codec = ExecutorCodec::V1_LONG;
+        for quantity in [U256::ZERO, U256::from(0x1000000u64), U256::from(u128::MAX)] {
+            let calldata = codec.encode_calldata(quantity).unwrap();
+            assert_eq!(calldata.len(), 17);
+            assert_eq!(calldata[0], 0x01);
+            assert_eq!(codec.decode_calldata(&calldata).unwrap(), quantity);
+        }
+        assert!(matches!(
+            codec.encode_calldata(U256::from(u128::MAX) + U256::from(1)),
+            Err(CodecError::QuantityOutOfRange { .. })
+        ));
+        assert_eq!(
+            codec.decode_calldata(&[0x01, 0x12, 0x34, 0x56]),
+            Err(CodecError::InvalidLength { format: CalldataFormat::Long, expected: 17, actual: 4 })
+        );
+        assert_eq!(
+            ExecutorCodec::V1_SHORT.decode_calldata(&codec.calldata_template()),
+            Err(CodecError::UnknownTag(0x01))
+        );
+        assert_eq!(ExecutorCodec::from_name("v1-long"), Some(ExecutorCodec::V1_LONG));
+        assert_eq!(ExecutorCodec::from_name("v2-short"), None);
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        let codec = ExecutorCodec::V1_SHORT;
+        let packed = PackedValue { gas_estimate: 184_320, bribe_rate: 500 };
+        let value = codec.encode_value(packed).unwrap();
+        assert_eq!(value, U256::from((18_432u64 << 16) | 500));
+        assert_eq!(codec.decode_value(value), packed);
+        assert_eq!(PackedValue::bribe_rate_from_balance(U256::from(0x0102_01f4u64)), 500);
+    }
+}
@@ -0,0 +1,456 @@
+use alloy_primitives::{B256, U256};
+use dashmap::DashMap;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::competitor_analyzer::BackrunObservation;
+use crate::flashblocks::FlashblocksEvent;
+use crate::redis_client::{RedisCommandError, RedisHandle};
+
+/// Neutral multiplier (1x) in the 5-digit fixed point format used by `mev:multiplier:<strategy>`
+pub const NEUTRAL_MULTIPLIER: u32 = 10000;
+
+/// Measured outcome of a submitted transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeeOutcome {
+    /// Landed on chain and paid for itself
+    Won,
+    /// Not included within the inclusion window while a competitor backran the same config
+    LostToCompetitor,
+    /// Landed, but reverted or the fee paid exceeded the expected profit
+    LandedUnprofitable,
+}
+
+/// Configuration for the fee multiplier controller
+#[derive(Debug, Clone)]
+pub struct FeeControllerConfig {
+    pub enabled: bool,
+    /// Lower bound for the multiplier (2000 = 0.2x)
+    pub min_multiplier: u32,
+    /// Upper bound for the multiplier (100000 = 10x)
+    pub max_multiplier: u32,
+    /// Step subtracted after a win (we probably overpaid)
+    pub win_step: u32,
+    /// Step added after losing to a competitor
+    pub loss_step: u32,
+    /// Step subtracted after landing unprofitably
+    pub unprofitable_step: u32,
+    /// Blocks after the opportunity block before a pending submission counts as lost
+    pub inclusion_window_blocks: u64,
+    /// Number of audit entries kept per strategy (at least 1)
+    pub history_len: isize,
+}
+
+impl Default for FeeControllerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_multiplier: 2000,
+            max_multiplier: 100000,
+            win_step: 250,
+            loss_step: 1000,
+            unprofitable_step: 1000,
+            inclusion_window_blocks: 2,
+            history_len: 500,
+        }
+    }
+}
+
+/// Signed multiplier change for an outcome
+fn outcome_step(outcome: FeeOutcome, config: &FeeControllerConfig) -> i64 {
+    match outcome {
+        FeeOutcome::Won => -(config.win_step as i64),
+        FeeOutcome::LostToCompetitor => config.loss_step as i64,
+        FeeOutcome::LandedUnprofitable => -(config.unprofitable_step as i64),
+    }
+}
+
+/// Read, step, clamp and write the multiplier in one round trip so concurrent instances
+/// sharing a Redis never overwrite each other's updates. Returns `{old, new}`.
+///
+/// This is the only implementation of the update rule; `test_step_script_against_redis`
+/// exercises it against a live Redis.
+///
+/// KEYS[1] multiplier key; ARGV: neutral, min, max, step
+const STEP_MULTIPLIER_SCRIPT: &str = r#"
+local min = tonumber(ARGV[2])
+local max = tonumber(ARGV[3])
+local raw = redis.call('GET', KEYS[1])
+local current = raw and tonumber(raw) or tonumber(ARGV[1])
+current = math.max(min, math.min(max, current))
+local next = math.max(min, math.min(max, current + tonumber(ARGV[4])))
+redis.call('SET', KEYS[1], next)
+return {current, next}
+"#;
+
+/// One of our transactions in a submitted bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackedLeg {
+    pub tx_hash: B256,
+    /// Fallback price when the receipt carries no `effectiveGasPrice`
+    pub max_fee_per_gas: u128,
+}
+
+/// A submitted bundle waiting for an outcome
+#[derive(Debug, Clone)]
+struct PendingBundle {
+    strategy: String,
+    block_number: u64,
+    expected_profit: U256,
+    /// Our legs not seen on chain yet, with their max fee per gas
+    outstanding: HashMap<B256, u128>,
+    /// Fees paid by the legs that already landed
+    fee_paid: U256,
+    /// Whether any landed leg reverted
+    reverted: bool,
+}
+
+impl PendingBundle {
+    /// Outcome once every leg landed: the bundle pays for itself only if the fees of all its
+    /// legs stay below the expected profit
+    fn landed_outcome(&self) -> FeeOutcome {
+        if self.reverted || self.fee_paid >= self.expected_profit {
+            FeeOutcome::LandedUnprofitable
+        } else {
+            FeeOutcome::Won
+        }
+    }
+}
+
+/// Audit entry stored in `mev:multiplier:history:<strategy>`
+#[derive(Debug, Serialize, Deserialize)]
+struct MultiplierAuditEntry {
+    timestamp: String,
+    outcome: FeeOutcome,
+    old_multiplier: u32,
+    new_multiplier: u32,
+    tx_hash: String,
+    block_number: u64,
+}
+
+/// Closed-loop controller that tunes `mev:multiplier:<strategy>` from submission outcomes
+pub struct FeeMultiplierController {
+    config: FeeControllerConfig,
+    redis: Arc<RedisHandle>,
+    /// Bundles keyed by their last leg, the one the audit history names
+    pending: DashMap<B256, PendingBundle>,
+    /// Bundle key for every outstanding leg
+    leg_bundles: DashMap<B256, B256>,
+    /// Blocks in which another searcher landed a backrun, per strategy
+    competitor_blocks: DashMap<String, VecDeque<u64>>,
+    step_script: redis::Script,
+}
+
+impl FeeMultiplierController {
+    /// Create a new controller on the shared Redis handle
+    pub fn new(config: FeeControllerConfig, redis: Arc<RedisHandle>) -> Self {
+        Self {
+            config,
+            redis,
+            pending: DashMap::new(),
+            leg_bundles: DashMap::new(),
+            competitor_blocks: DashMap::new(),
+            step_script: redis::Script::new(STEP_MULTIPLIER_SCRIPT),
+        }
+    }
+
+    /// Record competitor backruns so expired submissions can be attributed to them
+    pub fn observe_backruns(&self, observations: &[BackrunObservation]) {
+        if !self.config.enabled {
+            return;
+        }
+        for observation in observations.iter().filter(|o| !o.is_ours) {
+            // Submissions carry the worker's strategy name for the config
+            let strategy = format!("Backrun_{}", observation.config_name);
+            let mut blocks = self.competitor_blocks.entry(strategy).or_default();
+            if blocks.back() != Some(&observation.block_number) {
+                blocks.push_back(observation.block_number);
+            }
+        }
+    }
+
+    /// Whether a competitor landed a backrun for the strategy within `[from, to]`
+    fn competitor_landed(&self, strategy: &str, from: u64, to: u64) -> bool {
+        self.competitor_blocks
+            .get(strategy)
+            .is_some_and(|blocks| blocks.iter().any(|block| (from..=to).contains(block)))
+    }
+
+    /// Register the legs we sent for a bundle so its outcome can be measured
+    pub fn track_submission(&self, legs: &[TrackedLeg], strategy: &str, block_number: u64, expected_profit: U256) {
+        if !self.config.enabled {
+            return;
+        }
+        let Some(last) = legs.last() else { return };
+        for leg in legs {
+            self.leg_bundles.insert(leg.tx_hash, last.tx_hash);
+        }
+        self.pending.insert(last.tx_hash, PendingBundle {
+            strategy: strategy.to_string(),
+            block_number,
+            expected_profit,
+            outstanding: legs.iter().map(|leg| (leg.tx_hash, leg.max_fee_per_gas)).collect(),
+            fee_paid: U256::ZERO,
+            reverted: false,
+        });
+    }
+
+    /// Resolve pending submissions against a new flashblock
+    pub async fn observe_flashblock(&self, event: &FlashblocksEvent) {
+        if !self.config.enabled || self.pending.is_empty() {
+            return;
+        }
+
+        let mut resolved = Vec::new();
+
+        for tx in &event.transactions {
+            let hash = *tx.tx_hash();
+            let Some((_, bundle_key)) = self.leg_bundles.remove(&hash) else { continue };
+            let complete = match self.pending.get_mut(&bundle_key) {
+                Some(mut bundle) => {
+                    if let Some(max_fee_per_gas) = bundle.outstanding.remove(&hash) {
+                        let receipt = event.metadata.receipts.get(&format!("{:?}", hash));
+                        let (reverted, fee_paid) = landed_leg(receipt, max_fee_per_gas);
+                        bundle.reverted |= reverted;
+                        bundle.fee_paid += fee_paid;
+                    }
+                    bundle.outstanding.is_empty()
+                }
+                None => false,
+            };
+            if complete {
+                if let Some((_, bundle)) = self.pending.remove(&bundle_key) {
+                    let outcome = bundle.landed_outcome();
+                    resolved.push((bundle_key, bundle, outcome));
+                }
+            }
+        }
+
+        // Past the inclusion window a bundle only counts as lost when a competitor landed on the
+        // same target; drops, cancellations and rejections say nothing about our fee
+        let window = self.config.inclusion_window_blocks;
+        let expired: Vec<B256> = self.pending.iter()
+            .filter(|entry| entry.value().block_number + window < event.block_number)
+            .map(|entry| *entry.key())
+            .collect();
+        for hash in expired {
+            if let Some((_, bundle)) = self.pending.remove(&hash) {
+                for leg in bundle.outstanding.keys() {
+                    self.leg_bundles.remove(leg);
+                }
+                let deadline = bundle.block_number + window;
+                if self.competitor_landed(&bundle.strategy, bundle.block_number, deadline) {
+                    resolved.push((hash, bundle, FeeOutcome::LostToCompetitor));
+                } else {
+                    debug!(
+                        strategy = %bundle.strategy,
+                        tx_hash = %hash,
+                        legs_outstanding = bundle.outstanding.len(),
+                        "Submission expired without a competitor landing - not tuning"
+                    );
+                }
+            }
+        }
+
+        // Competitor landings older than any open window can no longer decide an outcome
+        let horizon = event.block_number.saturating_sub(window + 1);
+        self.competitor_blocks.retain(|_, blocks| {
+            while blocks.front().is_some_and(|block| *block < horizon) {
+                blocks.pop_front();
+            }
+            !blocks.is_empty()
+        });
+
+        for (hash, bundle, outcome) in resolved {
+            self.record_outcome(&bundle.strategy, outcome, hash, event.block_number).await;
+        }
+    }
+
+    /// Apply an outcome to a strategy's multiplier, write it back and append to the audit history
+    pub async fn record_outcome(&self, strategy: &str, outcome: FeeOutcome, tx_hash: B256, block_number: u64) -> Option<u32> {
+        let key = self.redis.key(&format!("multiplier:{}", strategy));
+        let mut invocation = self.step_script.key(key);
+        invocation
+            .arg(NEUTRAL_MULTIPLIER)
+            .arg(self.config.min_multiplier)
+            .arg(self.config.max_multiplier)
+            .arg(outcome_step(outcome, &self.config));
+        let (current, new_multiplier) = match self.redis
+            .run(|mut conn| async move {
+                let values: (u32, u32) = invocation.invoke_async(&mut conn).await?;
+                Ok(values)
+            })
+            .await
+        {
+            Ok(values) => values,
+            Err(RedisCommandError::Unavailable) => return None,
+            Err(e) => {
+                warn!(strategy = strategy, error = %e, "Failed to update multiplier for fee tuning");
+                return None;
+            }
+        };
+
+        let entry = MultiplierAuditEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            outcome,
+            old_multiplier: current,
+            new_multiplier,
+            tx_hash: format!("{:?}", tx_hash),
+            block_number,
+        };
+        let history_key = self.redis.key(&format!("multiplier:history:{}", strategy));
+        let history_len = self.config.history_len.max(1);
+        if let Ok(json) = serde_json::to_string(&entry) {
+            let _ = self.redis.run(|mut conn| async move {
+                conn.lpush::<_, _, ()>(&history_key, json).await?;
+                conn.ltrim::<_, ()>(&history_key, 0, history_len - 1).await
+            }).await;
+        }
+
+        crate::metrics::MEV_METRICS.fee_multiplier_updates_total.increment(1);
+
+        info!(
+            strategy = strategy,
+            outcome = ?outcome,
+            tx_hash = %tx_hash,
+            old_multiplier = format!("{}x", current as f64 / 10000.0),
+            new_multiplier = format!("{}x", new_multiplier as f64 / 10000.0),
+            "Tuned fee multiplier from submission outcome"
+        );
+
+        Some(new_multiplier)
+    }
+}
+
+/// Whether a landed leg reverted and the fee it paid, from its flashblock receipt if present
+///
+/// The fee is gas used x `effectiveGasPrice`; receipts without the price fall back to the max
+/// fee per gas, an upper bound.
+fn landed_leg(receipt: Option<&serde_json::Value>, max_fee_per_gas: u128) -> (bool, U256) {
+    let Some(receipt) = receipt else {
+        return (false, U256::ZERO);
+    };
+    let reverted = receipt_field(receipt, "status").and_then(parse_hex_u64) == Some(0);
+    let fee_paid = receipt_field(receipt, "gasUsed").and_then(parse_hex_u64)
+        .map(|gas_used| {
+            let gas_price = receipt_field(receipt, "effectiveGasPrice")
+                .and_then(parse_hex_u64)
+                .map(u128::from)
+                .unwrap_or(max_fee_per_gas);
+            U256::from(gas_used) * U256::from(gas_price)
+        })
+        .unwrap_or_default();
+    (reverted, fee_paid)
+}
+
+/// Look up a receipt field, allowing for a single level of typed-receipt wrapping
+pub(crate) fn receipt_field<'a>(receipt: &'a serde_json::Value, field: &str) -> Option<&'a serde_json::Value> {
+    receipt.get(field).or_else(|| {
+        receipt.as_object()?.values().find_map(|inner| inner.get(field))
+    })
+}
+
+pub(crate) fn parse_hex_u64(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::String(s) => u64::from_str_radix(s.trim_start_matches("0x"), 16).ok(),
+        serde_json::Value::Bool(b) => Some(*b as u64),
+        other => other.as_u64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore] // Needs a Redis: REDIS_URL=redis://localhost:6379/ cargo test -p mevbase step_script -- --ignored
+    async fn test_step_script_against_redis() {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379/".to_string());
+        let redis = RedisHandle::connect(crate::redis_client::RedisClientConfig {
+            url,
+            namespace: format!("mev:test:{}", chrono::Utc::now().timestamp_micros()),
+            ..Default::default()
+        });
+        while redis.health() != crate::redis_client::RedisHealth::Healthy {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        let controller = FeeMultiplierController::new(FeeControllerConfig { enabled: true, ..Default::default() }, redis);
+        let step = |strategy: &'static str, outcome| controller.record_outcome(strategy, outcome, B256::ZERO, 1);
+
+        // Unset multipliers start at neutral
+        assert_eq!(step("Steps", FeeOutcome::Won).await, Some(9750));
+        assert_eq!(step("Steps", FeeOutcome::LostToCompetitor).await, Some(10750));
+        assert_eq!(step("Steps", FeeOutcome::LandedUnprofitable).await, Some(9750));
+
+        // Clamped to the configured bounds
+        for _ in 0..10 {
+            step("Floor", FeeOutcome::LandedUnprofitable).await;
+        }
+        assert_eq!(step("Floor", FeeOutcome::LandedUnprofitable).await, Some(2000));
+        for _ in 0..90 {
+            step("Ceiling", FeeOutcome::LostToCompetitor).await;
+        }
+        assert_eq!(step("Ceiling", FeeOutcome::LostToCompetitor).await, Some(100000));
+    }
+
+    #[test]
+    fn test_bundle_outcome_counts_every_leg_fee() {
+        // 21000 gas at an effective 1 gwei, though the max fee allowed 10 gwei
+        let receipt = serde_json::json!({ "status": "0x1", "gasUsed": "0x5208", "effectiveGasPrice": "0x3b9aca00" });
+        assert_eq!(landed_leg(Some(&receipt), 10_000_000_000), (false, U256::from(21_000_000_000_000u64)));
+        let without_price = serde_json::json!({ "status": "0x0", "gasUsed": "0x5208" });
+        assert_eq!(landed_leg(Some(&without_price), 2), (true, U256::from(42_000)));
+
+        let mut bundle = PendingBundle {
+            strategy: "Backrun_WETH_USDC".to_string(),
+            block_number: 100,
+            expected_profit: U256::from(30_000_000_000_000u64),
+            outstanding: HashMap::new(),
+            fee_paid: U256::ZERO,
+            reverted: false,
+        };
+        bundle.fee_paid += landed_leg(Some(&receipt), 0).1;
+        assert_eq!(bundle.landed_outcome(), FeeOutcome::Won);
+        // A second leg's fee tips the bundle past its profit
+        bundle.fee_paid += landed_leg(Some(&receipt), 0).1;
+        assert_eq!(bundle.landed_outcome(), FeeOutcome::LandedUnprofitable);
+    }
+
+    #[test]
+    fn test_expiry_needs_competitor_on_same_target() {
+        let config = FeeControllerConfig { enabled: true, ..Default::default() };
+        let controller = FeeMultiplierController::new(
+            config,
+            Arc::new(RedisHandle::disconnected(Default::default())),
+        );
+        let observation = |config_name: &str, block_number, is_ours| BackrunObservation {
+            config_name: config_name.to_string(),
+            block_number,
+            flashblock_index: 0,
+            trigger_hash: B256::ZERO,
+            backrun_hash: B256::ZERO,
+            sender: Default::default(),
+            contract: None,
+            priority_fee: 0,
+            position: 0,
+            gap: 0,
+            is_ours,
+        };
+        controller.observe_backruns(&[observation("WETH_USDC", 101, true), observation("cbBTC_USDC", 101, false)]);
+        assert!(!controller.competitor_landed("Backrun_WETH_USDC", 100, 102));
+
+        controller.observe_backruns(&[observation("WETH_USDC", 102, false)]);
+        assert!(controller.competitor_landed("Backrun_WETH_USDC", 100, 102));
+        assert!(!controller.competitor_landed("Backrun_WETH_USDC", 103, 105));
+    }
+
+    #[test]
+    fn test_receipt_field_wrapped() {
+        let receipt = serde_json::json!({ "Eip1559": { "status": "0x0", "gasUsed": "0x5208" } });
+        assert_eq!(receipt_field(&receipt, "status").and_then(parse_hex_u64), Some(0));
+        assert_eq!(receipt_field(&receipt, "gasUsed").and_then(parse_hex_u64), Some(21000));
+    }
+}
@@ -1,6 +1,8 @@
 use alloy_primitives::{Address, U256, B256};
 use std::collections::HashMap;
 use revm::{state::AccountInfo, bytecode::Bytecode};
+use revm::database::{DbAccount, AccountState};
+use reth_revm::db::CacheDB;
 use alloy_consensus::TxEnvelope;
 
 /// Snapshot of state changes from flashblocks
@@ -78,4 +80,36 @@ impl FlashblockStateSnapshot {
     pub fn age_ms(&self) -> u64 {
         self.snapshot_time.elapsed().as_millis() as u64
     }
-}
\ No newline at end of file
+    
+    /// Apply this snapshot on top of a CacheDB (accounts, storage and code)
+    pub fn apply_to<DB>(&self, cache_db: &mut CacheDB<DB>) {
+        for (address, account_info) in &self.account_changes {
+            match cache_db.cache.accounts.entry(*address) {
+                std::collections::hash_map::Entry::Occupied(mut entry) => {
+                    let db_account = entry.get_mut();
+                    db_account.info = account_info.clone();
+                    db_account.account_state = AccountState::Touched;
+                }
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert(DbAccount {
+                        info: account_info.clone(),
+                        account_state: AccountState::Touched,
+                        storage: Default::default(),
+                    });
+                }
+            }
+        }
+        
+        for (address, storage_changes) in &self.storage_changes {
+            if let Some(db_account) = cache_db.cache.accounts.get_mut(address) {
+                for (slot, value) in storage_changes {
+                    db_account.storage.insert(*slot, *value);
+                }
+            }
+        }
+        
+        for (code_hash, bytecode) in &self.code_changes {
+            cache_db.cache.contracts.insert(*code_hash, bytecode.clone());
+        }
+    }
+}
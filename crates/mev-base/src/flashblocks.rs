@@ -65,7 +65,7 @@ impl FlashblocksClient {
     /// Start the websocket connection and event processing
     pub async fn start(&mut self) -> eyre::Result<()> {
         let url = Url::parse(&self.ws_url)?;
-        info!("Connecting to Flashblocks WebSocket at {}", url);
+        info!("Connecting to Flashblocks WebSocket at {}", crate::config::redact_url(url.as_str()));
         
         let _sender = self.sender.clone();
         let event_sender_clone = self.event_sender.clone();
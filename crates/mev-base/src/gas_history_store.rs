@@ -1,161 +1,499 @@
-use redis::aio::ConnectionManager;
-use redis::{AsyncCommands, Client as RedisClient};
+//! Learned per-target gas and bound history.
+//!
+//! The store keeps every record in memory and writes through to a pluggable backend, so an
+//! unreachable backend never resets what the optimizer has learned during this run.
+
+use alloy_primitives::{Address, U256};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, warn};
-use alloy_primitives::Address;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio_util::task::TaskTracker;
+use tracing::{debug, info, warn};
 
-/// Store for tracking gas usage history per target address
-pub struct GasHistoryStore {
-    redis_conn: Arc<RwLock<Option<ConnectionManager>>>,
-    key_prefix: String,
+use crate::bounds_controller::BoundsState;
+use crate::redis_client::RedisHandle;
+
+/// Current [`GasHistoryRecord`] layout
+pub const GAS_HISTORY_VERSION: u32 = 3;
+
+/// Winning quantities kept per target
+pub const RECENT_WINS_LEN: usize = 8;
+
+/// A winning quantity and the profit it produced, one point on the target's profit curve
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QtySample {
+    pub qty: U256,
+    pub profit: i128,
+    pub block_number: u64,
+    pub flashblock_index: u64,
 }
 
-impl GasHistoryStore {
-    /// Create a new gas history store
-    pub async fn new(redis_host: &str, redis_port: u16, redis_password: &str) -> Self {
-        let store = Self {
-            redis_conn: Arc::new(RwLock::new(None)),
-            key_prefix: "mev:gas:".to_string(),
-        };
+/// What the optimizer learned about one target contract
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasHistoryRecord {
+    /// Layout version; records written before versioning decode as 0
+    #[serde(default)]
+    pub version: u32,
+    /// IIR filtered gas usage
+    pub gas: u64,
+    /// Multiplier of the default quantity the upper bound was set to
+    #[serde(default)]
+    pub multiplier: Option<u64>,
+    /// Bounds controller state (added in version 3)
+    #[serde(default)]
+    pub bounds: Option<BoundsState>,
+    /// Most profitable quantity found by the last optimization
+    #[serde(default)]
+    pub last_best_qty: Option<U256>,
+    /// Recent winning quantities, oldest first (added in version 2)
+    #[serde(default)]
+    pub recent_wins: Vec<QtySample>,
+    /// Optimizations folded into this record
+    #[serde(default)]
+    pub sample_count: u64,
+    /// Unix seconds of the last update
+    #[serde(default)]
+    pub updated_at: u64,
+}
 
-        // Initialize Redis connection asynchronously
-        let redis_conn_clone = store.redis_conn.clone();
-        let redis_url = if redis_password.is_empty() {
-            format!("redis://{}:{}/", redis_host, redis_port)
-        } else {
-            format!("redis://:{}@{}:{}/", redis_password, redis_host, redis_port)
-        };
+impl GasHistoryRecord {
+    /// Decode a stored value, accepting the unversioned JSON and plain-number formats
+    pub fn decode(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        if raw.starts_with('{') {
+            return serde_json::from_str(raw).ok();
+        }
+        raw.parse::<u64>().ok().map(|gas| Self {
+            version: 0,
+            gas,
+            multiplier: None,
+            bounds: None,
+            last_best_qty: None,
+            recent_wins: Vec::new(),
+            sample_count: 0,
+            updated_at: 0,
+        })
+    }
 
-        tokio::spawn(async move {
-            match RedisClient::open(redis_url) {
-                Ok(client) => {
-                    match ConnectionManager::new(client).await {
-                        Ok(conn) => {
-                            debug!("Successfully connected to Redis for gas history");
-                            *redis_conn_clone.write().await = Some(conn);
-                        }
-                        Err(e) => {
-                            warn!("Failed to create Redis connection manager for gas history: {}", e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    warn!("Failed to connect to Redis for gas history: {}", e);
-                }
-            }
-        });
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).expect("gas history record serializes")
+    }
 
-        store
-    }
-
-    /// Get filtered gas value for a target address
-    pub async fn get_filtered_gas(&self, target: &Address) -> Option<u64> {
-        let conn_guard = self.redis_conn.read().await;
-        if let Some(conn) = conn_guard.as_ref() {
-            let mut conn = conn.clone();
-            let key = format!("{}{:?}", self.key_prefix, target);
-            match conn.get::<_, Option<String>>(&key).await {
-                Ok(Some(value)) => {
-                    // Try to parse as JSON first, fall back to plain number
-                    if value.starts_with('{') {
-                        // JSON format
-                        if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&value) {
-                            if let Some(gas) = parsed.get("gas").and_then(|v| v.as_u64()) {
-                                debug!(target = %target, filtered_gas = gas, "Retrieved gas history from Redis (JSON)");
-                                Some(gas)
-                            } else {
-                                warn!(target = %target, value = %value, "Invalid JSON gas value in Redis");
-                                None
-                            }
-                        } else {
-                            warn!(target = %target, value = %value, "Failed to parse JSON in Redis");
-                            None
-                        }
-                    } else {
-                        // Plain number format (backwards compatible)
-                        match value.parse::<u64>() {
-                            Ok(gas) => {
-                                debug!(target = %target, filtered_gas = gas, "Retrieved gas history from Redis");
-                                Some(gas)
-                            }
-                            Err(e) => {
-                                warn!(target = %target, error = %e, "Failed to parse gas value from Redis");
-                                None
-                            }
-                        }
-                    }
-                }
-                Ok(None) => {
-                    debug!(target = %target, "No gas history found in Redis");
-                    None
+    /// Fold a new optimization result into the record
+    fn updated(previous: Option<&Self>, gas: u64, bounds: Option<BoundsState>, win: Option<QtySample>) -> Self {
+        let mut recent_wins = previous.map(|p| p.recent_wins.clone()).unwrap_or_default();
+        if let Some(win) = &win {
+            recent_wins.push(win.clone());
+            let excess = recent_wins.len().saturating_sub(RECENT_WINS_LEN);
+            recent_wins.drain(..excess);
+        }
+        Self {
+            version: GAS_HISTORY_VERSION,
+            gas,
+            // Kept in step with the controller for tooling that reads the plain multiplier
+            multiplier: bounds.as_ref().map(BoundsState::whole_multiplier).or_else(|| previous.and_then(|p| p.multiplier)),
+            bounds: bounds.or_else(|| previous.and_then(|p| p.bounds.clone())),
+            last_best_qty: win.map(|w| w.qty).or_else(|| previous.and_then(|p| p.last_best_qty)),
+            recent_wins,
+            sample_count: previous.map_or(0, |p| p.sample_count).saturating_add(1),
+            updated_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+/// Where gas history records are persisted
+#[async_trait]
+pub trait GasHistoryBackend: Send + Sync {
+    /// Short name for logs
+    fn name(&self) -> &'static str;
+
+    async fn load(&self, target: &Address) -> eyre::Result<Option<GasHistoryRecord>>;
+
+    async fn store(&self, target: &Address, record: &GasHistoryRecord) -> eyre::Result<()>;
+
+    /// Make every stored record durable; called on shutdown
+    async fn flush(&self) -> eyre::Result<()> {
+        Ok(())
+    }
+}
+
+/// Records in the shared Redis, expiring after a TTL
+pub struct RedisGasHistory {
+    redis: Arc<RedisHandle>,
+    ttl: Duration,
+}
+
+impl RedisGasHistory {
+    pub fn new(redis: Arc<RedisHandle>, ttl: Duration) -> Self {
+        Self { redis, ttl }
+    }
+
+    fn key(&self, target: &Address) -> String {
+        self.redis.key(&format!("gas:{:?}", target))
+    }
+}
+
+#[async_trait]
+impl GasHistoryBackend for RedisGasHistory {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn load(&self, target: &Address) -> eyre::Result<Option<GasHistoryRecord>> {
+        let key = self.key(target);
+        let raw = self.redis.run(|mut conn| async move { conn.get::<_, Option<String>>(&key).await }).await?;
+        match raw {
+            Some(raw) => match GasHistoryRecord::decode(&raw) {
+                Some(record) => Ok(Some(record)),
+                None => Err(eyre::eyre!("undecodable gas history value {:?}", raw)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    async fn store(&self, target: &Address, record: &GasHistoryRecord) -> eyre::Result<()> {
+        let key = self.key(target);
+        let value = record.encode();
+        let ttl = self.ttl.as_secs().max(1);
+        self.redis.run(|mut conn| async move { conn.set_ex::<_, _, ()>(&key, value, ttl).await }).await?;
+        Ok(())
+    }
+}
+
+/// Records kept only for the life of the process
+#[derive(Default)]
+pub struct MemoryGasHistory {
+    records: DashMap<Address, GasHistoryRecord>,
+}
+
+#[async_trait]
+impl GasHistoryBackend for MemoryGasHistory {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn load(&self, target: &Address) -> eyre::Result<Option<GasHistoryRecord>> {
+        Ok(self.records.get(target).map(|r| r.clone()))
+    }
+
+    async fn store(&self, target: &Address, record: &GasHistoryRecord) -> eyre::Result<()> {
+        self.records.insert(*target, record.clone());
+        Ok(())
+    }
+}
+
+/// One line of the on-disk log
+#[derive(Serialize, Deserialize)]
+struct FileEntry {
+    target: Address,
+    record: GasHistoryRecord,
+}
+
+/// Lines the file may grow to per target before it is compacted again
+const FILE_COMPACT_RATIO: usize = 8;
+
+/// Targets assumed when computing the compaction threshold for a near-empty file
+const FILE_COMPACT_MIN_TARGETS: usize = 16;
+
+/// Records in a local append-only JSON lines file, compacted when opened and whenever it
+/// grows past [`FILE_COMPACT_RATIO`] lines per target
+///
+/// The latest line for a target wins, so a crash mid-write loses at most that one update.
+pub struct FileGasHistory {
+    state: Mutex<FileState>,
+}
+
+struct FileState {
+    path: PathBuf,
+    records: HashMap<Address, GasHistoryRecord>,
+    file: std::fs::File,
+    /// Lines in the file, including superseded ones
+    lines: usize,
+}
+
+impl FileState {
+    /// Rewrite the file to one line per target and continue appending to the new file
+    fn compact(&mut self) -> eyre::Result<()> {
+        self.file = compact_file(&self.path, &self.records)?;
+        self.lines = self.records.len();
+        Ok(())
+    }
+
+    fn needs_compaction(&self) -> bool {
+        self.lines > FILE_COMPACT_RATIO * self.records.len().max(FILE_COMPACT_MIN_TARGETS)
+    }
+}
+
+/// Write `records` to a temporary file, move it over `path` and open it for appending
+fn compact_file(path: &Path, records: &HashMap<Address, GasHistoryRecord>) -> eyre::Result<std::fs::File> {
+    let compacted = path.with_extension("compact");
+    {
+        let mut out = std::fs::File::create(&compacted)?;
+        for (target, record) in records {
+            writeln!(out, "{}", serde_json::to_string(&FileEntry { target: *target, record: record.clone() })?)?;
+        }
+        out.sync_all()?;
+    }
+    std::fs::rename(&compacted, path)?;
+    Ok(std::fs::OpenOptions::new().append(true).open(path)?)
+}
+
+impl FileGasHistory {
+    pub fn open(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut records = HashMap::new();
+        if path.exists() {
+            let reader = BufReader::new(std::fs::File::open(&path)?);
+            for (number, line) in reader.lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
                 }
-                Err(e) => {
-                    warn!(target = %target, error = %e, "Failed to get gas history from Redis");
-                    None
+                match serde_json::from_str::<FileEntry>(&line) {
+                    Ok(entry) => {
+                        records.insert(entry.target, entry.record);
+                    }
+                    // A torn final line from a crash is expected; anything else is worth a look
+                    Err(e) => warn!(path = %path.display(), line = number + 1, error = %e, "Skipping unreadable gas history line"),
                 }
             }
-        } else {
-            None
         }
+
+        // Compact to one line per target before appending
+        let file = compact_file(&path, &records)?;
+        info!(path = %path.display(), targets = records.len(), "Opened gas history file");
+        let lines = records.len();
+        Ok(Self { state: Mutex::new(FileState { path, records, file, lines }) })
     }
+}
 
-    /// Get multiplier value for a target address
-    pub async fn get_multiplier(&self, target: &Address) -> Option<u64> {
-        let conn_guard = self.redis_conn.read().await;
-        if let Some(conn) = conn_guard.as_ref() {
-            let mut conn = conn.clone();
-            let key = format!("{}{:?}", self.key_prefix, target);
-            match conn.get::<_, Option<String>>(&key).await {
-                Ok(Some(value)) => {
-                    if value.starts_with('{') {
-                        // JSON format with multiplier
-                        if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&value) {
-                            if let Some(mult) = parsed.get("multiplier").and_then(|v| v.as_u64()) {
-                                debug!(target = %target, multiplier = mult, "Retrieved multiplier from Redis");
-                                Some(mult)
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    } else {
-                        // Old format, no multiplier stored
-                        None
-                    }
-                }
-                _ => None,
+#[async_trait]
+impl GasHistoryBackend for FileGasHistory {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn load(&self, target: &Address) -> eyre::Result<Option<GasHistoryRecord>> {
+        Ok(self.state.lock().records.get(target).cloned())
+    }
+
+    async fn store(&self, target: &Address, record: &GasHistoryRecord) -> eyre::Result<()> {
+        let line = serde_json::to_string(&FileEntry { target: *target, record: record.clone() })?;
+        let mut state = self.state.lock();
+        writeln!(state.file, "{}", line)?;
+        state.lines += 1;
+        state.records.insert(*target, record.clone());
+        if state.needs_compaction() {
+            // The appended line is already on disk; a failed rewrite only defers compaction
+            if let Err(e) = state.compact() {
+                warn!(path = %state.path.display(), error = %e, "Failed to compact gas history file");
             }
-        } else {
-            None
         }
+        Ok(())
     }
-    
-    /// Set filtered gas value and multiplier for a target address with TTL of 24 hours
-    pub async fn set_filtered_gas_and_multiplier(&self, target: &Address, filtered_gas: u64, multiplier: Option<u64>) {
-        let conn_guard = self.redis_conn.read().await;
-        if let Some(conn) = conn_guard.as_ref() {
-            let mut conn = conn.clone();
-            let key = format!("{}{:?}", self.key_prefix, target);
-            
-            // Store as JSON with both gas and multiplier
-            let value = if let Some(mult) = multiplier {
-                format!("{{\"gas\":{},\"multiplier\":{}}}", filtered_gas, mult)
-            } else {
-                // Backwards compatible: just the gas value
-                filtered_gas.to_string()
-            };
-            
-            // Set with 24 hour TTL (86400 seconds)
-            match conn.set_ex::<_, _, ()>(&key, value, 86400).await {
-                Ok(_) => {
-                    debug!(target = %target, filtered_gas = filtered_gas, multiplier = ?multiplier, "Stored gas history in Redis");
-                }
-                Err(e) => {
-                    warn!(target = %target, error = %e, "Failed to store gas history in Redis");
-                }
+
+    async fn flush(&self) -> eyre::Result<()> {
+        self.state.lock().file.sync_data()?;
+        Ok(())
+    }
+}
+
+/// Which backend the store persists to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasHistoryBackendKind {
+    Redis,
+    Memory,
+    File,
+}
+
+impl GasHistoryBackendKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "redis" => Some(Self::Redis),
+            "memory" => Some(Self::Memory),
+            "file" => Some(Self::File),
+            _ => None,
+        }
+    }
+}
+
+/// Store for tracking gas usage history per target address
+pub struct GasHistoryStore {
+    backend: Arc<dyn GasHistoryBackend>,
+    /// Write-through copy of every record seen this run
+    cache: DashMap<Address, GasHistoryRecord>,
+    /// Serializes read-update-write of each target's record
+    record_locks: DashMap<Address, Arc<tokio::sync::Mutex<()>>>,
+    /// Background writes not yet persisted, awaited on shutdown
+    writes: TaskTracker,
+}
+
+impl GasHistoryStore {
+    pub fn new(backend: Arc<dyn GasHistoryBackend>) -> Self {
+        info!(backend = backend.name(), "Gas history store initialized");
+        Self { backend, cache: DashMap::new(), record_locks: DashMap::new(), writes: TaskTracker::new() }
+    }
+
+    /// Store on the backend selected by config
+    pub fn from_config(
+        kind: GasHistoryBackendKind,
+        path: &Path,
+        ttl: Duration,
+        redis: Arc<RedisHandle>,
+    ) -> eyre::Result<Self> {
+        let backend: Arc<dyn GasHistoryBackend> = match kind {
+            GasHistoryBackendKind::Redis => Arc::new(RedisGasHistory::new(redis, ttl)),
+            GasHistoryBackendKind::Memory => Arc::new(MemoryGasHistory::default()),
+            GasHistoryBackendKind::File => Arc::new(FileGasHistory::open(path)?),
+        };
+        Ok(Self::new(backend))
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Latest record for a target, from this run or the backend
+    pub async fn get(&self, target: &Address) -> Option<GasHistoryRecord> {
+        if let Some(record) = self.cache.get(target) {
+            return Some(record.clone());
+        }
+        match self.backend.load(target).await {
+            Ok(Some(record)) => {
+                debug!(target = %target, filtered_gas = record.gas, multiplier = ?record.multiplier, version = record.version, "Loaded gas history");
+                self.cache.insert(*target, record.clone());
+                Some(record)
+            }
+            Ok(None) => {
+                debug!(target = %target, "No gas history found");
+                None
+            }
+            Err(e) => {
+                debug!(target = %target, backend = self.backend.name(), error = %e, "Gas history backend read failed");
+                None
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Fold an optimization result into the target's record and persist it
+    ///
+    /// `None` bounds keep the stored controller state; `win` is the profitable quantity found, if any.
+    pub async fn record(&self, target: &Address, filtered_gas: u64, bounds: Option<BoundsState>, win: Option<QtySample>) -> GasHistoryRecord {
+        // Concurrent results for one target would otherwise fold into the same previous record
+        // and drop each other's wins and sample counts
+        let lock = self.record_locks.entry(*target).or_default().clone();
+        let _guard = lock.lock().await;
+
+        let previous = self.get(target).await;
+        let record = GasHistoryRecord::updated(previous.as_ref(), filtered_gas, bounds, win);
+        self.cache.insert(*target, record.clone());
+
+        match self.backend.store(target, &record).await {
+            Ok(()) => debug!(
+                target = %target,
+                filtered_gas = filtered_gas,
+                multiplier = ?record.multiplier,
+                samples = record.sample_count,
+                "Stored gas history"
+            ),
+            Err(e) => warn!(target = %target, backend = self.backend.name(), error = %e, "Failed to persist gas history; kept in memory"),
+        }
+        record
+    }
+
+    /// [`record`](Self::record) off the caller's path; [`flush`](Self::flush) waits for it
+    pub fn record_in_background(self: &Arc<Self>, target: Address, filtered_gas: u64, bounds: Option<BoundsState>, win: Option<QtySample>) {
+        let store = self.clone();
+        self.writes.spawn(async move {
+            store.record(&target, filtered_gas, bounds, win).await;
+        });
+    }
+
+    /// Wait for background writes and make them durable on the backend
+    pub async fn flush(&self) -> eyre::Result<()> {
+        self.writes.close();
+        self.writes.wait().await;
+        self.backend.flush().await?;
+        info!(backend = self.backend.name(), targets = self.cache.len(), "Gas history flushed");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds_controller::BoundsControllerConfig;
+
+    fn sample(qty: u64) -> QtySample {
+        QtySample { qty: U256::from(qty), profit: 1_000, block_number: 1, flashblock_index: 0 }
+    }
+
+    #[test]
+    fn test_decode_legacy_formats() {
+        let plain = GasHistoryRecord::decode("185000").unwrap();
+        assert_eq!((plain.version, plain.gas, plain.multiplier), (0, 185_000, None));
+
+        let json = GasHistoryRecord::decode(r#"{"gas":190000,"multiplier":250}"#).unwrap();
+        assert_eq!((json.version, json.gas, json.multiplier), (0, 190_000, Some(250)));
+
+        let current = GasHistoryRecord::updated(Some(&json), 191_000, None, Some(sample(42)));
+        assert_eq!(GasHistoryRecord::decode(&current.encode()), Some(current.clone()));
+        assert_eq!(current.multiplier, Some(250));
+        assert_eq!(current.version, GAS_HISTORY_VERSION);
+        assert!(GasHistoryRecord::decode("garbage").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_file_backend_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gas_history.jsonl");
+        let target = Address::repeat_byte(0x11);
+
+        {
+            let store = GasHistoryStore::new(Arc::new(FileGasHistory::open(&path).unwrap()));
+            let bounds = BoundsState::initial(300.0, &BoundsControllerConfig::default());
+            store.record(&target, 100_000, Some(bounds), Some(sample(7))).await;
+            store.record(&target, 110_000, None, None).await;
+        }
+
+        let store = GasHistoryStore::new(Arc::new(FileGasHistory::open(&path).unwrap()));
+        let record = store.get(&target).await.unwrap();
+        assert_eq!(record.gas, 110_000);
+        assert_eq!(record.multiplier, Some(300));
+        assert_eq!(record.bounds.map(|b| b.multiplier), Some(300.0));
+        assert_eq!(record.last_best_qty, Some(U256::from(7)));
+        assert_eq!(record.recent_wins, vec![sample(7)]);
+        assert_eq!(record.sample_count, 2);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_records_compact_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gas_history.jsonl");
+        let target = Address::repeat_byte(0x22);
+        let store = Arc::new(GasHistoryStore::new(Arc::new(FileGasHistory::open(&path).unwrap())));
+
+        let writes = 300;
+        for qty in 0..writes {
+            store.record_in_background(target, 100_000 + qty, None, Some(sample(qty)));
+        }
+        store.flush().await.unwrap();
+
+        // Every update folded into the record, and the file compacted while it grew
+        assert_eq!(store.get(&target).await.unwrap().sample_count, writes);
+        let lines = std::fs::read_to_string(&path).unwrap().lines().count();
+        assert!(lines <= FILE_COMPACT_RATIO * FILE_COMPACT_MIN_TARGETS, "{lines} lines");
+    }
+}
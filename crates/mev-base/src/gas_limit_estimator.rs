@@ -0,0 +1,328 @@
+use alloy_primitives::B256;
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::fee_multiplier_controller::{parse_hex_u64, receipt_field};
+use crate::flashblocks::FlashblocksEvent;
+
+/// Gas limit the bundle legs were sent with before estimation, and the search ceiling
+pub const MAX_BACKRUN_GAS_LIMIT: u64 = 4_000_000;
+
+/// Tracked submissions not seen within this window are forgotten
+const PENDING_TTL: Duration = Duration::from_secs(60);
+
+/// Configuration for gas limit estimation
+#[derive(Debug, Clone)]
+pub struct GasEstimatorConfig {
+    pub enabled: bool,
+    /// Binary search stops once the bracket is this narrow (gas)
+    pub search_tolerance: u64,
+    /// Margin applied until a config has enough landed samples (basis points)
+    pub default_margin_bps: u32,
+    /// Lower bound for the learned margin (basis points)
+    pub min_margin_bps: u32,
+    /// Upper bound for the learned margin (basis points)
+    pub max_margin_bps: u32,
+    /// Landed samples kept per config
+    pub sample_window: usize,
+    /// Landed samples needed before the learned margin replaces the default
+    pub min_samples: usize,
+}
+
+impl Default for GasEstimatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            search_tolerance: 1_000,
+            default_margin_bps: 1_000,
+            min_margin_bps: 200,
+            max_margin_bps: 5_000,
+            sample_window: 50,
+            min_samples: 5,
+        }
+    }
+}
+
+/// Binary-search the smallest gas limit in `[lower, upper]` for which `succeeds` holds
+///
+/// Assumes success is monotonic in the gas limit. Returns `None` if the transaction fails
+/// even at `upper`; otherwise the result is within `tolerance` of the true minimum.
+pub fn search_min_gas_limit(
+    lower: u64,
+    upper: u64,
+    tolerance: u64,
+    mut succeeds: impl FnMut(u64) -> bool,
+) -> Option<u64> {
+    if !succeeds(upper) {
+        return None;
+    }
+    let mut lo = lower.min(upper);
+    if succeeds(lo) {
+        return Some(lo);
+    }
+    // Invariant: lo fails, hi succeeds
+    let mut hi = upper;
+    while hi - lo > tolerance.max(1) {
+        let mid = lo + (hi - lo) / 2;
+        if succeeds(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Some(hi)
+}
+
+/// A submission whose receipt feeds the margin of its config
+#[derive(Debug, Clone)]
+struct TrackedGas {
+    config_name: String,
+    simulated_gas: u64,
+    gas_limit: u64,
+    sent_at: Instant,
+}
+
+/// Recent landed-gas overruns for one config, in basis points over the simulation
+#[derive(Debug, Default)]
+struct MarginSamples {
+    overruns_bps: VecDeque<u32>,
+}
+
+/// Sizes backrun gas limits from the simulated minimum plus a per-config safety margin
+///
+/// The margin is learned from how far landed gas drifted from the simulation: the largest
+/// recent overrun plus the configured minimum, so a config that always lands on simulated
+/// gas converges to the tightest limit and one that drifts keeps headroom.
+pub struct GasLimitEstimator {
+    config: GasEstimatorConfig,
+    pending: DashMap<B256, TrackedGas>,
+    samples: DashMap<String, Mutex<MarginSamples>>,
+    /// (block, cumulative gas) after the last transaction of the previous flashblock
+    last_cumulative_gas: Mutex<(u64, u64)>,
+}
+
+impl GasLimitEstimator {
+    pub fn new(config: GasEstimatorConfig) -> Self {
+        Self {
+            config,
+            pending: DashMap::new(),
+            samples: DashMap::new(),
+            last_cumulative_gas: Mutex::new((0, 0)),
+        }
+    }
+
+    pub fn config(&self) -> &GasEstimatorConfig {
+        &self.config
+    }
+
+    /// Current safety margin for a config, in basis points
+    pub fn margin_bps(&self, config_name: &str) -> u32 {
+        let Some(samples) = self.samples.get(config_name) else {
+            return self.config.default_margin_bps;
+        };
+        let samples = samples.lock();
+        if samples.overruns_bps.len() < self.config.min_samples {
+            return self.config.default_margin_bps;
+        }
+        let worst = samples.overruns_bps.iter().copied().max().unwrap_or(0);
+        worst
+            .saturating_add(self.config.min_margin_bps)
+            .clamp(self.config.min_margin_bps, self.config.max_margin_bps)
+    }
+
+    /// Gas limit to submit with, given the minimum limit the simulation succeeded at
+    pub fn gas_limit(&self, config_name: &str, min_gas_limit: u64) -> u64 {
+        let margin = self.margin_bps(config_name) as u64;
+        let limit = min_gas_limit.saturating_mul(10_000 + margin) / 10_000;
+        limit.min(MAX_BACKRUN_GAS_LIMIT)
+    }
+
+    /// Watch for a submitted transaction's receipt
+    pub fn track(&self, tx_hash: B256, config_name: &str, simulated_gas: u64, gas_limit: u64) {
+        self.pending.insert(tx_hash, TrackedGas {
+            config_name: config_name.to_string(),
+            simulated_gas,
+            gas_limit,
+            sent_at: Instant::now(),
+        });
+    }
+
+    /// Record landed gas from the receipts of tracked submissions
+    ///
+    /// Flashblock receipts carry only the block's cumulative gas, so a transaction's gas is
+    /// the difference from the receipt before it, which may sit in the previous flashblock.
+    pub fn observe_flashblock(&self, event: &FlashblocksEvent) {
+        let receipt_of = |tx: &alloy_consensus::TxEnvelope| event.metadata.receipts.get(&format!("{:?}", tx.tx_hash()));
+        let mut last_cumulative_gas = self.last_cumulative_gas.lock();
+        let mut previous_cumulative = match *last_cumulative_gas {
+            (block, cumulative) if block == event.block_number && event.index > 0 => Some(cumulative),
+            _ if event.index == 0 => Some(0),
+            _ => None,
+        };
+
+        for tx in &event.transactions {
+            let tx_hash = *tx.tx_hash();
+            let receipt = receipt_of(tx);
+            let cumulative = receipt.and_then(|r| receipt_field(r, "cumulativeGasUsed")).and_then(parse_hex_u64);
+            let gas_used = receipt.and_then(|r| receipt_field(r, "gasUsed")).and_then(parse_hex_u64)
+                .or_else(|| Some(cumulative?.checked_sub(previous_cumulative?)?));
+            previous_cumulative = cumulative;
+
+            let Some((_, tracked)) = self.pending.remove(&tx_hash) else { continue };
+            let Some(receipt) = receipt else { continue };
+            let Some(gas_used) = gas_used else { continue };
+            let reverted = receipt_field(receipt, "status").and_then(parse_hex_u64) == Some(0);
+
+            let overrun_bps = if reverted && gas_used >= tracked.gas_limit {
+                // Ran out of gas: the margin was too small, widen it as far as allowed
+                warn!(
+                    tx_hash = %tx_hash,
+                    config = %tracked.config_name,
+                    gas_used = gas_used,
+                    gas_limit = tracked.gas_limit,
+                    "⛽ Backrun ran out of gas - widening margin"
+                );
+                self.config.max_margin_bps
+            } else {
+                let overrun = gas_used.saturating_sub(tracked.simulated_gas);
+                (overrun.saturating_mul(10_000) / tracked.simulated_gas.max(1)).min(u32::MAX as u64) as u32
+            };
+
+            self.record_sample(&tracked.config_name, overrun_bps);
+            debug!(
+                tx_hash = %tx_hash,
+                config = %tracked.config_name,
+                simulated_gas = tracked.simulated_gas,
+                gas_used = gas_used,
+                gas_limit = tracked.gas_limit,
+                overrun_bps = overrun_bps,
+                margin_bps = self.margin_bps(&tracked.config_name),
+                "Recorded landed gas"
+            );
+        }
+
+        if let Some(cumulative) = previous_cumulative {
+            *last_cumulative_gas = (event.block_number, cumulative);
+        }
+        drop(last_cumulative_gas);
+
+        self.pending.retain(|_, tracked| tracked.sent_at.elapsed() < PENDING_TTL);
+    }
+
+    fn record_sample(&self, config_name: &str, overrun_bps: u32) {
+        let entry = self.samples.entry(config_name.to_string()).or_default();
+        let mut samples = entry.lock();
+        samples.overruns_bps.push_back(overrun_bps);
+        while samples.overruns_bps.len() > self.config.sample_window {
+            samples.overruns_bps.pop_front();
+        }
+        if samples.overruns_bps.len() == self.config.min_samples {
+            info!(config = config_name, "Gas margin now learned from landed transactions");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction_service::TransactionService;
+    use crate::wallet_service::{LocalKeySigner, SharedSigner};
+    use alloy_consensus::{TxEip1559, TxEnvelope};
+    use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+    use alloy_primitives::{Address, TxKind};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// A leg signed the way the submission path signs it, as the flashblock stream decodes it
+    async fn landed_leg(nonce: u64) -> TxEnvelope {
+        // Hardhat account #1 (test key, do not use in production)
+        let signer: SharedSigner = Arc::new(LocalKeySigner::new(
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d".parse().unwrap(),
+        ));
+        let tx = TxEip1559 {
+            chain_id: 8453,
+            nonce,
+            gas_limit: 150_000,
+            max_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(Address::with_last_byte(0x42)),
+            ..Default::default()
+        };
+        let (envelope, _) = TransactionService::sign_leg(&signer, tx).await.unwrap();
+        TxEnvelope::decode_2718(&mut envelope.encoded_2718().as_slice()).unwrap()
+    }
+
+    fn flashblock(index: u32, transactions: Vec<(TxEnvelope, u64)>) -> FlashblocksEvent {
+        // Receipts as the sequencer sends them: typed, keyed by hash, cumulative gas only
+        let receipts = transactions.iter()
+            .map(|(tx, cumulative)| (
+                format!("{:?}", tx.tx_hash()),
+                serde_json::json!({ "Eip1559": { "status": "0x1", "cumulativeGasUsed": format!("{:#x}", cumulative), "logs": [] } }),
+            ))
+            .collect();
+        FlashblocksEvent {
+            block_number: 10,
+            index,
+            transactions: transactions.into_iter().map(|(tx, _)| tx).collect(),
+            state_root: B256::ZERO,
+            receipts_root: B256::ZERO,
+            metadata: crate::flashblocks::Metadata {
+                receipts,
+                new_account_balances: HashMap::new(),
+                block_number: 10,
+            },
+            received_at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_search_min_gas_limit() {
+        let mut calls = 0;
+        let limit = search_min_gas_limit(100_000, MAX_BACKRUN_GAS_LIMIT, 1_000, |gas| {
+            calls += 1;
+            gas >= 187_654
+        });
+        let limit = limit.unwrap();
+        assert!((187_654..187_654 + 1_000).contains(&limit));
+        assert!(calls < 20);
+
+        assert_eq!(search_min_gas_limit(100_000, 200_000, 1_000, |_| true), Some(100_000));
+        assert_eq!(search_min_gas_limit(100_000, 200_000, 1_000, |_| false), None);
+    }
+
+    #[test]
+    fn test_margin_learned_from_samples() {
+        let estimator = GasLimitEstimator::new(GasEstimatorConfig::default());
+        assert_eq!(estimator.margin_bps("cfg"), 1_000);
+
+        for _ in 0..5 {
+            estimator.record_sample("cfg", 0);
+        }
+        assert_eq!(estimator.margin_bps("cfg"), 200);
+        assert_eq!(estimator.gas_limit("cfg", 100_000), 102_000);
+
+        estimator.record_sample("cfg", 300);
+        assert_eq!(estimator.margin_bps("cfg"), 500);
+
+        estimator.record_sample("cfg", 10_000);
+        assert_eq!(estimator.margin_bps("cfg"), 5_000);
+        assert_eq!(estimator.gas_limit("other", 3_900_000), MAX_BACKRUN_GAS_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn test_landed_gas_from_signed_leg_receipt() {
+        let estimator = GasLimitEstimator::new(GasEstimatorConfig { min_samples: 1, ..Default::default() });
+        let first = landed_leg(0).await;
+        let ours = landed_leg(1).await;
+        estimator.track(*ours.tx_hash(), "cfg", 100_000, 120_000);
+
+        // Our leg opens the second flashblock, so its gas comes from the previous one's receipts
+        estimator.observe_flashblock(&flashblock(0, vec![(first, 50_000)]));
+        estimator.observe_flashblock(&flashblock(1, vec![(ours, 160_000)]));
+
+        // 110k landed against 100k simulated: 10% overrun plus the 2% floor
+        assert_eq!(estimator.margin_bps("cfg"), 1_200);
+    }
+}
@@ -24,6 +24,14 @@ pub struct GradientParams {
     pub target_address: Address,
     /// IIR filtered gas usage (None for first run)
     pub filtered_gas: Option<u64>,
+    /// Wallet the optimized transaction will be sent from
+    pub sender: crate::mev_bundle_types::SimulationSender,
+    /// Executor codec used to encode the optimized quantity
+    pub codec: crate::executor_codec::ExecutorCodec,
+    /// Chain id simulated transactions are encoded for
+    pub chain_id: u64,
+    /// Bounds were narrowed around recent winners; keep them and the learned gas as-is
+    pub warm_start: bool,
 }
 
 /// Output from gradient descent optimization
@@ -35,8 +43,6 @@ pub struct OptimizeOutput {
     pub gas_used: u64,
     /// Updated filtered gas value (IIR filtered)
     pub filtered_gas: Option<u64>,
-    /// Actual multiplier used for bounds (after adjustments and clamping)
-    pub actual_multiplier: Option<u64>,
 }
 
 /// Gradient descent optimizer ported from Solidity
@@ -71,7 +77,6 @@ impl GradientOptimizer {
             calldata_used: params.calldata_template.clone(),
             gas_used: 0,
             filtered_gas: None,
-            actual_multiplier: None,
         };
         
         let mut iterations_used = 0;
@@ -104,7 +109,7 @@ impl GradientOptimizer {
                 &params,
                 cache_db,
                 evm_config,
-                state.base_fee,
+                state.block_number,
                 iterations_used,
             )?;
             
@@ -149,7 +154,7 @@ impl GradientOptimizer {
                     &params,
                     cache_db,
                     evm_config,
-                    state.base_fee,
+                    state.block_number,
                     iterations_used,
                 )?;
                 
@@ -194,7 +199,7 @@ impl GradientOptimizer {
                 &params,
                 cache_db,
                 evm_config,
-                state.base_fee,
+                state.block_number,
                 iterations_used,
             )?;
             
@@ -219,19 +224,15 @@ impl GradientOptimizer {
         params: &GradientParams,
         cache_db: &mut CacheDB<DB>,
         evm_config: &OpEvmConfig,
-        _base_fee: u128,
+        block_number: u64,
         iterations_used: usize,
     ) -> eyre::Result<OptimizeOutput> 
     where
         DB: revm::Database + revm::DatabaseRef + std::fmt::Debug,
         <DB as revm::DatabaseRef>::Error: Send + Sync + 'static,
     {
-        // Format calldata for short format (4 bytes)
-        // First byte is 0x00 for the function selector, next 3 bytes are the quantity
-        let qty_bytes = qty_in.to_be_bytes::<32>();
-        let mut calldata = vec![0x00];
-        // Take the last 3 bytes of the quantity (24 bits max)
-        calldata.extend_from_slice(&qty_bytes[29..32]);
+        // Encode the quantity in the target's executor calldata format
+        let calldata = params.codec.encode_calldata(qty_in)?.to_vec();
         
         // Use constant address from first 20 bytes of the provided hash
         // 0x3a3f76931108c79658a90f340b4cbec860346b2bd5ffe918ede99e74a7e821f1
@@ -278,7 +279,7 @@ impl GradientOptimizer {
         let mut evm_env = evm_config.evm_env(&alloy_consensus::Header {
             base_fee_per_gas: Some(0), // Set base fee to 0 for MEV simulation
             gas_limit: 2_000_000_000,   // 2 billion gas limit
-            number: 33_634_688,         // Current Base mainnet block number
+            number: block_number,
             timestamp: current_timestamp, // Today's timestamp
             ..Default::default()
         });
@@ -326,7 +327,7 @@ impl GradientOptimizer {
         
         // Create a proper transaction envelope for Optimism
         let tx_eip1559 = TxEip1559 {
-            chain_id: 8453, // Base mainnet chain ID
+            chain_id: params.chain_id,
             nonce: tx_env.nonce,
             gas_limit: tx_env.gas_limit,
             max_fee_per_gas: 0, // Ensure 0 fee
@@ -400,7 +401,6 @@ impl GradientOptimizer {
                                         calldata_used: calldata.into(),
                                         gas_used,
                                         filtered_gas: None,
-                                        actual_multiplier: None,
                                     });
                                 }
                             }
@@ -412,7 +412,6 @@ impl GradientOptimizer {
                                     calldata_used: calldata.into(),
                                     gas_used,
                                     filtered_gas: None,
-                                    actual_multiplier: None,
                                 });
                             }
                         }
@@ -460,7 +459,6 @@ impl GradientOptimizer {
                             calldata_used: calldata.into(),
                             gas_used,
                             filtered_gas: None,
-                            actual_multiplier: None,
                         })
                     }
                     ExecutionResult::Revert { output, gas_used: revert_gas_used } => {
@@ -506,7 +504,6 @@ impl GradientOptimizer {
                             calldata_used: calldata.into(),
                             gas_used: revert_gas_used,
                             filtered_gas: None,
-                            actual_multiplier: None,
                         })
                     }
                     ExecutionResult::Halt { reason, .. } => {
@@ -517,7 +514,6 @@ impl GradientOptimizer {
                             calldata_used: calldata.into(),
                             gas_used,
                             filtered_gas: None,
-                            actual_multiplier: None,
                         })
                     }
                 }
@@ -545,7 +541,6 @@ impl GradientOptimizer {
                     calldata_used: calldata.into(),
                     gas_used: 0,
                     filtered_gas: None,
-                    actual_multiplier: None,
                 })
             }
         }
@@ -70,6 +70,22 @@ where
     trace!(address = %BATCH_TEST_V4_ADDRESS, "BatchGradientTestV4 contract injected via code override");
 }
 
+/// IIR filter coefficient (5% new, 95% old)
+const GAS_FILTER_ALPHA: f64 = 0.05;
+
+/// Filtered search gas after a run that used `gas_used`
+///
+/// Warm-started runs search a narrower bracket, so they leave the filter untouched.
+fn next_filtered_gas(params: &GradientParams, gas_used: u64) -> u64 {
+    match params.filtered_gas {
+        Some(old_filtered) if params.warm_start => old_filtered,
+        // IIR filter: new_value = alpha * current + (1 - alpha) * old
+        Some(old_filtered) => ((gas_used as f64 * GAS_FILTER_ALPHA) + (old_filtered as f64 * (1.0 - GAS_FILTER_ALPHA))) as u64,
+        // First run, use current gas as initial value
+        None => gas_used,
+    }
+}
+
 /// Binary search gradient descent optimizer with single EVM call
 pub struct BinarySearchGradientOptimizer {
     /// Maximum iterations for the binary search
@@ -91,56 +107,9 @@ impl BinarySearchGradientOptimizer {
         }
     }
     
-    /// Adjust bounds based on filtered gas usage, returns (params, actual_multiplier)
-    fn adjust_bounds_for_gas(&self, mut params: GradientParams) -> (GradientParams, u64) {
-        // Target gas scales with iteration count
-        // Base: 35M for 40 iterations = 875k per iteration
-        let target_gas_per_iteration = 875_000u64;
-        let target_gas = target_gas_per_iteration * self.max_iterations as u64;
-        
-        // Calculate the current multiplier (what was loaded and applied)
-        let current_multiplier = params.upper_bound / params.initial_qty;
-        
-        if let Some(filtered_gas) = params.filtered_gas {
-            // Calculate adjustment factor based on gas usage
-            let adjustment = if filtered_gas > target_gas * 2 {
-                0.5 // Reduce to 50% if using way too much gas
-            } else if filtered_gas > target_gas {
-                0.8 // Reduce to 80% if slightly over
-            } else if filtered_gas < target_gas / 2 {
-                1.5 // Increase to 150% if plenty of headroom
-            } else {
-                1.0 // Keep as is
-            };
-            
-            // Apply adjustment to current multiplier
-            let new_multiplier = U256::from((current_multiplier.to::<u64>() as f64 * adjustment) as u64);
-            
-            // Clamp between 10x minimum and 1000x maximum
-            let new_multiplier = new_multiplier.max(U256::from(10)).min(U256::from(1000));
-            let new_upper = params.initial_qty * new_multiplier;
-            
-            if new_upper != params.upper_bound {
-                debug!(
-                    target = %params.target_address,
-                    current_multiplier = current_multiplier.to::<u64>(),
-                    new_multiplier = new_multiplier.to::<u64>(),
-                    old_upper = %params.upper_bound,
-                    new_upper = %new_upper,
-                    filtered_gas_millions = filtered_gas / 1_000_000,
-                    target_gas_millions = target_gas / 1_000_000,
-                    adjustment = adjustment,
-                    "Adjusting upper bound based on gas usage"
-                );
-                params.upper_bound = new_upper;
-            }
-            
-            // Return the NEW multiplier that will be stored
-            (params, new_multiplier.to::<u64>())
-        } else {
-            // No gas history, return current multiplier as-is
-            (params, current_multiplier.to::<u64>())
-        }
+    /// Iterations per search; the bounds controller sizes its gas budget from this
+    pub fn max_iterations(&self) -> usize {
+        self.max_iterations
     }
 
     /// Optimize quantity using in-contract binary search
@@ -155,8 +124,6 @@ impl BinarySearchGradientOptimizer {
         DB: revm::Database + revm::DatabaseRef + std::fmt::Debug,
         <DB as revm::DatabaseRef>::Error: Send + Sync + 'static,
     {
-        // Adjust bounds based on filtered gas history
-        let (params, actual_multiplier) = self.adjust_bounds_for_gas(params);
         let start_time = std::time::Instant::now();
         
         debug!(
@@ -172,25 +139,8 @@ impl BinarySearchGradientOptimizer {
             inject_batch_test_v4_contract(cache_db);
         }
         
-        // Pre-fund bot address once
-        let bot_address = Address::from([
-            0x3a, 0x3f, 0x76, 0x93, 0x11, 0x08, 0xc7, 0x96,
-            0x58, 0xa9, 0x0f, 0x34, 0x0b, 0x4c, 0xbe, 0xc8,
-            0x60, 0x34, 0x6b, 0x2b
-        ]);
-        
-        let bot_account_info = AccountInfo {
-            balance: U256::from(1_000_000_000_000_000_000u64),
-            nonce: 0,
-            code_hash: alloy_primitives::KECCAK256_EMPTY,
-            code: None,
-        };
-        
-        cache_db.cache.accounts.insert(bot_address, DbAccount {
-            info: bot_account_info,
-            account_state: AccountState::Touched,
-            storage: Default::default(),
-        });
+        // Run the search from the submitting wallet, on its real nonce and balance
+        let sender = params.sender;
         
         trace!(
             iterations = self.max_iterations,
@@ -232,8 +182,8 @@ impl BinarySearchGradientOptimizer {
         
         // Setup transaction
         let mut tx_env = TxEnv::default();
-        tx_env.caller = bot_address;
-        tx_env.nonce = 0;
+        tx_env.caller = sender.address;
+        tx_env.nonce = sender.nonce;
         tx_env.kind = TxKind::Call(BATCH_TEST_V4_ADDRESS);
         tx_env.data = calldata.clone().into();
         tx_env.gas_limit = 1_000_000_000; // 1 billion gas limit
@@ -243,8 +193,8 @@ impl BinarySearchGradientOptimizer {
         
         // Create transaction for Optimism
         let tx_eip1559 = TxEip1559 {
-            chain_id: 8453,
-            nonce: 0,
+            chain_id: params.chain_id,
+            nonce: sender.nonce,
             gas_limit: 1_000_000_000,
             max_fee_per_gas: 0,
             max_priority_fee_per_gas: 0,
@@ -318,29 +268,17 @@ impl BinarySearchGradientOptimizer {
                                 );
                                 
                                 // Calculate new filtered gas value using IIR filter
-                                const ALPHA: f64 = 0.05; // IIR filter coefficient (5% new, 95% old)
-                                let new_filtered_gas = match params.filtered_gas {
-                                    Some(old_filtered) => {
-                                        // IIR filter: new_value = alpha * current + (1 - alpha) * old
-                                        ((gas_used as f64 * ALPHA) + (old_filtered as f64 * (1.0 - ALPHA))) as u64
-                                    }
-                                    None => {
-                                        // First run, use current gas as initial value
-                                        gas_used
-                                    }
-                                };
+                                let new_filtered_gas = next_filtered_gas(&params, gas_used);
                                 
                                 // Create calldata for the best quantity
-                                let qty_bytes = decoded.bestQuantity.to_be_bytes::<32>();
-                                let calldata = [&[0x00], &qty_bytes[29..32]].concat();
+                                let calldata = params.codec.encode_calldata(decoded.bestQuantity)?;
                                 
                                 OptimizeOutput {
                                     qty_in: decoded.bestQuantity,
                                     delta: best_profit,
-                                    calldata_used: calldata.into(),
+                                    calldata_used: calldata,
                                     gas_used: 200_000, // Estimate for actual swap
                                     filtered_gas: Some(new_filtered_gas),
-                                    actual_multiplier: Some(actual_multiplier),
                                 }
                             }
                             _ => {
@@ -351,7 +289,6 @@ impl BinarySearchGradientOptimizer {
                                     calldata_used: params.calldata_template.clone(),
                                     gas_used: 0,
                                     filtered_gas: params.filtered_gas,
-                                    actual_multiplier: Some(actual_multiplier),
                                 }
                             }
                         };
@@ -371,7 +308,6 @@ impl BinarySearchGradientOptimizer {
                             calldata_used: params.calldata_template.clone(),
                             gas_used: 0,
                             filtered_gas: params.filtered_gas,
-                            actual_multiplier: Some(actual_multiplier),
                         })
                     }
                     ExecutionResult::Halt { reason, gas_used } => {
@@ -386,7 +322,6 @@ impl BinarySearchGradientOptimizer {
                             calldata_used: params.calldata_template.clone(),
                             gas_used: 0,
                             filtered_gas: params.filtered_gas,
-                            actual_multiplier: Some(actual_multiplier),
                         })
                     }
                 }
@@ -403,9 +338,8 @@ impl BinarySearchGradientOptimizer {
                     calldata_used: params.calldata_template.clone(),
                     gas_used: 0,
                     filtered_gas: params.filtered_gas,
-                    actual_multiplier: Some(actual_multiplier),
                 })
             }
         }
     }
-}
\ No newline at end of file
+}
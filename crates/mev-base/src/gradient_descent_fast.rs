@@ -49,7 +49,6 @@ impl FastGradientOptimizer {
             calldata_used: params.calldata_template.clone(),
             gas_used: 0,
             filtered_gas: None,
-            actual_multiplier: None,
         };
         
         let mut iterations_used = 0;
@@ -85,7 +84,7 @@ impl FastGradientOptimizer {
         let evm_env = evm_config.evm_env(&alloy_consensus::Header {
             base_fee_per_gas: Some(0),
             gas_limit: 2_000_000_000,
-            number: 33_634_688,
+            number: state.block_number,
             timestamp: current_timestamp,
             ..Default::default()
         });
@@ -263,9 +262,8 @@ impl FastGradientOptimizer {
         DB: revm::Database + revm::DatabaseRef + std::fmt::Debug,
         <DB as revm::DatabaseRef>::Error: Send + Sync + 'static,
     {
-        // Ultra-fast calldata creation
-        let qty_bytes = qty_in.to_be_bytes::<32>();
-        let calldata = [&[0x00], &qty_bytes[29..32]].concat();
+        // Encode the quantity in the target's executor calldata format
+        let calldata = params.codec.encode_calldata(qty_in)?.to_vec();
         
         // Minimal transaction setup
         let mut tx_env = TxEnv::default();
@@ -284,7 +282,7 @@ impl FastGradientOptimizer {
         
         // Create minimal transaction for Optimism
         let tx_eip1559 = TxEip1559 {
-            chain_id: 8453,
+            chain_id: params.chain_id,
             nonce: 0,
             gas_limit: 4_000_000,
             max_fee_per_gas: 0,
@@ -339,7 +337,6 @@ impl FastGradientOptimizer {
                             calldata_used: calldata.into(),
                             gas_used,
                             filtered_gas: None,
-                            actual_multiplier: None,
                         })
                     }
                     _ => Ok(OptimizeOutput {
@@ -348,7 +345,6 @@ impl FastGradientOptimizer {
                         calldata_used: calldata.into(),
                         gas_used: exec_result.result.gas_used(),
                         filtered_gas: None,
-                        actual_multiplier: None,
                     })
                 }
             }
@@ -358,7 +354,6 @@ impl FastGradientOptimizer {
                 calldata_used: calldata.into(),
                 gas_used: 0,
                 filtered_gas: None,
-                actual_multiplier: None,
             })
         }
     }
@@ -108,7 +108,6 @@ impl MulticallGradientOptimizer {
             calldata_used: params.calldata_template.clone(),
             gas_used: 0,
             filtered_gas: None,
-            actual_multiplier: None,
         };
         
         // Pre-fund bot address once
@@ -314,7 +313,7 @@ impl MulticallGradientOptimizer {
         
         // Create transaction for Optimism
         let tx_eip1559 = TxEip1559 {
-            chain_id: 8453,
+            chain_id: params.chain_id,
             nonce: 0,
             gas_limit: 20_000_000,
             max_fee_per_gas: 0,
@@ -364,7 +363,6 @@ impl MulticallGradientOptimizer {
                                     calldata_used: self.create_calldata(qty),
                                     gas_used: 0,
                                     filtered_gas: None,
-                                    actual_multiplier: None,
                                 }).collect()
                             }
                         };
@@ -387,7 +385,6 @@ impl MulticallGradientOptimizer {
                             calldata_used: self.create_calldata(qty),
                             gas_used: 0,
                             filtered_gas: None,
-                            actual_multiplier: None,
                         }).collect())
                     }
                     _ => {
@@ -398,7 +395,6 @@ impl MulticallGradientOptimizer {
                             calldata_used: self.create_calldata(qty),
                             gas_used: 0,
                             filtered_gas: None,
-                            actual_multiplier: None,
                         }).collect())
                     }
                 }
@@ -411,7 +407,6 @@ impl MulticallGradientOptimizer {
                     calldata_used: self.create_calldata(qty),
                     gas_used: 0,
                     filtered_gas: None,
-                    actual_multiplier: None,
                 }).collect())
             }
         }
@@ -459,17 +454,15 @@ impl MulticallGradientOptimizer {
                 calldata_used: self.create_calldata(*qty),
                 gas_used: test_result.gasUsed.try_into().unwrap_or(0),
                 filtered_gas: None,
-                actual_multiplier: None,
             });
         }
         
         Ok(results)
     }
 
-    /// Create calldata for a single test
+    /// Create calldata for a single test; the multicall contract only speaks the short format
     fn create_calldata(&self, qty: U256) -> Bytes {
-        let qty_bytes = qty.to_be_bytes::<32>();
-        let calldata = [&[0x00], &qty_bytes[29..32]].concat();
-        calldata.into()
+        let codec = crate::executor_codec::ExecutorCodec::V1_SHORT;
+        codec.encode_calldata(qty.min(codec.max_quantity())).expect("clamped to the format maximum")
     }
 }
\ No newline at end of file
@@ -61,7 +61,6 @@ impl ParallelGradientOptimizer {
             calldata_used: params.calldata_template.clone(),
             gas_used: 0,
             filtered_gas: None,
-            actual_multiplier: None,
         }));
         
         let hotspots = Arc::new(Mutex::new(Vec::<U256>::new()));
@@ -110,7 +109,7 @@ impl ParallelGradientOptimizer {
                         &params,
                         &mut local_cache_db,
                         evm_config,
-                        state.base_fee,
+                        state.block_number,
                         test_case.iteration,
                         batch_idx == 0 && test_case.iteration == 1, // Only log first
                     )
@@ -177,7 +176,7 @@ impl ParallelGradientOptimizer {
                         &params,
                         &mut local_cache_db,
                         evm_config,
-                        state.base_fee,
+                        state.block_number,
                         0,
                         false,
                     )?;
@@ -218,7 +217,7 @@ impl ParallelGradientOptimizer {
         params: &GradientParams,
         cache_db: &mut CacheDB<DB>,
         evm_config: &OpEvmConfig,
-        _base_fee: u128,
+        block_number: u64,
         _iteration: usize,
         should_log: bool,
     ) -> eyre::Result<OptimizeOutput> 
@@ -226,10 +225,8 @@ impl ParallelGradientOptimizer {
         DB: revm::Database + revm::DatabaseRef + std::fmt::Debug,
         <DB as revm::DatabaseRef>::Error: Send + Sync + 'static,
     {
-        // Format calldata efficiently
-        let qty_bytes = qty_in.to_be_bytes::<32>();
-        let mut calldata = vec![0x00];
-        calldata.extend_from_slice(&qty_bytes[29..32]);
+        // Encode the quantity in the target's executor calldata format
+        let calldata = params.codec.encode_calldata(qty_in)?.to_vec();
         
         // Use constant bot address
         let bot_address = Address::from([
@@ -273,7 +270,7 @@ impl ParallelGradientOptimizer {
         let mut evm_env = evm_config.evm_env(&alloy_consensus::Header {
             base_fee_per_gas: Some(0),
             gas_limit: 2_000_000_000,
-            number: 33_634_688,
+            number: block_number,
             timestamp: current_timestamp,
             ..Default::default()
         });
@@ -290,7 +287,7 @@ impl ParallelGradientOptimizer {
         
         // Create minimal transaction for Optimism
         let tx_eip1559 = TxEip1559 {
-            chain_id: 8453,
+            chain_id: params.chain_id,
             nonce: tx_env.nonce,
             gas_limit: tx_env.gas_limit,
             max_fee_per_gas: 0,
@@ -330,7 +327,6 @@ impl ParallelGradientOptimizer {
                             calldata_used: calldata.into(),
                             gas_used,
                             filtered_gas: None,
-                            actual_multiplier: None,
                         })
                     }
                     ExecutionResult::Revert { output, gas_used: revert_gas_used } => {
@@ -361,7 +357,6 @@ impl ParallelGradientOptimizer {
                             calldata_used: calldata.into(),
                             gas_used: revert_gas_used,
                             filtered_gas: None,
-                            actual_multiplier: None,
                         })
                     }
                     ExecutionResult::Halt { .. } => {
@@ -371,7 +366,6 @@ impl ParallelGradientOptimizer {
                             calldata_used: calldata.into(),
                             gas_used,
                             filtered_gas: None,
-                            actual_multiplier: None,
                         })
                     }
                 }
@@ -383,7 +377,6 @@ impl ParallelGradientOptimizer {
                     calldata_used: calldata.into(),
                     gas_used: 0,
                     filtered_gas: None,
-                    actual_multiplier: None,
                 })
             }
         }
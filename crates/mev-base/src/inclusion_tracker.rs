@@ -0,0 +1,278 @@
+use alloy_consensus::transaction::SignerRecoverable;
+use alloy_consensus::Transaction;
+use alloy_primitives::{Address, B256};
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use crate::flashblocks::FlashblocksEvent;
+use crate::metrics::MEV_METRICS;
+use crate::wallet_service::redact_address;
+
+/// Submissions not seen within this window are forgotten
+const PENDING_TTL: Duration = Duration::from_secs(60);
+
+/// A submission waiting to show up in the flashblock stream
+#[derive(Debug, Clone)]
+struct PendingInclusion {
+    strategy: String,
+    sent_at: Instant,
+    /// When the state the opportunity was found on was exported
+    found_at: Option<Instant>,
+    trigger_hash: Option<B256>,
+}
+
+/// Where and when one of our submissions landed
+#[derive(Debug, Clone)]
+pub struct InclusionRecord {
+    pub tx_hash: B256,
+    pub strategy: String,
+    pub block_number: u64,
+    pub flashblock_index: u32,
+    /// Position within the flashblock
+    pub position: usize,
+    pub send_to_inclusion: Duration,
+    /// Transactions that landed between the trigger and us, if the trigger is in this block
+    pub gap_from_trigger: Option<usize>,
+}
+
+/// Per-block transaction positions, reset on every new block
+#[derive(Default)]
+struct BlockPositions {
+    block_number: u64,
+    tx_count: usize,
+    positions: HashMap<B256, usize>,
+}
+
+/// Detects our own submissions landing in the flashblock stream
+pub struct InclusionTracker {
+    pending: DashMap<B256, PendingInclusion>,
+    our_wallets: HashSet<Address>,
+    /// Nonces our wallets sent recently; only transactions reusing one are checked for our sender
+    our_nonces: DashMap<u64, Instant>,
+    block: Mutex<BlockPositions>,
+}
+
+impl InclusionTracker {
+    pub fn new(our_wallets: Vec<Address>) -> Self {
+        Self {
+            pending: DashMap::new(),
+            our_wallets: our_wallets.into_iter().collect(),
+            our_nonces: DashMap::new(),
+            block: Mutex::new(BlockPositions::default()),
+        }
+    }
+
+    /// Start watching for a submitted transaction
+    pub fn track(&self, tx_hash: B256, strategy: &str, found_at: Option<Instant>, trigger_hash: Option<B256>) {
+        self.pending.insert(tx_hash, PendingInclusion {
+            strategy: strategy.to_string(),
+            sent_at: Instant::now(),
+            found_at,
+            trigger_hash,
+        });
+    }
+
+    /// Watch for other transactions landing at a nonce one of our wallets sent with
+    pub fn track_nonce(&self, nonce: u64) {
+        self.our_nonces.insert(nonce, Instant::now());
+    }
+
+    /// Match a flashblock's transactions against our submissions and wallets
+    pub fn observe_flashblock(&self, event: &FlashblocksEvent) -> Vec<InclusionRecord> {
+        let mut records = Vec::new();
+        let mut block = self.block.lock();
+        if block.block_number != event.block_number {
+            *block = BlockPositions { block_number: event.block_number, ..Default::default() };
+        }
+
+        for (position, tx) in event.transactions.iter().enumerate() {
+            let tx_hash = *tx.tx_hash();
+            let block_position = block.tx_count + position;
+            block.positions.insert(tx_hash, block_position);
+
+            if let Some((_, pending)) = self.pending.remove(&tx_hash) {
+                let send_to_inclusion = pending.sent_at.elapsed();
+                let gap_from_trigger = pending.trigger_hash
+                    .and_then(|trigger| block.positions.get(&trigger).copied())
+                    .map(|trigger_position| block_position.saturating_sub(trigger_position + 1));
+
+                MEV_METRICS.self_inclusions_total.increment(1);
+                MEV_METRICS.inclusion_latency_seconds.record(send_to_inclusion.as_secs_f64());
+                MEV_METRICS.inclusion_flashblock_index.record(event.index as f64);
+                MEV_METRICS.inclusion_position.record(position as f64);
+                if let Some(found_at) = pending.found_at {
+                    MEV_METRICS.opportunity_to_inclusion_seconds.record(found_at.elapsed().as_secs_f64());
+                }
+                if let Some(gap) = gap_from_trigger {
+                    MEV_METRICS.inclusion_gap_from_trigger.record(gap as f64);
+                }
+
+                info!(
+                    tx_hash = %tx_hash,
+                    strategy = %pending.strategy,
+                    block = event.block_number,
+                    flashblock = event.index,
+                    position = position,
+                    send_to_inclusion_ms = send_to_inclusion.as_millis(),
+                    gap_from_trigger = ?gap_from_trigger,
+                    "Our submission landed"
+                );
+
+                records.push(InclusionRecord {
+                    tx_hash,
+                    strategy: pending.strategy,
+                    block_number: event.block_number,
+                    flashblock_index: event.index,
+                    position,
+                    send_to_inclusion,
+                    gap_from_trigger,
+                });
+            } else if !self.our_wallets.is_empty() && self.our_nonces.contains_key(&tx.nonce()) {
+                // Replacements and cancellations reuse our nonces; recovering every foreign
+                // sender would cost a signature recovery per transaction in the block
+                if let Ok(sender) = tx.recover_signer() {
+                    if self.our_wallets.contains(&sender) {
+                        MEV_METRICS.untracked_self_inclusions_total.increment(1);
+                        debug!(
+                            tx_hash = %tx_hash,
+                            sender = %redact_address(&sender),
+                            block = event.block_number,
+                            flashblock = event.index,
+                            position = position,
+                            "Untracked transaction from our wallet landed"
+                        );
+                    }
+                }
+            }
+        }
+        block.tx_count += event.transactions.len();
+        drop(block);
+
+        self.pending.retain(|_, pending| pending.sent_at.elapsed() < PENDING_TTL);
+        self.our_nonces.retain(|_, sent_at| sent_at.elapsed() < PENDING_TTL);
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction_service::TransactionService;
+    use crate::wallet_service::{LocalKeySigner, SharedSigner};
+    use alloy_consensus::{TxEip1559, TxEnvelope};
+    use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+    use alloy_primitives::TxKind;
+    use std::sync::Arc;
+
+    fn signer() -> SharedSigner {
+        // Hardhat account #1 (test key, do not use in production)
+        Arc::new(LocalKeySigner::new(
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d".parse().unwrap(),
+        ))
+    }
+
+    /// A leg signed the way the submission path signs it
+    async fn tx(nonce: u64) -> TxEnvelope {
+        let tx = TxEip1559 {
+            chain_id: 8453,
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(Address::with_last_byte(0x42)),
+            ..Default::default()
+        };
+        TransactionService::sign_leg(&signer(), tx).await.unwrap().0
+    }
+
+    /// The transaction as the flashblock stream delivers it: decoded from its raw bytes
+    fn landed(tx: &TxEnvelope) -> TxEnvelope {
+        TxEnvelope::decode_2718(&mut tx.encoded_2718().as_slice()).unwrap()
+    }
+
+    fn event(block_number: u64, index: u32, transactions: Vec<TxEnvelope>) -> FlashblocksEvent {
+        FlashblocksEvent {
+            block_number,
+            index,
+            transactions: transactions.iter().map(landed).collect(),
+            state_root: B256::ZERO,
+            receipts_root: B256::ZERO,
+            metadata: crate::flashblocks::Metadata {
+                receipts: HashMap::new(),
+                new_account_balances: HashMap::new(),
+                block_number,
+            },
+            received_at: Instant::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inclusion_position_and_gap_across_flashblocks() {
+        let tracker = InclusionTracker::new(vec![]);
+        let trigger = tx(1).await;
+        let ours = tx(4).await;
+        tracker.track(*ours.tx_hash(), "Backrun_test", None, Some(*trigger.tx_hash()));
+
+        assert!(tracker.observe_flashblock(&event(10, 0, vec![tx(0).await, trigger])).is_empty());
+        let records = tracker.observe_flashblock(&event(10, 1, vec![tx(2).await, tx(3).await, ours]));
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].flashblock_index, 1);
+        assert_eq!(records[0].position, 2);
+        assert_eq!(records[0].gap_from_trigger, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_trigger_from_previous_block_has_no_gap() {
+        let tracker = InclusionTracker::new(vec![]);
+        let trigger = tx(1).await;
+        let ours = tx(2).await;
+        tracker.track(*ours.tx_hash(), "Backrun_test", None, Some(*trigger.tx_hash()));
+
+        tracker.observe_flashblock(&event(10, 10, vec![trigger]));
+        let records = tracker.observe_flashblock(&event(11, 0, vec![ours]));
+
+        assert_eq!(records[0].gap_from_trigger, None);
+    }
+
+    #[tokio::test]
+    async fn test_tracked_leg_is_not_counted_as_untracked() {
+        let tracker = InclusionTracker::new(vec![signer().address()]);
+        let ours = tx(7).await;
+        tracker.track(*ours.tx_hash(), "Backrun_test", None, None);
+
+        let records = tracker.observe_flashblock(&event(10, 0, vec![ours.clone()]));
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].tx_hash, *ours.tx_hash());
+        assert!(tracker.pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_landed_leg_settles_submitted_opportunity() {
+        use crate::opportunity_lifecycle::{Admission, OpportunityLifecycle, OpportunityState};
+        use alloy_primitives::U256;
+
+        let lifecycle = OpportunityLifecycle::new(2);
+        lifecycle.advance(10);
+        let Admission::New(ticket) = lifecycle.admit(10, 0, "Backrun_test", U256::from(1)) else {
+            panic!("expected a new opportunity");
+        };
+        let ours = tx(3).await;
+        lifecycle.validated(&ticket);
+        lifecycle.submitted(&ticket, Some(*ours.tx_hash()));
+
+        let tracker = InclusionTracker::new(vec![signer().address()]);
+        tracker.track(*ours.tx_hash(), "Backrun_test", None, None);
+        assert!(tracker.observe_flashblock(&event(10, 0, vec![tx(2).await])).is_empty());
+        assert_eq!(lifecycle.state_of(10, "Backrun_test"), Some(OpportunityState::Submitted));
+
+        // As the pipeline's flashblock loop does for every inclusion
+        for inclusion in tracker.observe_flashblock(&event(10, 1, vec![ours])) {
+            assert!(lifecycle.included(&inclusion.tx_hash));
+        }
+        assert_eq!(lifecycle.state_of(10, "Backrun_test"), Some(OpportunityState::Included));
+    }
+}
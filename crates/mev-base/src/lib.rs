@@ -0,0 +1,46 @@
+//! mevbase: flashblock-driven MEV search and submission for OP-stack chains.
+//!
+//! The node binary, replay tools and integration tests all build the same [`pipeline::MevPipeline`].
+
+mod lifecycle_timing;
+pub mod flashblocks;
+pub mod flashblock_state;
+pub mod mev_bundle_types;
+pub mod mev_search_worker;
+mod mev_simulation;
+mod mev_task_worker;
+mod revm_flashblock_executor;
+mod gradient_descent;
+mod gradient_descent_parallel;
+mod gradient_descent_fast;
+mod gradient_descent_multicall;
+mod gradient_descent_binary;
+mod executor_codec;
+pub mod gas_history_store;
+pub mod gas_limit_estimator;
+mod warm_start;
+mod bounds_controller;
+pub mod redis_client;
+pub mod backrun_analyzer;
+pub mod logging;
+pub mod config;
+pub mod network;
+pub mod transaction_service;
+pub mod wallet_service;
+pub mod sequencer_service;
+mod submission_error;
+#[cfg(test)]
+mod mock_sequencer;
+pub mod metrics;
+pub mod database_service;
+mod arbitrage;
+pub mod fee_multiplier_controller;
+mod competitor_analyzer;
+mod bundle_simulator;
+mod inclusion_tracker;
+pub mod opportunity_lifecycle;
+pub mod shutdown;
+pub mod pipeline;
+pub mod state_source;
+pub mod remote_state;
+pub mod runtime;
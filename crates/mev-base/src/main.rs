@@ -3,44 +3,19 @@ use reth_optimism_node::{
     node::OpAddOns,
     OpNode,
 };
-use reth_optimism_cli::Cli;
-use reth_provider::{ReceiptProvider, BlockNumReader};
-use reth_optimism_chainspec::BASE_MAINNET;
-use alloy_rpc_types_eth::BlockId;
-use alloy_primitives::B256;
+use reth_optimism_cli::{chainspec::OpChainSpecParser, Cli};
+use reth_provider::ReceiptProvider;
 
 use futures::TryStreamExt;
 use reth_exex::{ExExContext, ExExEvent, ExExNotification};
 use reth_node_api::FullNodeComponents;
 
-use std::sync::Arc;
-use tracing::{info, debug, error, warn};
-use crate::transaction_service::{TransactionService, TransactionServiceConfig, WalletStrategy};
-use crate::wallet_service::WalletService;
-use crate::sequencer_service::SequencerService;
-
-mod lifecycle_timing;
-mod flashblocks;
-mod flashblock_state;
-mod mev_bundle_types;
-mod mev_search_worker;
-mod mev_simulation;
-mod mev_task_worker;
-mod revm_flashblock_executor;
-mod gradient_descent;
-mod gradient_descent_parallel;
-mod gradient_descent_fast;
-mod gradient_descent_multicall;
-mod gradient_descent_binary;
-mod gas_history_store;
-pub mod backrun_analyzer;
-mod logging;
-mod transaction_service;
-mod wallet_service;
-mod sequencer_service;
-mod metrics;
-mod database_service;
-mod arbitrage;
+use tracing::{info, debug, warn};
+use mevbase::config::{redact_url, MevArgs, MevConfig};
+use mevbase::remote_state::RpcStateSource;
+use mevbase::runtime::MevRuntime;
+use mevbase::state_source::ProviderStateSource;
+use mevbase::logging;
 
 /// Block subscriber ExEx that echoes block numbers
 async fn block_subscriber_exex<Node: FullNodeComponents>(
@@ -99,7 +74,23 @@ fn main() -> eyre::Result<()> {
     // Initialize logging before anything else
     logging::init_logging();
     
-    Cli::parse_args().run(|builder, rollup_args| async move {
+    Cli::<OpChainSpecParser, MevArgs>::parse_args().run(|builder, mev_args| async move {
+        // Fail before launching the node if the configuration is unusable
+        let mut config = MevConfig::from_args(&mev_args)?;
+        info!("Effective mevbase configuration:\n{}", config.to_redacted_toml());
+        
+        // The chain comes from the node's --chain; endpoints default per network
+        let network = config.network(builder.config().chain.clone())?;
+        info!(
+            network = %network.network,
+            chain_id = network.chain_id,
+            flashblocks_url = %redact_url(&network.flashblocks_url),
+            sequencer_url = %redact_url(&network.sequencer_url),
+            backrun_processors = network.address_book.backrun_processors.len(),
+            "🌐 Network resolved"
+        );
+        
+        let rollup_args = mev_args.rollup;
         let node = OpNode::new(rollup_args.clone());
         let handle = builder
             .with_types::<OpNode>()
@@ -121,508 +112,21 @@ fn main() -> eyre::Result<()> {
             .await?;
 
         
-        // Get the provider from the node for revm executor
+        // Get the provider from the node for the state source
         let blockchain_provider = handle.node.provider().clone();
         
-        // Start flashblocks client
-        let mut flashblocks_client = flashblocks::FlashblocksClient::new(
-            // "wss://mainnet.flashblocks.base.org/ws".to_string(),
-            "ws://127.0.0.1:6081".to_string(),
-            4096, // event buffer size
-        );
-        
-        // Subscribe to flashblocks events
-        let mut flashblocks_receiver = flashblocks_client.subscribe();
-        
-        // Start the flashblocks connection
-        flashblocks_client.start().await?;
-        
-        info!("Flashblocks client connected to wss://mainnet.flashblocks.base.org/ws");
-        
-        
-        // Create a channel for flashblock processing queue
-        let (flashblock_tx, mut flashblock_rx) = tokio::sync::mpsc::channel(100);
-        
-        // Spawn task to receive flashblocks and queue them
-        tokio::spawn(async move {
-            while let Ok(event) = flashblocks_receiver.recv().await {
-                debug!(
-                    block = event.block_number,
-                    flashblock = event.index,
-                    tx_count = event.transactions.len(),
-                    state_root = %event.state_root,
-                    receipts_root = %event.receipts_root,
-                    "Flashblocks event received"
-                );
-                
-                // Increment metrics
-                crate::metrics::MEV_METRICS.flashblocks_received_total.increment(1);
-                
-                // Queue the event for processing
-                if let Err(e) = flashblock_tx.send(event).await {
-                    error!(error = %e, "Failed to queue flashblock");
-                }
-            }
-        });
-        
-        // Clone provider for the spawned task
-        let blockchain_provider_for_task = blockchain_provider.clone();
-        
-        // Create channel for MEV results
-        let (mev_result_tx, mut mev_result_rx) = tokio::sync::mpsc::channel::<mev_search_worker::MevOpportunity>(1000);
-        
-        // Initialize database service for transaction logging
-        let db_service = match database_service::DatabaseService::new().await {
-            Ok(service) => {
-                info!("Database service initialized for transaction logging");
-                Some(Arc::new(service))
-            }
-            Err(e) => {
-                warn!("Failed to initialize database service: {}. Transaction logging disabled.", e);
-                None
-            }
-        };
-        
-        // Create timing tracker
-        let timing_tracker = lifecycle_timing::create_timing_tracker();
-        
-        // Initialize transaction services
-        let wallet_service = match WalletService::from_env() {
-            Ok(service) => {
-                info!("Wallet service initialized with {} wallets", service.wallet_count());
-                Arc::new(service)
-            }
-            Err(e) => {
-                warn!("Failed to initialize wallet service: {}. Transaction submission disabled.", e);
-                // Create empty wallet service
-                Arc::new(WalletService::new(vec![]).unwrap())
-            }
-        };
+        let runtime = MevRuntime::build(&mut config, network).await?;
         
-        let sequencer_service = match SequencerService::from_env() {
-            Ok(service) => {
-                info!("Sequencer service initialized");
-                Arc::new(service)
+        // Intake, simulation, search and submission over the flashblock feed, reading state from
+        // the node's provider unless a remote node is configured
+        let pipeline = match config.remote_state_config() {
+            Some(remote_state) => {
+                info!(url = %redact_url(&remote_state.rpc_url), "Reading chain state from remote node");
+                runtime.start_pipeline(RpcStateSource::new(&remote_state), &config)?
             }
-            Err(e) => {
-                error!("Failed to initialize sequencer service: {}", e);
-                return Err(e.into());
-            }
-        };
-        
-        // Initialize gas history store using REDIS_LOCAL_* config (fallback to REDIS_*)
-        let redis_host = std::env::var("REDIS_LOCAL_HOST")
-            .or_else(|_| std::env::var("REDIS_HOST"))
-            .unwrap_or_else(|_| "localhost".to_string());
-        let redis_port = std::env::var("REDIS_LOCAL_PORT")
-            .or_else(|_| std::env::var("REDIS_PORT"))
-            .unwrap_or_else(|_| "6379".to_string())
-            .parse::<u16>()
-            .unwrap_or(6379);
-        let redis_password = std::env::var("REDIS_LOCAL_PASSWORD")
-            .or_else(|_| std::env::var("REDIS_PASSWORD"))
-            .unwrap_or_default();
-        
-        info!("Initializing gas history store with Redis at {}:{}", redis_host, redis_port);
-        let gas_history_store = Arc::new(
-            crate::gas_history_store::GasHistoryStore::new(&redis_host, redis_port, &redis_password).await
-        );
-        info!("Gas history store initialized");
-        
-        // Load transaction service config from env
-        let tx_config = TransactionServiceConfig {
-            enabled: std::env::var("BLOCK_TX_ENABLED")
-                .unwrap_or_else(|_| "true".to_string())
-                .parse::<bool>()
-                .unwrap_or(true),
-            dry_run: std::env::var("BLOCK_TX_DRY_RUN")
-                .unwrap_or_else(|_| "false".to_string())
-                .parse::<bool>()
-                .unwrap_or(false),
-            chain_id: 8453, // Base mainnet
-            default_gas_limit: std::env::var("BLOCK_TX_DEFAULT_GAS_LIMIT")
-                .ok()
-                .and_then(|s| s.parse::<u64>().ok()),
-            gas_multiplier: std::env::var("BLOCK_TX_GAS_MULTIPLIER")
-                .unwrap_or_else(|_| "1.2".to_string())
-                .parse::<f64>()
-                .unwrap_or(1.2),
-            wallet_strategy: match std::env::var("BLOCK_TX_WALLET_STRATEGY")
-                .unwrap_or_else(|_| "default".to_string())
-                .to_lowercase()
-                .as_str() {
-                "random" => WalletStrategy::Random,
-                "round-robin" => WalletStrategy::RoundRobin,
-                _ => WalletStrategy::Default,
-            },
+            None => runtime.start_pipeline(ProviderStateSource::new(blockchain_provider), &config)?,
         };
         
-        let transaction_service = Arc::new(TransactionService::new(
-            tx_config.clone(),
-            wallet_service.clone(),
-            sequencer_service.clone(),
-        ));
-        
-        info!(
-            enabled = tx_config.enabled,
-            dry_run = tx_config.dry_run,
-            wallet_strategy = ?tx_config.wallet_strategy,
-            "Transaction service initialized"
-        );
-        
-        // Define minimum profit threshold from env or default (before spawning tasks)
-        let min_profit_threshold = std::env::var("MEV_MIN_PROFIT_THRESHOLD")
-            .ok()
-            .and_then(|s| s.parse::<u64>().ok())
-            .map(alloy_primitives::U256::from)
-            .unwrap_or_else(|| alloy_primitives::U256::from(10_000_000_000_000u64)); // Default: 0.00001 ETH (10 microether)
-        
-        info!(
-            threshold_wei = %min_profit_threshold,
-            threshold_eth = format!("{:.6}", min_profit_threshold.as_limbs()[0] as f64 / 1e18),
-            "MEV profit threshold configured"
-        );
-        
-        // Clone for the MEV handler task
-        let threshold_for_handler = min_profit_threshold;
-        
-        // Clone provider for MEV handler
-        let mev_provider = blockchain_provider.clone();
-        
-        // Configuration for opportunity processing
-        const MAX_CONCURRENT_OPPORTUNITIES: usize = 5;
-        const OPPORTUNITY_TIMEOUT_SECS: u64 = 10;
-        const MAX_BLOCK_STALENESS: u64 = 2;
-        
-        // Spawn MEV opportunity handler with parallel processing
-        tokio::spawn(async move {
-            let semaphore = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_OPPORTUNITIES));
-            let submitted_txs = Arc::new(dashmap::DashSet::<(u64, String)>::new()); // (block_number, processor)
-            
-            while let Some(opportunity) = mev_result_rx.recv().await {
-                // Get current block number for staleness check
-                let current_block = match mev_provider.best_block_number() {
-                    Ok(num) => num,
-                    Err(e) => {
-                        error!(error = ?e, "Failed to get current block number");
-                        continue;
-                    }
-                };
-                
-                // Check if opportunity is stale
-                if opportunity.block_number + MAX_BLOCK_STALENESS < current_block {
-                    warn!(
-                        strategy = %opportunity.strategy,
-                        opportunity_block = opportunity.block_number,
-                        current_block = current_block,
-                        blocks_behind = current_block - opportunity.block_number,
-                        "Skipping stale MEV opportunity"
-                    );
-                    continue;
-                }
-                
-                // Check if we already submitted a transaction for this block and processor
-                let block_processor_key = (opportunity.block_number, opportunity.strategy.clone());
-                if !submitted_txs.insert(block_processor_key.clone()) {
-                    debug!(
-                        strategy = %opportunity.strategy,
-                        block = opportunity.block_number,
-                        "Skipping - already submitted tx for this block/processor"
-                    );
-                    continue;
-                }
-                
-                info!(
-                    strategy = %opportunity.strategy,
-                    block = opportunity.block_number,
-                    flashblock = opportunity.flashblock_index,
-                    profit_wei = %opportunity.expected_profit,
-                    bundle_size = opportunity.bundle.transactions.len(),
-                    current_block = current_block,
-                    "MEV opportunity found (first for this block/processor)"
-                );
-                
-                // Record opportunity metrics
-                crate::metrics::MEV_METRICS.opportunities_found_total.increment(1);
-                
-                // Skip if below threshold
-                if opportunity.expected_profit <= threshold_for_handler {
-                    debug!(
-                        strategy = %opportunity.strategy,
-                        profit_wei = %opportunity.expected_profit,
-                        threshold_wei = %threshold_for_handler,
-                        "MEV opportunity below profit threshold, skipping"
-                    );
-                    continue;
-                }
-                
-                // Process profitable opportunities in parallel
-                let permit = semaphore.clone().acquire_owned().await.unwrap();
-                let tx_service = transaction_service.clone();
-                let provider = mev_provider.clone();
-                let submitted_txs_clone = submitted_txs.clone();
-                let block_processor_key_clone = block_processor_key.clone();
-                
-                tokio::spawn(async move {
-                    // Hold permit for duration of processing
-                    let _permit = permit;
-                    
-                    crate::metrics::MEV_METRICS.opportunities_profitable_total.increment(1);
-                    
-                    // Process with timeout
-                    let process_start = std::time::Instant::now();
-                    let timeout = tokio::time::Duration::from_secs(OPPORTUNITY_TIMEOUT_SECS);
-                    
-                    match tokio::time::timeout(
-                        timeout,
-                        tx_service.process_opportunity(&opportunity, &provider)
-                    ).await {
-                        Ok(Ok(tx_hash)) => {
-                            let elapsed = process_start.elapsed();
-                            info!(
-                                strategy = %opportunity.strategy,
-                                block = opportunity.block_number,
-                                elapsed_ms = elapsed.as_millis(),
-                                tx_hash = ?tx_hash,
-                                "🎊🎉 MEV OPPORTUNITY CAPTURED! 🎯💸 {} strategy executed in {}ms! 🚀⚡ Transaction: {:?} 🌟🔥",
-                                opportunity.strategy,
-                                elapsed.as_millis(),
-                                tx_hash
-                            );
-                            
-                            // Log to JSON with transaction hash
-                            if let Err(e) = log_mev_opportunity_to_json(&opportunity, tx_hash) {
-                                error!(error = ?e, "Failed to log MEV opportunity to JSON");
-                            }
-                        }
-                        Ok(Err(e)) => {
-                            error!(
-                                strategy = %opportunity.strategy,
-                                block = opportunity.block_number,
-                                error = ?e,
-                                "Failed to process MEV opportunity"
-                            );
-                            
-                            // Log to JSON even on failure (with no tx hash)
-                            if let Err(log_err) = log_mev_opportunity_to_json(&opportunity, None) {
-                                error!(error = ?log_err, "Failed to log MEV opportunity to JSON");
-                            }
-                            
-                            // Remove from submitted set so another flashblock can try
-                            submitted_txs_clone.remove(&block_processor_key_clone);
-                        }
-                        Err(_) => {
-                            error!(
-                                strategy = %opportunity.strategy,
-                                block = opportunity.block_number,
-                                timeout_secs = OPPORTUNITY_TIMEOUT_SECS,
-                                "MEV opportunity processing timed out"
-                            );
-                            
-                            // Log to JSON even on timeout (with no tx hash)
-                            if let Err(log_err) = log_mev_opportunity_to_json(&opportunity, None) {
-                                error!(error = ?log_err, "Failed to log MEV opportunity to JSON");
-                            }
-                            
-                            // Remove from submitted set so another flashblock can try
-                            submitted_txs_clone.remove(&block_processor_key_clone);
-                        }
-                    }
-                });
-            }
-        });
-        
-        // Clone database service for flashblock thread
-        let db_service_for_flashblocks = db_service.clone();
-        let gas_history_store_for_flashblocks = gas_history_store.clone();
-        
-        // Spawn dedicated synchronous flashblock simulator thread
-        tokio::spawn(async move {
-            info!("Starting dedicated flashblock simulator thread");
-            
-            // Create revm executor with the node's provider
-            let chain_spec = BASE_MAINNET.clone();
-            let mut revm_executor = revm_flashblock_executor::RevmFlashblockExecutor::new(chain_spec.clone());
-            let mut revm_initialized = false;
-            let mut current_block = 0u64;
-            
-            while let Some(event) = flashblock_rx.recv().await {
-                let sim_start = std::time::Instant::now();
-                
-                // Create lifecycle timing for this flashblock
-                let mut timing = lifecycle_timing::LifecycleTiming::new(
-                    event.received_at,
-                    event.block_number,
-                    event.index,
-                );
-                timing.processing_started = Some(sim_start);
-                
-                // Record queue latency metric
-                let queue_latency = sim_start.duration_since(event.received_at).as_secs_f64();
-                crate::metrics::MEV_METRICS.flashblock_queue_latency_seconds.record(queue_latency);
-                
-                // Clone for workers
-                let timing_for_workers = Arc::new(tokio::sync::Mutex::new(Some(timing.clone())));
-                *timing_tracker.lock().await = Some(timing.clone());
-                
-                debug!(
-                    block = event.block_number,
-                    flashblock = event.index,
-                    "Processing flashblock in simulator thread"
-                );
-                
-                // Log all transaction hashes from this flashblock to database
-                if let Some(ref db_service) = db_service_for_flashblocks {
-                    let tx_logs: Vec<database_service::TransactionLog> = event.transactions.iter()
-                        .map(|tx| database_service::TransactionLog {
-                            hash: *tx.tx_hash(),
-                            source: format!("flashblock_{}", event.index),
-                            timestamp: chrono::Utc::now(),
-                            block_number: event.block_number,
-                        })
-                        .collect();
-                    
-                    if !tx_logs.is_empty() {
-                        debug!(
-                            count = tx_logs.len(),
-                            block = event.block_number,
-                            flashblock = event.index,
-                            "Logging flashblock transactions to database"
-                        );
-                        
-                        if let Err(e) = db_service.log_transactions(tx_logs).await {
-                            error!("Failed to log flashblock transactions: {}", e);
-                        }
-                    }
-                }
-                
-                // Use revm-based executor
-                debug!("Using revm-based executor");
-                
-                // Re-initialize for new block if needed
-                if !revm_initialized || event.block_number != current_block {
-                    if event.block_number != current_block {
-                        debug!(old_block = current_block, new_block = event.block_number, "New block detected");
-                        current_block = event.block_number;
-                    }
-                    
-                    match revm_executor.initialize(blockchain_provider_for_task.clone(), BlockId::latest()).await {
-                        Ok(_) => {
-                            debug!("Revm executor initialized with node provider");
-                            revm_initialized = true;
-                        }
-                        Err(e) => {
-                            error!(error = ?e, "Failed to initialize revm executor");
-                            continue;
-                        }
-                    }
-                }
-                
-                // Execute with revm
-                match revm_executor.execute_flashblock(&event, event.index).await {
-                    Ok(results) => {
-                        let successful = results.iter().filter(|r| r.error.is_none()).count();
-                        debug!(
-                            successful = successful,
-                            total = results.len(),
-                            "Revm execution complete"
-                        );
-                        
-                        // Update timing and record metric
-                        timing.execution_completed = Some(std::time::Instant::now());
-                        let exec_duration = timing.execution_completed.unwrap().duration_since(timing.processing_started.unwrap()).as_secs_f64();
-                        crate::metrics::MEV_METRICS.flashblock_execution_duration_seconds.record(exec_duration);
-                        
-                        // Export state snapshot and trigger MEV search
-                        let export_start = std::time::Instant::now();
-                        match revm_executor.export_state_snapshot(event.index, event.transactions.clone()) {
-                            Ok(state_snapshot) => {
-                                let export_time = export_start.elapsed().as_secs_f64() * 1000.0;
-                                debug!(
-                                    accounts = state_snapshot.account_changes.len(),
-                                    time_ms = export_time,
-                                    "State snapshot exported"
-                                );
-                                
-                                // Update timing and record metric
-                                timing.state_export_completed = Some(std::time::Instant::now());
-                                let export_duration = export_start.elapsed().as_secs_f64();
-                                crate::metrics::MEV_METRICS.state_export_duration_seconds.record(export_duration);
-                                
-                                // Analyze state to determine which strategies to trigger
-                                let strategies = mev_search_worker::analyze_state_for_strategies(&state_snapshot);
-                                timing.strategy_analysis_completed = Some(std::time::Instant::now());
-                                
-                                if !strategies.is_empty() {
-                                    debug!(
-                                        count = strategies.len(),
-                                        strategies = ?strategies,
-                                        "Triggering MEV strategies"
-                                    );
-                                    
-                                    // Spawn all MEV tasks in batch for reduced overhead
-                                    mev_task_worker::spawn_mev_tasks_batch(
-                                        chain_spec.clone(),
-                                        blockchain_provider_for_task.clone(),
-                                        strategies,
-                                        state_snapshot.clone(),
-                                        event.received_at,
-                                        mev_result_tx.clone(),
-                                        Some(timing_for_workers.clone()),
-                                        min_profit_threshold,
-                                        gas_history_store_for_flashblocks.clone(),
-                                    );
-                                    timing.workers_spawned = Some(std::time::Instant::now());
-                                } else {
-                                    debug!("No MEV strategies triggered for this flashblock");
-                                }
-                                
-                                // Benchmarking removed - no longer needed
-                            }
-                            Err(e) => {
-                                error!(error = ?e, "Failed to export state snapshot");
-                            }
-                        }
-                        
-                        // Example: Simulate an MEV bundle on top of the current flashblock state
-                        // This demonstrates how to test MEV opportunities after flashblock execution
-                        if event.index == 10 && !event.transactions.is_empty() {
-                            debug!("Testing MEV bundle simulation on final flashblock");
-                            
-                            // Create a test bundle with one of the existing transactions
-                            // In real usage, this would be your MEV transactions
-                            let test_bundle = vec![event.transactions[0].clone()];
-                            
-                            match revm_executor.simulate_bundle(test_bundle, event.block_number).await {
-                                Ok(_bundle_results) => {
-                                    debug!("MEV bundle simulation completed");
-                                }
-                                Err(e) => {
-                                    error!(error = ?e, "MEV bundle simulation failed");
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!(error = ?e, "Revm execution failed");
-                    }
-                }
-                
-                let total_time = sim_start.elapsed().as_secs_f64() * 1000.0;
-                debug!(
-                    block = event.block_number,
-                    flashblock = event.index,
-                    time_ms = total_time,
-                    "Flashblock processing completed"
-                );
-                
-                // Update timing tracker with final timing
-                *timing_tracker.lock().await = Some(timing);
-            }
-            
-            warn!("Flashblock simulator thread exiting");
-        });
-        
         // // Spawn a task to simulate calls every 2 seconds
         // tokio::spawn(async move {
         //     let mut interval = tokio::time::interval(std::time::Duration::from_secs(2));
@@ -657,76 +161,14 @@ fn main() -> eyre::Result<()> {
         //     }
         // });
 
+        // Drain and persist on the node's graceful shutdown, which waits for the guard; in-flight
+        // submissions are tracked by the pipeline's shutdown signal
+        let shutdown_pipeline = runtime.shutdown_pipeline(&pipeline, &config);
+        handle.node.task_executor.spawn_critical_with_graceful_shutdown_signal("mev-shutdown", |signal| async move {
+            let _guard = signal.await;
+            shutdown_pipeline.run().await;
+        });
+
         handle.wait_for_node_exit().await
     })
 }
-
-/// Log MEV opportunity to JSON file
-fn log_mev_opportunity_to_json(opportunity: &mev_search_worker::MevOpportunity, tx_hash: Option<B256>) -> eyre::Result<()> {
-    use std::fs::OpenOptions;
-    use std::io::Write;
-    use serde::Serialize;
-    
-    #[derive(Serialize)]
-    struct MevResultLog {
-        timestamp: String,
-        block_number: u64,
-        flashblock_index: u32,
-        strategy: String,
-        expected_profit_wei: String,
-        expected_profit_eth: f64,
-        bundle_size: usize,
-        // Add first transaction details if available
-        first_tx_to: Option<String>,
-        first_tx_calldata: Option<String>,
-        // Hash of the last transaction in the flashblock
-        index_hash: Option<String>,
-        // Scan ID to link back to trigger
-        scan_id: String,
-        // Transaction hash if submitted
-        transaction_hash: Option<String>,
-    }
-    
-    let first_tx = opportunity.bundle.transactions.first();
-    
-    let (first_tx_to, first_tx_calldata) = match first_tx {
-        Some(mev_bundle_types::BundleTransaction::Unsigned { to, input, .. }) => {
-            (to.map(|addr| format!("{:?}", addr)), Some(format!("0x{}", hex::encode(input))))
-        }
-        Some(mev_bundle_types::BundleTransaction::Signed(_)) => {
-            // For signed transactions, we'd need to decode the envelope
-            (None, None)
-        }
-        None => (None, None),
-    };
-    
-    // Get current time with milliseconds
-    let now = chrono::Utc::now();
-    let timestamp = now.format("%Y-%m-%d %H:%M:%S%.3f UTC").to_string();
-    
-    let result = MevResultLog {
-        timestamp,
-        block_number: opportunity.block_number,
-        flashblock_index: opportunity.flashblock_index,
-        strategy: opportunity.strategy.clone(),
-        expected_profit_wei: opportunity.expected_profit.to_string(),
-        expected_profit_eth: opportunity.expected_profit.as_limbs()[0] as f64 / 1e18,
-        bundle_size: opportunity.bundle.transactions.len(),
-        first_tx_to,
-        first_tx_calldata,
-        index_hash: opportunity.last_flashblock_tx_hash.map(|h| format!("{:?}", h)),
-        scan_id: opportunity.scan_id.clone(),
-        transaction_hash: tx_hash.map(|h| format!("{:?}", h)),
-    };
-    
-    // Append to JSONL file (JSON Lines format)
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("mev_results.jsonl")?;
-    
-    let json = serde_json::to_string(&result)?;
-    writeln!(file, "{}", json)?;
-    
-    Ok(())
-}
\ No newline at end of file
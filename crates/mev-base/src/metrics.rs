@@ -36,6 +36,33 @@ pub struct MevMetrics {
     
     /// Total flashblock processing time (websocket to workers spawned)
     pub flashblock_total_duration_seconds: Histogram,
+    
+    /// Our submissions seen landing in a flashblock
+    pub self_inclusions_total: Counter,
+    
+    /// Transactions from our wallets that landed without a tracked submission
+    pub untracked_self_inclusions_total: Counter,
+    
+    /// Submission send to inclusion in a flashblock
+    pub inclusion_latency_seconds: Histogram,
+    
+    /// Opportunity state export to inclusion of its submission
+    pub opportunity_to_inclusion_seconds: Histogram,
+    
+    /// Flashblock index our submissions landed in
+    pub inclusion_flashblock_index: Histogram,
+    
+    /// Position within the flashblock our submissions landed at
+    pub inclusion_position: Histogram,
+    
+    /// Transactions between the trigger and our landed submission
+    pub inclusion_gap_from_trigger: Histogram,
+    
+    /// Total number of fee multiplier adjustments from measured outcomes
+    pub fee_multiplier_updates_total: Counter,
+    
+    /// Send-to-first-acknowledgement latency across all submission endpoints
+    pub submission_ack_duration_seconds: Histogram,
 }
 
 /// Per-strategy MEV metrics
@@ -59,6 +86,12 @@ pub struct MevStrategyMetrics {
     
     /// Profit amount in wei (as histogram to track distribution)
     pub profit_wei: Histogram,
+    
+    /// Searches seeded with a bracket around recent winners
+    pub warm_starts_total: Counter,
+    
+    /// Warm searches re-run over the full range
+    pub warm_start_fallbacks_total: Counter,
 }
 
 /// Global MEV metrics instance
@@ -1,6 +1,23 @@
-use alloy_consensus::TxEnvelope;
+use alloy_consensus::{Transaction, TxEnvelope};
 use alloy_primitives::{Address, Bytes, U256};
 
+/// The wallet a bundle will be submitted from, as seen on the simulated state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationSender {
+    pub address: Address,
+    /// Account nonce on the simulated state
+    pub nonce: u64,
+    /// Account balance on the simulated state
+    pub balance: U256,
+}
+
+/// Fees a leg is simulated and submitted with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeQuote {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
 /// A transaction that can be either signed or unsigned
 #[derive(Debug, Clone)]
 pub enum BundleTransaction {
@@ -13,7 +30,8 @@ pub enum BundleTransaction {
         value: U256,
         input: Bytes,
         gas_limit: u64,
-        gas_price: U256,
+        /// Fees the leg was simulated with; a zero max fee asks the submitter to quote
+        fees: FeeQuote,
         nonce: u64,
     },
 }
@@ -27,7 +45,7 @@ impl BundleTransaction {
         value: U256,
         input: Bytes,
         gas_limit: u64,
-        gas_price: U256,
+        fees: FeeQuote,
         nonce: u64,
     ) -> Self {
         Self::Unsigned {
@@ -36,7 +54,7 @@ impl BundleTransaction {
             value,
             input,
             gas_limit,
-            gas_price,
+            fees,
             nonce,
         }
     }
@@ -49,10 +67,18 @@ impl BundleTransaction {
         value: U256,
         input: Bytes,
         gas_limit: u64,
-        gas_price: U256,
+        fees: FeeQuote,
         nonce: u64,
     ) -> Self {
-        Self::unsigned(from, Some(to), value, input, gas_limit, gas_price, nonce)
+        Self::unsigned(from, Some(to), value, input, gas_limit, fees, nonce)
+    }
+    
+    /// Call target (None for contract creation)
+    pub fn to(&self) -> Option<Address> {
+        match self {
+            Self::Signed(tx) => tx.to(),
+            Self::Unsigned { to, .. } => *to,
+        }
     }
     
     /// Get a hash for logging (returns zero hash for unsigned)
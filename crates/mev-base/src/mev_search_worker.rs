@@ -43,6 +43,10 @@ pub struct MevOpportunity {
     pub scan_id: String,
     /// The processor config that generated this opportunity
     pub processor_config: Option<crate::backrun_analyzer::TokenPairProcessorConfig>,
+    /// The flashblock state the opportunity was found on (used for bundle simulation)
+    pub state_snapshot: Option<Arc<FlashblockStateSnapshot>>,
+    /// Storage slots the simulation read and their values, used as submission preconditions
+    pub known_accounts: crate::sequencer_service::KnownAccounts,
 }
 
 /// Work-stealing MEV search system optimized for high core counts
@@ -230,14 +234,12 @@ mod base_addresses {
 }
 
 /// Analyze state changes to determine which MEV strategies to trigger
-pub fn analyze_state_for_strategies(state: &FlashblockStateSnapshot) -> Vec<MevStrategy> {
+pub fn analyze_state_for_strategies(backrun_analyzer: &crate::backrun_analyzer::BackrunAnalyzer, state: &FlashblockStateSnapshot) -> Vec<MevStrategy> {
     use std::collections::HashSet;
-    use crate::backrun_analyzer::BackrunAnalyzer;
     
     let mut strategies = HashSet::new();
     
     // Check for backrun opportunities using BackrunAnalyzer
-    let backrun_analyzer = BackrunAnalyzer::new(U256::from(10_000_000_000_000u64)); // 0.00001 ETH (10 microether) min profit
     let triggered_configs = backrun_analyzer.analyze_state_for_backrun(state);
     if !triggered_configs.is_empty() {
         let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
@@ -1,9 +1,10 @@
 use alloy_rpc_types_eth::{BlockId, EthCallResponse};
-use reth_provider::{StateProviderFactory, HeaderProvider, BlockReader};
-use reth_optimism_chainspec::BASE_MAINNET;
+use reth_optimism_chainspec::OpChainSpec;
+use std::sync::Arc;
 use crate::revm_flashblock_executor::RevmFlashblockExecutor;
 use crate::flashblocks::FlashblocksEvent;
 use crate::mev_bundle_types::MevBundle;
+use crate::state_source::StateSource;
 
 /// Simulate an MEV bundle on top of accumulated flashblock state
 /// 
@@ -13,28 +14,25 @@ use crate::mev_bundle_types::MevBundle;
 /// 3. Simulates your MEV bundle on top of that state
 /// 
 /// # Arguments
-/// * `provider` - The blockchain state provider
+/// * `source` - Where chain state is read from
+/// * `chain_spec` - Chain spec of the network the node runs on
 /// * `flashblocks` - All flashblocks for the current block (indices 0-10)
 /// * `mev_bundle` - Your MEV transactions to simulate
 /// 
 /// # Returns
 /// Results for each transaction in your MEV bundle
 #[allow(dead_code)]
-pub async fn simulate_mev_bundle_on_flashblocks<P>(
-    provider: P,
+pub async fn simulate_mev_bundle_on_flashblocks<S: StateSource>(
+    source: S,
+    chain_spec: Arc<OpChainSpec>,
     flashblocks: Vec<FlashblocksEvent>,
     mev_bundle: MevBundle,
-) -> eyre::Result<Vec<EthCallResponse>>
-where
-    P: StateProviderFactory + HeaderProvider + BlockReader + Clone,
-    P::Header: alloy_consensus::BlockHeader,
-{
+) -> eyre::Result<Vec<EthCallResponse>> {
     // Create executor
-    let chain_spec = BASE_MAINNET.clone();
     let mut executor = RevmFlashblockExecutor::new(chain_spec);
     
     // Initialize with latest state
-    executor.initialize(provider, BlockId::latest()).await?;
+    executor.initialize(source, BlockId::latest()).await?;
     
     println!("🎯 MEV Bundle Simulation on Flashblock State");
     println!("   ├─ Flashblocks to apply: {}", flashblocks.len());
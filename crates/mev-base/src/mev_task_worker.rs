@@ -1,5 +1,4 @@
-use reth_provider::StateProviderFactory;
-use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+use reth_revm::db::CacheDB;
 use revm::database::{DbAccount, AccountState};
 use revm::Database;
 use reth_optimism_evm::OpEvmConfig;
@@ -7,6 +6,7 @@ use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_node::OpRethReceiptBuilder;
 use reth_optimism_primitives::OpPrimitives;
 use reth_evm::ConfigureEvm;
+use reth_chainspec::EthChainSpec;
 use std::sync::Arc;
 use tracing::{debug, trace, info, warn, error};
 
@@ -16,8 +16,40 @@ use crate::backrun_analyzer::BackrunAnalyzer;
 use crate::gradient_descent::GradientParams;
 use crate::gradient_descent_binary::BinarySearchGradientOptimizer;
 use crate::lifecycle_timing::TimingTracker;
+use crate::sequencer_service::KnownAccounts;
+use crate::mev_bundle_types::{FeeQuote, SimulationSender};
+use crate::executor_codec::{PackedValue, DEFAULT_BRIBE_RATE};
+use crate::pipeline::{SubmissionSink, SubmissionWallets};
+use crate::gas_limit_estimator::{search_min_gas_limit, MAX_BACKRUN_GAS_LIMIT};
+use crate::gas_history_store::QtySample;
+use crate::warm_start::{needs_exploration, warm_bracket, WarmStartConfig};
+use crate::bounds_controller::{self, BoundsControllerConfig, BoundsObservation, BoundsState};
+use crate::state_source::StateSource;
 
-/// A short-lived MEV task that gets its own StateProvider
+/// Upper bound on storage slots carried as submission preconditions
+const MAX_KNOWN_SLOTS: usize = 128;
+
+/// Simulation-only sender used when no wallet is configured
+const FALLBACK_SENDER: alloy_primitives::Address = alloy_primitives::Address::new([
+    0xc0, 0xff, 0xee, 0x48, 0x94, 0x5a, 0x95, 0x18, 0xb0, 0xb5,
+    0x43, 0xa2, 0xc5, 0x9d, 0xfb, 0x10, 0x22, 0x21, 0xfb, 0xb7,
+]);
+
+/// Outcome of simulating a single backrun transaction
+struct SimulatedTransaction {
+    gas_used: u64,
+    success: bool,
+    known_accounts: KnownAccounts,
+}
+
+/// Search tuning shared by every worker
+#[derive(Debug, Clone, Default)]
+pub struct SearchConfig {
+    pub warm_start: WarmStartConfig,
+    pub bounds: BoundsControllerConfig,
+}
+
+/// A short-lived MEV task that gets its own state from a [`StateSource`]
 pub struct MevTaskWorker {
     /// The chain specification
     chain_spec: Arc<OpChainSpec>,
@@ -31,8 +63,16 @@ pub struct MevTaskWorker {
     timing_tracker: Option<TimingTracker>,
     /// Minimum profit threshold for logging
     min_profit_threshold: alloy_primitives::U256,
+    /// Processor configs of the network, shared by every worker
+    analyzer: Arc<BackrunAnalyzer>,
     /// Gas history store for adaptive optimization
     gas_history_store: Arc<crate::gas_history_store::GasHistoryStore>,
+    /// Quotes fees and gas margins the opportunity will be submitted with
+    sink: Arc<dyn SubmissionSink>,
+    /// Selects the wallet the opportunity will be submitted from, so simulation runs as it
+    wallets: Option<Arc<dyn SubmissionWallets>>,
+    /// Warm-start and bounds controller settings
+    search_config: Arc<SearchConfig>,
 }
 
 impl MevTaskWorker {
@@ -43,7 +83,11 @@ impl MevTaskWorker {
         flashblock_received_at: std::time::Instant,
         timing_tracker: Option<TimingTracker>,
         min_profit_threshold: alloy_primitives::U256,
+        analyzer: Arc<BackrunAnalyzer>,
         gas_history_store: Arc<crate::gas_history_store::GasHistoryStore>,
+        sink: Arc<dyn SubmissionSink>,
+        wallets: Option<Arc<dyn SubmissionWallets>>,
+        search_config: Arc<SearchConfig>,
     ) -> Self {
         Self {
             chain_spec,
@@ -52,16 +96,21 @@ impl MevTaskWorker {
             flashblock_received_at,
             timing_tracker,
             min_profit_threshold,
+            analyzer,
             gas_history_store,
+            sink,
+            wallets,
+            search_config,
         }
     }
     
+    /// Chain id of the network the worker simulates
+    fn chain_id(&self) -> u64 {
+        self.chain_spec.chain().id()
+    }
+    
     /// Execute the MEV search task
-    pub async fn execute<P>(self, provider: P) -> eyre::Result<Option<MevOpportunity>>
-    where
-        P: StateProviderFactory + reth_provider::HeaderProvider + reth_provider::BlockReader,
-        P::Header: alloy_consensus::BlockHeader,
-    {
+    pub async fn execute<S: StateSource>(self, source: S) -> eyre::Result<Option<MevOpportunity>> {
         let task_start = std::time::Instant::now();
         let latency_ms = self.flashblock_received_at.elapsed().as_secs_f64() * 1000.0;
         debug!(
@@ -84,20 +133,19 @@ impl MevTaskWorker {
             None
         };
         
-        // Get a fresh state provider - this will hold a database read transaction
+        // Get fresh state - for the node this holds a database read transaction
         let provider_start = std::time::Instant::now();
-        let state_provider = provider.latest()?;
+        let state = source.latest_state()?;
         let provider_time = provider_start.elapsed().as_secs_f64() * 1000.0;
         
-        // Get the block header
-        let header_start = std::time::Instant::now();
-        let _header = provider.header_by_number(provider.best_block_number()?)?
-            .ok_or_else(|| eyre::eyre!("Header not found"))?;
-        let header_time = header_start.elapsed().as_secs_f64() * 1000.0;
+        // Remote sources read the processor's accounts in one batch instead of one at a time
+        let prefetch_start = std::time::Instant::now();
+        source.prefetch(&state, &self.prefetch_accounts());
+        let prefetch_time = prefetch_start.elapsed().as_secs_f64() * 1000.0;
         
-        // Create CacheDB with the state provider
+        // Create CacheDB with the state
         let cache_start = std::time::Instant::now();
-        let mut cache_db = CacheDB::new(StateProviderDatabase::new(state_provider));
+        let mut cache_db = CacheDB::new(state);
         let cache_time = cache_start.elapsed().as_secs_f64() * 1000.0;
         
         // Apply the flashblock state snapshot to the CacheDB
@@ -119,7 +167,7 @@ impl MevTaskWorker {
             .as_secs();
             
         let evm_header = alloy_consensus::Header {
-            number: 33_634_688, // Current Base mainnet block number
+            number: self.state_snapshot.block_number,
             timestamp: current_timestamp, // Today's timestamp to ensure all hardforks active
             gas_limit: 2_000_000_000, // 2 billion gas limit for MEV simulation
             base_fee_per_gas: Some(0), // Zero base fee for MEV simulation
@@ -133,7 +181,7 @@ impl MevTaskWorker {
         let setup_total = task_start.elapsed().as_secs_f64() * 1000.0;
         trace!(
             provider_ms = provider_time,
-            header_ms = header_time,
+            prefetch_ms = prefetch_time,
             cache_ms = cache_time,
             apply_ms = apply_time,
             evm_ms = evm_time,
@@ -186,48 +234,28 @@ impl MevTaskWorker {
             debug!(total_ms = total_time, search_ms = search_time, "Task completed");
         }
         
-        // The state provider (and database transaction) will be dropped here
+        // The state (and any database transaction) will be dropped here
         result
     }
     
+    /// Accounts the strategy reads that the flashblock snapshot may not carry
+    fn prefetch_accounts(&self) -> Vec<alloy_primitives::Address> {
+        let MevStrategy::Backrun(config_name) = &self.strategy;
+        self.analyzer.get_configs().get(config_name)
+            .map(|config| std::iter::once(config.contract_address)
+                .chain(config.accounts.iter().copied())
+                .chain(config.tokens.iter().copied())
+                .filter(|address| !self.state_snapshot.account_changes.contains_key(address))
+                .collect())
+            .unwrap_or_default()
+    }
+    
     /// Apply the flashblock state snapshot to the CacheDB
     fn apply_state_snapshot<DB>(&self, cache_db: &mut CacheDB<DB>) -> eyre::Result<()>
     where
         DB: revm::Database,
     {
-        // Apply account changes
-        for (address, account_info) in &self.state_snapshot.account_changes {
-            // Update or insert account
-            match cache_db.cache.accounts.entry(*address) {
-                std::collections::hash_map::Entry::Occupied(mut entry) => {
-                    let db_account = entry.get_mut();
-                    db_account.info = account_info.clone();
-                    db_account.account_state = AccountState::Touched;
-                }
-                std::collections::hash_map::Entry::Vacant(entry) => {
-                    entry.insert(DbAccount {
-                        info: account_info.clone(),
-                        account_state: AccountState::Touched,
-                        storage: Default::default(),
-                    });
-                }
-            }
-        }
-        
-        // Apply storage changes
-        for (address, storage_changes) in &self.state_snapshot.storage_changes {
-            if let Some(db_account) = cache_db.cache.accounts.get_mut(address) {
-                for (slot, value) in storage_changes {
-                    db_account.storage.insert(*slot, *value);
-                }
-            }
-        }
-        
-        // Apply code changes (contract deployments)
-        for (code_hash, bytecode) in &self.state_snapshot.code_changes {
-            // Add bytecode to contracts cache
-            cache_db.cache.contracts.insert(*code_hash, bytecode.clone());
-        }
+        self.state_snapshot.apply_to(cache_db);
         
         trace!(
             accounts = self.state_snapshot.account_changes.len(),
@@ -257,11 +285,8 @@ impl MevTaskWorker {
         let strategy_metrics = crate::metrics::get_strategy_metrics(&format!("Backrun_{}", config_name));
         strategy_metrics.triggered_total.increment(1);
         
-        // Create a backrun analyzer with the worker's profit threshold
-        let analyzer = BackrunAnalyzer::new(self.min_profit_threshold);
-        
         // Get the configs
-        let configs = analyzer.get_configs();
+        let configs = self.analyzer.get_configs();
         if let Some(config) = configs.get(config_name) {
                 debug!(
                     config = %config_name,
@@ -320,46 +345,78 @@ impl MevTaskWorker {
                 // Calculate bounds based on initial quantity (matching TypeScript logic)
                 let min_qty = (config.default_value / alloy_primitives::U256::from(5)).max(alloy_primitives::U256::from(1)); // max(1, 20% of initial)
                 let max_qty_uncapped = config.default_value.saturating_mul(alloy_primitives::U256::from(1000)); // 1000x initial
-                let max_qty = if max_qty_uncapped > alloy_primitives::U256::from(0xffffff) {
-                    alloy_primitives::U256::from(0xffffff) // Cap at 16.7M (24-bit max)
-                } else {
-                    max_qty_uncapped
-                };
+                let max_qty = max_qty_uncapped.min(config.codec.max_quantity()); // Cap at what the calldata format carries
                 
-                // Get filtered gas and multiplier from Redis for this target
-                let filtered_gas = self.gas_history_store.get_filtered_gas(&config.contract_address).await;
-                let stored_multiplier = self.gas_history_store.get_multiplier(&config.contract_address).await;
+                // Get filtered gas and bounds controller state learned for this target
+                let history = self.gas_history_store.get(&config.contract_address).await;
+                let filtered_gas = history.as_ref().map(|h| h.gas);
+                let bounds_config = &self.search_config.bounds;
+                let bounds = history.as_ref()
+                    .and_then(|h| h.bounds.clone())
+                    .unwrap_or_else(|| {
+                        // Seed from a multiplier stored by the old step heuristic, else start wide
+                        let legacy = history.as_ref().and_then(|h| h.multiplier).map(|m| m as f64);
+                        BoundsState::initial(legacy.unwrap_or(bounds_config.max_multiplier), bounds_config)
+                    });
                 
-                // Apply stored multiplier if available
-                let adjusted_upper_bound = if let Some(multiplier) = stored_multiplier {
-                    let adjusted = config.default_value.saturating_mul(alloy_primitives::U256::from(multiplier));
-                    // Still cap at 16.7M
-                    if adjusted > alloy_primitives::U256::from(0xffffff) {
-                        alloy_primitives::U256::from(0xffffff)
-                    } else {
-                        adjusted
-                    }
-                } else {
-                    max_qty // Use initial 1000x if no history
-                };
+                // Apply the controller's multiplier, still capped at the calldata format maximum
+                let controlled_upper_bound = config.default_value.saturating_mul(alloy_primitives::U256::from(bounds.whole_multiplier()));
+                let adjusted_upper_bound = controlled_upper_bound.min(config.codec.max_quantity());
                 
                 debug!(
                     contract = %config.contract_address,
-                    stored_multiplier = ?stored_multiplier,
+                    multiplier = bounds.multiplier,
                     initial_upper = %max_qty,
                     adjusted_upper = %adjusted_upper_bound,
-                    "Applied stored multiplier to bounds"
+                    "Applied bounds controller multiplier"
                 );
                 
+                // Simulate as the wallet that will submit, on its real nonce and balance
+                let sender = self.resolve_sender(cache_db, config).await?;
+                
                 // Create gradient parameters
                 let params = GradientParams {
                     initial_qty: config.default_value,
-                    calldata_template: alloy_primitives::Bytes::from(vec![0x00, 0x00, 0x00, 0x00]), // Short format
+                    calldata_template: config.codec.calldata_template(),
                     seed: alloy_primitives::U256::from(self.state_snapshot.block_number * 1000 + self.state_snapshot.flashblock_index as u64),
                     lower_bound: min_qty,
                     upper_bound: adjusted_upper_bound,
                     target_address: config.contract_address,
                     filtered_gas,
+                    sender,
+                    codec: config.codec,
+                    chain_id: self.chain_id(),
+                    warm_start: false,
+                };
+                
+                // Seed the search around recent winners when the optimum has been stable
+                let warm_start_config = &self.search_config.warm_start;
+                let bracket = history.as_ref().and_then(|h| warm_bracket(
+                    h,
+                    self.state_snapshot.block_number,
+                    min_qty,
+                    adjusted_upper_bound,
+                    warm_start_config,
+                ));
+                let search_params = match &bracket {
+                    Some(bracket) => {
+                        debug!(
+                            config = %config_name,
+                            initial = %bracket.initial,
+                            lower = %bracket.lower,
+                            upper = %bracket.upper,
+                            "Warm-starting search from recent winners"
+                        );
+                        strategy_metrics.warm_starts_total.increment(1);
+                        GradientParams {
+                            initial_qty: bracket.initial,
+                            lower_bound: bracket.lower,
+                            upper_bound: bracket.upper,
+                            warm_start: true,
+                            ..params.clone()
+                        }
+                    }
+                    None => params.clone(),
                 };
                 
                 // Run gradient optimization - use binary search version for best performance
@@ -376,21 +433,58 @@ impl MevTaskWorker {
                     "Starting binary search optimization"
                 );
                 
-                match optimizer.optimize_quantity(params, &self.state_snapshot, cache_db, evm_config) {
+                let mut outcome = optimizer.optimize_quantity(search_params, &self.state_snapshot, cache_db, evm_config);
+                let mut searched_cold = bracket.is_none();
+                if let (Some(bracket), Ok(result)) = (&bracket, &outcome) {
+                    if needs_exploration(bracket, result.qty_in, result.delta, warm_start_config) {
+                        debug!(
+                            config = %config_name,
+                            qty_in = %result.qty_in,
+                            delta = result.delta,
+                            "Warm result at bracket edge or unprofitable - exploring full range"
+                        );
+                        strategy_metrics.warm_start_fallbacks_total.increment(1);
+                        outcome = optimizer.optimize_quantity(params, &self.state_snapshot, cache_db, evm_config);
+                        searched_cold = true;
+                    }
+                }
+                
+                match outcome {
                     Ok(result) => {
                         // Mark gradient completion in worker timing
                         if let Some(ref mut timing) = worker_timing {
                             timing.gradient_completed = Some(std::time::Instant::now());
                         }
                         
-                        // Save updated filtered gas and multiplier to Redis if available
+                        // Feed the result back into the bounds controller
+                        let next_bounds = bounds_controller::update(&bounds, &BoundsObservation {
+                            filtered_gas: result.filtered_gas,
+                            cold: searched_cold,
+                            iterations: optimizer.max_iterations(),
+                            best_qty: result.qty_in,
+                            profit: result.delta,
+                            upper_bound: adjusted_upper_bound,
+                            upper_capped: adjusted_upper_bound < controlled_upper_bound,
+                        }, bounds_config);
+                        bounds_controller::record_metrics(config_name, &bounds, &next_bounds);
+                        trace!(
+                            config = %config_name,
+                            multiplier = next_bounds.multiplier,
+                            previous_multiplier = bounds.multiplier,
+                            position = ?next_bounds.last_position,
+                            gas_ratio = ?next_bounds.last_gas_ratio,
+                            "Bounds controller updated"
+                        );
+                        
+                        // Save updated filtered gas, bounds state and best quantity to the history store
                         if let Some(new_filtered_gas) = result.filtered_gas {
-                            let gas_store = self.gas_history_store.clone();
-                            let target = config.contract_address;
-                            let multiplier = result.actual_multiplier;
-                            tokio::spawn(async move {
-                                gas_store.set_filtered_gas_and_multiplier(&target, new_filtered_gas, multiplier).await;
+                            let win = (result.delta > 0).then(|| QtySample {
+                                qty: result.qty_in,
+                                profit: result.delta,
+                                block_number: self.state_snapshot.block_number,
+                                flashblock_index: self.state_snapshot.flashblock_index as u64,
                             });
+                            self.gas_history_store.record_in_background(config.contract_address, new_filtered_gas, Some(next_bounds), win);
                         }
                         
                         debug!(
@@ -440,23 +534,22 @@ impl MevTaskWorker {
                                 );
                             }
                             
-                            // Bot address for MEV execution
-                            let bot_address = alloy_primitives::Address::from([0xc0, 0xff, 0xee, 0x48, 0x94, 0x5a, 0x95, 0x18, 
-                                                                               0xb0, 0xb5, 0x43, 0xa2, 0xc5, 0x9d, 0xfb, 0x10, 
-                                                                               0x22, 0x21, 0xfb, 0xb7]);
-                            
                             // First, simulate the transaction with value=0 to get gas usage
                             debug!("Simulating transaction to determine gas usage");
                             
+                            let expected_profit = alloy_primitives::U256::from(result.delta as u128);
+                            let provisional_fees = self.quote_fees(config, expected_profit, None).await;
                             let gas_used = match self.simulate_transaction(
                                 cache_db,
                                 evm_config,
-                                bot_address,
+                                sender,
+                                provisional_fees,
                                 config.contract_address,
                                 result.calldata_used.clone(),
                                 alloy_primitives::U256::from(0), // Zero value for gas estimation
+                                MAX_BACKRUN_GAS_LIMIT,
                             ) {
-                                Ok(gas) => gas,
+                                Ok(simulated) => simulated.gas_used,
                                 Err(e) => {
                                     warn!(error = ?e, "Failed to simulate transaction, using default gas");
                                     200_000 // Default fallback
@@ -465,10 +558,10 @@ impl MevTaskWorker {
                             
                             // Check ERC20 balance if configured
                             let balance_check_value = if let Some((erc20_token, check_address)) = config.check_balance_of {
-                                match self.get_erc20_balance(cache_db, erc20_token, check_address) {
+                                match self.get_erc20_balance(cache_db, sender.address, erc20_token, check_address) {
                                     Ok(balance) => {
                                         // Take bottom 2 bytes of balance
-                                        let balance_u16 = (balance.as_limbs()[0] & 0xffff) as u16;
+                                        let balance_u16 = PackedValue::bribe_rate_from_balance(balance);
                                         debug!(
                                             erc20 = %erc20_token,
                                             address = %check_address,
@@ -485,15 +578,18 @@ impl MevTaskWorker {
                                             error = ?e,
                                             "Failed to check ERC20 balance, using default"
                                         );
-                                        500 // Default bribe rate on error
+                                        DEFAULT_BRIBE_RATE
                                     }
                                 }
                             } else {
-                                500 // Default bribe rate when no balance check configured
+                                DEFAULT_BRIBE_RATE
                             };
                             
                             // Calculate bribe value based on actual gas used and balance check
-                            let bribe_value = self.encode_transaction_value(gas_used, balance_check_value);
+                            let bribe_value = config.codec.encode_value(PackedValue {
+                                gas_estimate: gas_used,
+                                bribe_rate: balance_check_value,
+                            })?;
                             debug!(
                                 gas_used = gas_used,
                                 bribe_value = %bribe_value,
@@ -501,16 +597,48 @@ impl MevTaskWorker {
                                 "Calculated bribe value from gas simulation and balance check"
                             );
                             
+                            // The fees the leg is submitted with; every simulation below uses them
+                            let fees = self.quote_fees(config, expected_profit, Some(gas_used)).await;
+                            let gas_limit = self.estimate_gas_limit(
+                                cache_db,
+                                evm_config,
+                                sender,
+                                fees,
+                                config,
+                                result.calldata_used.clone(),
+                                bribe_value,
+                                gas_used,
+                            );
+                            
+                            // Re-simulate exactly the transaction that will be sent, collecting
+                            // the storage it reads as submission preconditions
+                            let known_accounts = match self.simulate_transaction(
+                                cache_db,
+                                evm_config,
+                                sender,
+                                fees,
+                                config.contract_address,
+                                result.calldata_used.clone(),
+                                bribe_value,
+                                gas_limit,
+                            ) {
+                                Ok(simulated) => simulated.known_accounts,
+                                Err(e) => {
+                                    warn!(error = ?e, "Failed to simulate final transaction, submitting without preconditions");
+                                    KnownAccounts::new()
+                                }
+                            };
+                            
                             // Create MEV bundle with calculated bribe value
                             let bundle = crate::mev_bundle_types::MevBundle::new(
                                 vec![crate::mev_bundle_types::BundleTransaction::unsigned(
-                                    bot_address,
+                                    sender.address,
                                     Some(config.contract_address),
                                     bribe_value, // Use calculated bribe value
                                     result.calldata_used,
-                                    4_000_000, // gas limit
-                                    alloy_primitives::U256::from(self.state_snapshot.base_fee + 100_000),
-                                    0, // nonce
+                                    gas_limit,
+                                    fees,
+                                    sender.nonce,
                                 )],
                                 self.state_snapshot.block_number,
                             );
@@ -523,12 +651,14 @@ impl MevTaskWorker {
                                 block_number: self.state_snapshot.block_number,
                                 flashblock_index: self.state_snapshot.flashblock_index,
                                 bundle,
-                                expected_profit: alloy_primitives::U256::from(result.delta as u128),
+                                expected_profit,
                                 strategy: format!("Backrun_{}", config.name),
                                 simulated_gas_used: Some(gas_used),
                                 last_flashblock_tx_hash: last_tx_hash,
                                 scan_id: self.state_snapshot.scan_id.clone(),
                                 processor_config: Some(config.clone()),
+                                state_snapshot: Some(Arc::new(self.state_snapshot.clone())),
+                                known_accounts,
                             }));
                         } else {
                             debug!(
@@ -551,47 +681,158 @@ impl MevTaskWorker {
         Ok(None)
     }
     
-    /// Encode transaction value with gas cost and bribe info
-    fn encode_transaction_value(&self, gas_cost: u64, bribe_rate: u16) -> alloy_primitives::U256 {
-        let encoded = ((gas_cost / 10) << 16) | bribe_rate as u64;
-        alloy_primitives::U256::from(encoded)
+    /// Fees for an opportunity as the sink will submit it
+    async fn quote_fees(
+        &self,
+        config: &crate::backrun_analyzer::TokenPairProcessorConfig,
+        expected_profit: alloy_primitives::U256,
+        gas_used: Option<u64>,
+    ) -> FeeQuote {
+        self.sink.quote_fees(config, expected_profit, gas_used, self.state_snapshot.base_fee).await
     }
     
-    /// Simulate a transaction to get gas usage
+    /// Preview the submitting wallet and read its balance from the simulated state
+    ///
+    /// The nonce is the one the submitter will assign, past any of the wallet's pending
+    /// submissions, and is written into the simulated state. Without a usable wallet, falls
+    /// back to a simulation-only address funded with 1 ETH so opportunities are still found
+    /// and logged.
+    async fn resolve_sender<DB>(
+        &self,
+        cache_db: &mut CacheDB<DB>,
+        config: &crate::backrun_analyzer::TokenPairProcessorConfig,
+    ) -> eyre::Result<SimulationSender>
+    where
+        DB: revm::Database + revm::DatabaseRef + std::fmt::Debug,
+        <DB as revm::DatabaseRef>::Error: Send + Sync + 'static,
+    {
+        let wallet = match self.wallets {
+            Some(ref wallets) => {
+                match wallets.preview_sender(config).await {
+                    Ok(address) => Some((wallets, address)),
+                    Err(e) => {
+                        debug!(error = %e, "No wallet available, simulating from fallback sender");
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        
+        match wallet {
+            Some((wallets, address)) => {
+                let account = cache_db.load_account(address)?;
+                account.info.nonce = wallets.next_nonce(address, account.info.nonce);
+                Ok(SimulationSender { address, nonce: account.info.nonce, balance: account.info.balance })
+            }
+            None => {
+                let info = match cache_db.basic(FALLBACK_SENDER)? {
+                    Some(info) if !info.balance.is_zero() => info,
+                    _ => {
+                        let account_info = revm::state::AccountInfo {
+                            balance: alloy_primitives::U256::from(1_000_000_000_000_000_000u64), // 1 ETH
+                            nonce: 0,
+                            code_hash: alloy_primitives::KECCAK256_EMPTY,
+                            code: None,
+                        };
+                        cache_db.cache.accounts.insert(FALLBACK_SENDER, DbAccount {
+                            info: account_info.clone(),
+                            account_state: AccountState::Touched,
+                            storage: Default::default(),
+                        });
+                        account_info
+                    }
+                };
+                Ok(SimulationSender { address: FALLBACK_SENDER, nonce: info.nonce, balance: info.balance })
+            }
+        }
+    }
+    
+    /// Smallest gas limit the exact transaction succeeds with, widened by the config's
+    /// learned safety margin
+    ///
+    /// Falls back to the fixed ceiling when estimation is off or the transaction fails even there.
+    #[allow(clippy::too_many_arguments)]
+    fn estimate_gas_limit<DB>(
+        &self,
+        cache_db: &mut CacheDB<DB>,
+        evm_config: &OpEvmConfig<OpChainSpec, OpPrimitives>,
+        sender: SimulationSender,
+        fees: FeeQuote,
+        config: &crate::backrun_analyzer::TokenPairProcessorConfig,
+        calldata: alloy_primitives::Bytes,
+        value: alloy_primitives::U256,
+        gas_used: u64,
+    ) -> u64
+    where
+        DB: revm::Database + revm::DatabaseRef + std::fmt::Debug,
+        <DB as revm::DatabaseRef>::Error: Send + Sync + 'static,
+    {
+        let Some(estimator) = self.sink.gas_limit_estimator() else {
+            return MAX_BACKRUN_GAS_LIMIT;
+        };
+        if !estimator.config().enabled {
+            return MAX_BACKRUN_GAS_LIMIT;
+        }
+
+        let search_start = std::time::Instant::now();
+        let min_gas_limit = search_min_gas_limit(
+            gas_used,
+            MAX_BACKRUN_GAS_LIMIT,
+            estimator.config().search_tolerance,
+            |gas_limit| {
+                self.simulate_transaction(cache_db, evm_config, sender, fees, config.contract_address, calldata.clone(), value, gas_limit)
+                    .map(|simulated| simulated.success)
+                    .unwrap_or(false)
+            },
+        );
+
+        match min_gas_limit {
+            Some(min_gas_limit) => {
+                let gas_limit = estimator.gas_limit(&config.name, min_gas_limit);
+                debug!(
+                    config = %config.name,
+                    gas_used = gas_used,
+                    min_gas_limit = min_gas_limit,
+                    margin_bps = estimator.margin_bps(&config.name),
+                    gas_limit = gas_limit,
+                    search_ms = search_start.elapsed().as_millis(),
+                    "Estimated backrun gas limit"
+                );
+                gas_limit
+            }
+            None => {
+                warn!(
+                    config = %config.name,
+                    gas_limit = MAX_BACKRUN_GAS_LIMIT,
+                    "Backrun fails at the maximum gas limit, submitting with the ceiling"
+                );
+                MAX_BACKRUN_GAS_LIMIT
+            }
+        }
+    }
+    
+    /// Simulate a transaction to get gas usage, whether it succeeded, and the storage slots
+    /// it read with the values they held before the transaction
+    ///
+    /// Runs against the flashblock's base fee with the fees the leg will be submitted with.
+    #[allow(clippy::too_many_arguments)]
     fn simulate_transaction<DB>(
         &self,
         cache_db: &mut CacheDB<DB>,
         evm_config: &OpEvmConfig<OpChainSpec, OpPrimitives>,
-        from: alloy_primitives::Address,
+        sender: SimulationSender,
+        fees: FeeQuote,
         to: alloy_primitives::Address,
         calldata: alloy_primitives::Bytes,
         value: alloy_primitives::U256,
-    ) -> eyre::Result<u64>
+        gas_limit: u64,
+    ) -> eyre::Result<SimulatedTransaction>
     where
         DB: revm::Database + revm::DatabaseRef + std::fmt::Debug,
         <DB as revm::DatabaseRef>::Error: Send + Sync + 'static,
     {
-        // Fund the sender account if needed
-        let sender_info = match cache_db.basic(from)? {
-            Some(info) if info.balance >= value => info,
-            _ => {
-                // Need to fund the account
-                let account_info = revm::state::AccountInfo {
-                    balance: alloy_primitives::U256::from(1_000_000_000_000_000_000u64), // 1 ETH
-                    nonce: 0,
-                    code_hash: alloy_primitives::KECCAK256_EMPTY,
-                    code: None,
-                };
-                
-                cache_db.cache.accounts.insert(from, DbAccount {
-                    info: account_info.clone(),
-                    account_state: AccountState::Touched,
-                    storage: Default::default(),
-                });
-                
-                account_info
-            }
-        };
+        let from = sender.address;
         
         // Create dummy signature for simulation
         let signature = alloy_primitives::Signature::new(
@@ -603,21 +844,22 @@ impl MevTaskWorker {
         // Set up transaction environment
         let mut tx_env = revm::context::TxEnv::default();
         tx_env.caller = from;
-        tx_env.nonce = sender_info.nonce;
+        tx_env.nonce = sender.nonce;
         tx_env.kind = revm::primitives::TxKind::Call(to);
         tx_env.data = calldata.clone();
-        tx_env.gas_limit = 4_000_000;
-        tx_env.gas_price = (self.state_snapshot.base_fee + 100_000) as u128;
-        tx_env.gas_priority_fee = Some(100_000u128);
+        tx_env.gas_limit = gas_limit;
+        tx_env.tx_type = 2;
+        tx_env.gas_price = fees.max_fee_per_gas;
+        tx_env.gas_priority_fee = Some(fees.max_priority_fee_per_gas);
         tx_env.value = value;
         
         // Create transaction for Optimism
         let tx_eip1559 = alloy_consensus::TxEip1559 {
-            chain_id: 8453, // Base mainnet
-            nonce: sender_info.nonce,
-            gas_limit: 4_000_000,
-            max_fee_per_gas: self.state_snapshot.base_fee as u128 + 100_000,
-            max_priority_fee_per_gas: 100_000,
+            chain_id: self.chain_id(),
+            nonce: sender.nonce,
+            gas_limit,
+            max_fee_per_gas: fees.max_fee_per_gas,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
             to: alloy_primitives::TxKind::Call(to),
             value,
             access_list: Default::default(),
@@ -631,16 +873,15 @@ impl MevTaskWorker {
         let mut op_tx = op_revm::OpTransaction::new(tx_env);
         op_tx.enveloped_tx = Some(enveloped_bytes.into());
         
-        // Use MEV-friendly EVM environment
         let current_timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
         
         let evm_env = evm_config.evm_env(&alloy_consensus::Header {
-            base_fee_per_gas: Some(0), // Zero base fee for MEV simulation
+            base_fee_per_gas: Some(u64::try_from(self.state_snapshot.base_fee).unwrap_or(u64::MAX)),
             gas_limit: 2_000_000_000,
-            number: 33_634_688,
+            number: self.state_snapshot.block_number,
             timestamp: current_timestamp,
             ..Default::default()
         });
@@ -653,14 +894,29 @@ impl MevTaskWorker {
         match evm.transact(op_tx) {
             Ok(result) => {
                 let gas = result.result.gas_used();
+                let success = result.result.is_success();
+                let mut known_accounts = KnownAccounts::new();
+                let mut slot_count = 0;
+                for (address, account) in result.state.iter().filter(|(address, _)| **address != from) {
+                    for (slot, value) in account.storage.iter() {
+                        if slot_count >= MAX_KNOWN_SLOTS {
+                            break;
+                        }
+                        known_accounts.entry(*address).or_default().insert(*slot, value.original_value());
+                        slot_count += 1;
+                    }
+                }
                 trace!(
                     from = %from,
                     to = %to,
                     value = %value,
+                    gas_limit = gas_limit,
                     gas_used = gas,
+                    success = success,
+                    known_slots = slot_count,
                     "Transaction simulation complete"
                 );
-                Ok(gas)
+                Ok(SimulatedTransaction { gas_used: gas, success, known_accounts })
             }
             Err(e) => {
                 debug!(error = ?e, "Transaction simulation failed");
@@ -673,6 +929,7 @@ impl MevTaskWorker {
     fn get_erc20_balance<DB>(
         &self,
         cache_db: &mut CacheDB<DB>,
+        from: alloy_primitives::Address,
         token_address: alloy_primitives::Address,
         check_address: alloy_primitives::Address,
     ) -> eyre::Result<alloy_primitives::U256>
@@ -686,33 +943,9 @@ impl MevTaskWorker {
         calldata.extend_from_slice(&[0u8; 12]); // 12 zero bytes for padding
         calldata.extend_from_slice(check_address.as_slice());
         
-        // Simulate a static call using MEV simulation environment
-        let bot_address = alloy_primitives::Address::from([0xc0, 0xff, 0xee, 0x48, 0x94, 0x5a, 0x95, 0x18, 
-                                                           0xb0, 0xb5, 0x43, 0xa2, 0xc5, 0x9d, 0xfb, 0x10, 
-                                                           0x22, 0x21, 0xfb, 0xb7]);
-        
-        // Fund the bot address if needed
-        match cache_db.basic(bot_address)? {
-            None => {
-                let account_info = revm::state::AccountInfo {
-                    balance: alloy_primitives::U256::from(1_000_000_000_000_000_000u64), // 1 ETH
-                    nonce: 0,
-                    code_hash: alloy_primitives::KECCAK256_EMPTY,
-                    code: None,
-                };
-                
-                cache_db.cache.accounts.insert(bot_address, DbAccount {
-                    info: account_info,
-                    account_state: AccountState::Touched,
-                    storage: Default::default(),
-                });
-            }
-            _ => {}
-        };
-        
         // Use the simulate_transaction method we already have, but with minimal gas
         // This will execute the balanceOf call and return the result
-        match self.simulate_balance_query(cache_db, bot_address, token_address, calldata.into()) {
+        match self.simulate_balance_query(cache_db, from, token_address, calldata.into()) {
             Ok(output) => {
                 if output.len() >= 32 {
                     // Parse the first 32 bytes as U256
@@ -782,7 +1015,7 @@ impl MevTaskWorker {
         
         // Create transaction for Optimism
         let tx_eip1559 = alloy_consensus::TxEip1559 {
-            chain_id: 8453, // Base mainnet
+            chain_id: self.chain_id(),
             nonce: sender_info.nonce,
             gas_limit: tx_env.gas_limit,
             max_fee_per_gas: 0,
@@ -839,31 +1072,23 @@ impl MevTaskWorker {
     }
 }
 
-/// Get the MEV worker timeout duration from environment or use default
-fn get_worker_timeout() -> std::time::Duration {
-    std::env::var("MEV_WORKER_TIMEOUT_SECS")
-        .ok()
-        .and_then(|s| s.parse::<u64>().ok())
-        .map(std::time::Duration::from_secs)
-        .unwrap_or(std::time::Duration::from_secs(30))
-}
-
 /// Spawn a short-lived MEV task
-pub fn spawn_mev_task<P>(
+pub fn spawn_mev_task<S: StateSource>(
     chain_spec: Arc<OpChainSpec>,
-    provider: P,
+    source: S,
     strategy: MevStrategy,
     state_snapshot: FlashblockStateSnapshot,
     flashblock_received_at: std::time::Instant,
     result_tx: tokio::sync::mpsc::Sender<MevOpportunity>,
     timing_tracker: Option<TimingTracker>,
     min_profit_threshold: alloy_primitives::U256,
+    analyzer: Arc<BackrunAnalyzer>,
     gas_history_store: Arc<crate::gas_history_store::GasHistoryStore>,
-)
-where
-    P: StateProviderFactory + reth_provider::HeaderProvider + reth_provider::BlockReader + Clone + Send + 'static,
-    P::Header: alloy_consensus::BlockHeader,
-{
+    sink: Arc<dyn SubmissionSink>,
+    wallets: Option<Arc<dyn SubmissionWallets>>,
+    search_config: Arc<SearchConfig>,
+    worker_timeout: std::time::Duration,
+) {
     tokio::spawn(async move {
         let worker = MevTaskWorker::new(
             chain_spec,
@@ -872,12 +1097,16 @@ where
             flashblock_received_at,
             timing_tracker,
             min_profit_threshold,
+            analyzer,
             gas_history_store,
+            sink,
+            wallets,
+            search_config,
         );
         
         // Add timeout to prevent stuck workers
-        let timeout_duration = get_worker_timeout();
-        match tokio::time::timeout(timeout_duration, worker.execute(provider)).await {
+        let timeout_duration = worker_timeout;
+        match tokio::time::timeout(timeout_duration, worker.execute(source)).await {
             Ok(Ok(Some(opportunity))) => {
                 // Only log at info level if above threshold
                 if opportunity.expected_profit > min_profit_threshold {
@@ -908,32 +1137,37 @@ where
 }
 
 /// Spawn multiple MEV tasks in batch for reduced overhead
-pub fn spawn_mev_tasks_batch<P>(
+pub fn spawn_mev_tasks_batch<S: StateSource>(
     chain_spec: Arc<OpChainSpec>,
-    provider: P,
+    source: S,
     strategies: Vec<MevStrategy>,
     state_snapshot: FlashblockStateSnapshot,
     flashblock_received_at: std::time::Instant,
     result_tx: tokio::sync::mpsc::Sender<MevOpportunity>,
     timing_tracker: Option<TimingTracker>,
     min_profit_threshold: alloy_primitives::U256,
+    analyzer: Arc<BackrunAnalyzer>,
     gas_history_store: Arc<crate::gas_history_store::GasHistoryStore>,
-)
-where
-    P: StateProviderFactory + reth_provider::HeaderProvider + reth_provider::BlockReader + Clone + Send + 'static,
-    P::Header: alloy_consensus::BlockHeader,
-{
+    sink: Arc<dyn SubmissionSink>,
+    wallets: Option<Arc<dyn SubmissionWallets>>,
+    search_config: Arc<SearchConfig>,
+    worker_timeout: std::time::Duration,
+) {
     // Convert to Arc to share across tasks without cloning
     let state_snapshot = Arc::new(state_snapshot);
     
     // Spawn all tasks with a single batch operation
     let handles: Vec<_> = strategies.into_iter().map(|strategy| {
         let chain_spec = chain_spec.clone();
-        let provider = provider.clone();
+        let source = source.clone();
         let state_snapshot = state_snapshot.clone();
         let result_tx = result_tx.clone();
         let timing_tracker = timing_tracker.clone();
+        let analyzer = analyzer.clone();
         let gas_history_store = gas_history_store.clone();
+        let sink = sink.clone();
+        let wallets = wallets.clone();
+        let search_config = search_config.clone();
         
         tokio::spawn(async move {
             let worker = MevTaskWorker::new(
@@ -943,12 +1177,16 @@ where
                 flashblock_received_at,
                 timing_tracker,
                 min_profit_threshold,
+                analyzer,
                 gas_history_store,
+                sink,
+                wallets,
+                search_config,
             );
             
             // Add timeout to prevent stuck workers
-            let timeout_duration = get_worker_timeout();
-            match tokio::time::timeout(timeout_duration, worker.execute(provider)).await {
+            let timeout_duration = worker_timeout;
+            match tokio::time::timeout(timeout_duration, worker.execute(source)).await {
                 Ok(Ok(Some(opportunity))) => {
                     // Only log at info level if above threshold
                     if opportunity.expected_profit > min_profit_threshold {
@@ -984,4 +1222,64 @@ where
             let _ = handle.await;
         }
     });
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backrun_analyzer::TokenPairProcessorConfig;
+    use crate::executor_codec::ExecutorCodec;
+    use crate::gas_history_store::{GasHistoryStore, MemoryGasHistory};
+    use crate::remote_state::mock_node::{spawn_mock_node, ACCOUNT, CONTRACT};
+    use crate::remote_state::{RemoteStateConfig, RpcStateSource};
+    use alloy_primitives::{B256, U256};
+    use async_trait::async_trait;
+    use std::sync::atomic::Ordering;
+
+    /// Accepts nothing; the worker only asks it for fee quotes
+    struct NoSubmissions;
+
+    #[async_trait]
+    impl SubmissionSink for NoSubmissions {
+        async fn submit(&self, _opportunity: &MevOpportunity) -> eyre::Result<Option<B256>> {
+            Ok(None)
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_worker_searches_remote_state() {
+        let (url, requests) = spawn_mock_node();
+        let source = RpcStateSource::new(&RemoteStateConfig::new(url));
+        let analyzer = Arc::new(BackrunAnalyzer::with_processors(vec![TokenPairProcessorConfig {
+            name: "Test".to_string(),
+            tokens: vec![],
+            accounts: vec![ACCOUNT],
+            contract_address: CONTRACT,
+            default_value: U256::from(1_000),
+            codec: ExecutorCodec::V1_SHORT,
+            check_balance_of: None,
+            priority_fee_multiplier: None,
+            wallet_strategy: None,
+        }], U256::from(1)));
+
+        let worker = MevTaskWorker::new(
+            reth_optimism_chainspec::BASE_MAINNET.clone(),
+            MevStrategy::Backrun("Test".to_string()),
+            FlashblockStateSnapshot::new(100, 0, 1_000_000),
+            std::time::Instant::now(),
+            None,
+            U256::from(1),
+            analyzer,
+            Arc::new(GasHistoryStore::new(Arc::new(MemoryGasHistory::default()))),
+            Arc::new(NoSubmissions),
+            None,
+            Arc::new(SearchConfig::default()),
+        );
+        let opportunity = worker.execute(source).await.unwrap().expect("opportunity found");
+
+        assert_eq!(opportunity.strategy, "Backrun_Test");
+        assert_eq!(opportunity.block_number, 100);
+        assert_eq!(opportunity.expected_profit, U256::from(1_000_000));
+        assert_eq!(opportunity.bundle.transactions[0].to(), Some(CONTRACT));
+        assert!(requests.load(Ordering::Relaxed) > 0);
+    }
+}
@@ -0,0 +1,203 @@
+//! Minimal HTTP/1.1 keep-alive JSON-RPC server standing in for the sequencer.
+//!
+//! Used by the `mock-sequencer` binary and by `SequencerService` tests to exercise
+//! connection reuse, latency and error handling without a real endpoint.
+
+use alloy_consensus::{SignableTransaction, TxEip1559, TxEnvelope};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_network::TxSignerSync;
+use alloy_primitives::{keccak256, Address, Bytes, TxKind, U256};
+use alloy_signer_local::PrivateKeySigner;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info, warn};
+
+/// Behaviour of the mock sequencer
+#[derive(Debug, Clone, Default)]
+pub struct MockSequencerConfig {
+    /// Delay before every response
+    pub latency: Duration,
+    /// JSON-RPC error returned for every submission instead of a hash
+    pub submission_error: Option<(i32, String)>,
+    /// Whether `eth_sendRawTransactionConditional` is implemented
+    pub support_conditional: bool,
+    /// Key used to answer `eth_signTransaction`, standing in for a remote signer
+    pub signer: Option<PrivateKeySigner>,
+}
+
+/// Counters shared with the caller
+#[derive(Debug, Default)]
+pub struct MockSequencerStats {
+    pub connections: AtomicUsize,
+    pub requests: AtomicUsize,
+    pub conditional_requests: AtomicUsize,
+}
+
+/// Bind and serve in the background, returning the bound address and live stats
+pub async fn spawn(
+    addr: SocketAddr,
+    config: MockSequencerConfig,
+) -> std::io::Result<(SocketAddr, Arc<MockSequencerStats>)> {
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+    let stats = Arc::new(MockSequencerStats::default());
+
+    let stats_clone = stats.clone();
+    tokio::spawn(async move {
+        serve(listener, config, stats_clone).await;
+    });
+
+    Ok((local_addr, stats))
+}
+
+/// Accept connections until the listener fails
+pub async fn serve(listener: TcpListener, config: MockSequencerConfig, stats: Arc<MockSequencerStats>) {
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                warn!(error = %e, "Mock sequencer accept failed");
+                return;
+            }
+        };
+        let connection = stats.connections.fetch_add(1, Ordering::Relaxed) + 1;
+        info!(peer = %peer, connection = connection, "Mock sequencer connection opened");
+
+        let config = config.clone();
+        let stats = stats.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &config, &stats).await {
+                debug!(peer = %peer, error = %e, "Mock sequencer connection closed");
+            }
+        });
+    }
+}
+
+/// Serve requests on one connection until the client closes it
+async fn handle_connection(
+    stream: TcpStream,
+    config: &MockSequencerConfig,
+    stats: &MockSequencerStats,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        let mut content_length = 0usize;
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(());
+        }
+        loop {
+            line.clear();
+            reader.read_line(&mut line).await?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).await?;
+        stats.requests.fetch_add(1, Ordering::Relaxed);
+
+        if !config.latency.is_zero() {
+            tokio::time::sleep(config.latency).await;
+        }
+
+        let response = respond(&body, config, stats).to_string();
+        let http = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: keep-alive\r\n\r\n{}",
+            response.len(),
+            response
+        );
+        reader.get_mut().write_all(http.as_bytes()).await?;
+    }
+}
+
+/// Build the JSON-RPC response for a request body
+fn respond(body: &[u8], config: &MockSequencerConfig, stats: &MockSequencerStats) -> serde_json::Value {
+    let request: serde_json::Value = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return rpc_error(serde_json::Value::Null, -32700, &format!("parse error: {}", e)),
+    };
+    let id = request["id"].clone();
+
+    match request["method"].as_str().unwrap_or_default() {
+        "eth_blockNumber" => serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": "0x1" }),
+        "eth_sendRawTransactionConditional" if !config.support_conditional => rpc_error(
+            id,
+            -32601,
+            "the method eth_sendRawTransactionConditional does not exist/is not available",
+        ),
+        method @ ("eth_sendRawTransaction" | "eth_sendRawTransactionConditional") => {
+            if method == "eth_sendRawTransactionConditional" {
+                stats.conditional_requests.fetch_add(1, Ordering::Relaxed);
+            }
+            if let Some((code, message)) = &config.submission_error {
+                return rpc_error(id, *code, message);
+            }
+            let raw = request["params"][0].as_str().unwrap_or_default();
+            match hex::decode(raw.trim_start_matches("0x")) {
+                Ok(bytes) => serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "result": format!("{:#x}", keccak256(&bytes)),
+                }),
+                Err(_) => rpc_error(id, -32602, "invalid raw transaction"),
+            }
+        }
+        "eth_signTransaction" => match &config.signer {
+            Some(signer) => match sign_transaction(signer, &request["params"][0]) {
+                Some(raw) => serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "result": { "raw": format!("0x{}", hex::encode(raw)) },
+                }),
+                None => rpc_error(id, -32602, "invalid transaction object"),
+            },
+            None => rpc_error(id, -32601, "the method eth_signTransaction does not exist/is not available"),
+        },
+        method => rpc_error(id, -32601, &format!("the method {} does not exist/is not available", method)),
+    }
+}
+
+/// Sign an `eth_signTransaction` transaction object, returning the EIP-2718 encoding
+fn sign_transaction(signer: &PrivateKeySigner, params: &serde_json::Value) -> Option<Vec<u8>> {
+    let quantity = |field: &str| -> Option<u128> {
+        u128::from_str_radix(params[field].as_str()?.trim_start_matches("0x"), 16).ok()
+    };
+    let from = params["from"].as_str()?.parse::<Address>().ok()?;
+    if from != signer.address() {
+        return None;
+    }
+
+    let mut tx = TxEip1559 {
+        chain_id: quantity("chainId")? as u64,
+        nonce: quantity("nonce")? as u64,
+        gas_limit: quantity("gas")? as u64,
+        max_fee_per_gas: quantity("maxFeePerGas")?,
+        max_priority_fee_per_gas: quantity("maxPriorityFeePerGas")?,
+        to: match params["to"].as_str() {
+            Some(to) => TxKind::Call(to.parse().ok()?),
+            None => TxKind::Create,
+        },
+        value: params["value"].as_str()?.parse::<U256>().ok()?,
+        access_list: Default::default(),
+        input: Bytes::from(hex::decode(params["input"].as_str()?.trim_start_matches("0x")).ok()?),
+    };
+    let signature = signer.sign_transaction_sync(&mut tx).ok()?;
+    Some(TxEnvelope::Eip1559(tx.into_signed(signature)).encoded_2718())
+}
+
+fn rpc_error(id: serde_json::Value, code: i32, message: &str) -> serde_json::Value {
+    serde_json::json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
@@ -0,0 +1,170 @@
+//! Networks mevbase can run against.
+//!
+//! The chain spec comes from the node's `--chain` argument; the network it maps to supplies
+//! the defaults that differ between chains: flashblock feed, sequencer and the protocol
+//! addresses the strategies use.
+
+use crate::backrun_analyzer::{BackrunAnalyzer, TokenPairProcessorConfig};
+use reth_chainspec::EthChainSpec;
+use reth_optimism_chainspec::OpChainSpec;
+use std::sync::Arc;
+
+/// Known network families
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    BaseMainnet,
+    BaseSepolia,
+    /// Local dev chain (`--chain dev`)
+    Dev,
+    /// Any other OP-stack chain with a flashblocks feed; endpoints must be configured
+    OpStack,
+}
+
+/// Protocol addresses strategies rely on
+#[derive(Debug, Clone, Default)]
+pub struct AddressBook {
+    /// Backrun processors (executors and watched pools) deployed on the network
+    pub backrun_processors: Vec<TokenPairProcessorConfig>,
+}
+
+impl Network {
+    pub fn from_chain_id(chain_id: u64) -> Self {
+        match chain_id {
+            8453 => Self::BaseMainnet,
+            84532 => Self::BaseSepolia,
+            1337 => Self::Dev,
+            _ => Self::OpStack,
+        }
+    }
+
+    /// Parse the names accepted by the node's `--chain` argument
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "base" | "base-mainnet" => Some(Self::BaseMainnet),
+            "base-sepolia" => Some(Self::BaseSepolia),
+            "dev" => Some(Self::Dev),
+            "op-stack" => Some(Self::OpStack),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BaseMainnet => "base",
+            Self::BaseSepolia => "base-sepolia",
+            Self::Dev => "dev",
+            Self::OpStack => "op-stack",
+        }
+    }
+
+    pub fn default_chain_id(&self) -> Option<u64> {
+        match self {
+            Self::BaseMainnet => Some(8453),
+            Self::BaseSepolia => Some(84532),
+            Self::Dev => Some(1337),
+            Self::OpStack => None,
+        }
+    }
+
+    pub fn default_flashblocks_url(&self) -> Option<&'static str> {
+        match self {
+            Self::BaseMainnet => Some("wss://mainnet.flashblocks.base.org/ws"),
+            Self::BaseSepolia => Some("wss://sepolia.flashblocks.base.org/ws"),
+            Self::Dev => Some("ws://127.0.0.1:6081"),
+            Self::OpStack => None,
+        }
+    }
+
+    pub fn default_sequencer_url(&self) -> Option<&'static str> {
+        match self {
+            Self::BaseMainnet => Some("https://mainnet-sequencer.base.org/"),
+            Self::BaseSepolia => Some("https://sepolia-sequencer.base.org/"),
+            Self::Dev => Some("http://127.0.0.1:8547/"),
+            Self::OpStack => None,
+        }
+    }
+
+    /// Built-in address book; networks without deployed processors get theirs from `[[backrun_processors]]`
+    pub fn address_book(&self) -> AddressBook {
+        match self {
+            Self::BaseMainnet => AddressBook { backrun_processors: BackrunAnalyzer::base_mainnet_processors() },
+            Self::BaseSepolia | Self::Dev | Self::OpStack => AddressBook::default(),
+        }
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The network the node is running on, with its endpoints resolved
+#[derive(Debug, Clone)]
+pub struct NetworkContext {
+    pub network: Network,
+    pub chain_spec: Arc<OpChainSpec>,
+    pub chain_id: u64,
+    pub flashblocks_url: String,
+    pub sequencer_url: String,
+    pub address_book: AddressBook,
+}
+
+impl NetworkContext {
+    /// Resolve endpoints for the node's chain spec, preferring explicitly configured ones
+    pub fn resolve(
+        chain_spec: Arc<OpChainSpec>,
+        flashblocks_url: Option<&str>,
+        sequencer_url: Option<&str>,
+    ) -> eyre::Result<Self> {
+        let chain_id = chain_spec.chain().id();
+        let network = Network::from_chain_id(chain_id);
+
+        let flashblocks_url = flashblocks_url
+            .or(network.default_flashblocks_url())
+            .ok_or_else(|| eyre::eyre!("node.flashblocks_url is required on {} chain {}", network, chain_id))?
+            .to_string();
+        let sequencer_url = sequencer_url
+            .or(network.default_sequencer_url())
+            .ok_or_else(|| eyre::eyre!("sequencer.url is required on {} chain {}", network, chain_id))?
+            .to_string();
+
+        Ok(Self {
+            network,
+            chain_spec,
+            chain_id,
+            flashblocks_url,
+            sequencer_url,
+            address_book: network.address_book(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_optimism_chainspec::{BASE_MAINNET, BASE_SEPOLIA};
+
+    #[test]
+    fn test_resolve_defaults_and_overrides() {
+        let base = NetworkContext::resolve(BASE_SEPOLIA.clone(), None, None).unwrap();
+        assert_eq!(base.network, Network::BaseSepolia);
+        assert_eq!(base.chain_id, 84532);
+        assert_eq!(base.sequencer_url, "https://sepolia-sequencer.base.org/");
+        assert!(base.address_book.backrun_processors.is_empty());
+
+        let local = NetworkContext::resolve(BASE_MAINNET.clone(), Some("ws://127.0.0.1:6081"), None).unwrap();
+        assert_eq!(local.flashblocks_url, "ws://127.0.0.1:6081");
+        assert!(!local.address_book.backrun_processors.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_chain_requires_endpoints() {
+        let op_mainnet = reth_optimism_chainspec::OP_MAINNET.clone();
+        assert!(NetworkContext::resolve(op_mainnet.clone(), None, None).is_err());
+
+        let context = NetworkContext::resolve(op_mainnet, Some("wss://flashblocks.example/ws"), Some("https://sequencer.example/")).unwrap();
+        assert_eq!(context.network, Network::OpStack);
+        assert_eq!(context.chain_id, 10);
+    }
+}
@@ -0,0 +1,318 @@
+//! Lifecycle of MEV opportunities from discovery to inclusion.
+//!
+//! Every opportunity is tracked per (block, processor) through
+//! `Found → Validated → Submitted → Included/Failed/Expired`. One processor gets one live
+//! submission per block; a later flashblock may replace a submitted one only with a strictly
+//! better opportunity, and failed attempts free the slot for the next flashblock. Entries are
+//! pruned once their block can no longer be targeted, so the state stays bounded.
+
+use alloy_primitives::{B256, U256};
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use std::collections::{BTreeMap, HashMap};
+use tracing::debug;
+
+/// Where an opportunity is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpportunityState {
+    /// Received from a worker
+    Found,
+    /// Passed the profit threshold, being submitted
+    Validated,
+    /// Sent to the sequencer
+    Submitted,
+    /// Landed on chain
+    Included,
+    /// Below threshold, or submission failed or timed out
+    Failed,
+    /// Its block went stale before it resolved
+    Expired,
+}
+
+impl OpportunityState {
+    /// Whether a new opportunity for the same slot may take over
+    fn is_resolved(&self) -> bool {
+        matches!(self, Self::Failed | Self::Expired)
+    }
+}
+
+/// Handle to one admitted opportunity; transitions through a superseded ticket are ignored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpportunityTicket {
+    pub block_number: u64,
+    pub processor: String,
+    id: u64,
+}
+
+/// Outcome of offering an opportunity to the lifecycle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Admission {
+    /// First live opportunity for this block and processor
+    New(OpportunityTicket),
+    /// Strictly better than the submitted one from an earlier flashblock
+    Resubmission { ticket: OpportunityTicket, previous_profit: U256 },
+    /// The slot is taken
+    Duplicate(OpportunityState),
+    /// Its block is too far behind the chain head
+    Stale { head: u64 },
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    id: u64,
+    state: OpportunityState,
+    flashblock_index: u32,
+    expected_profit: U256,
+    tx_hash: Option<B256>,
+}
+
+type SlotKey = (u64, String);
+
+#[derive(Default)]
+struct LifecycleState {
+    head: u64,
+    next_id: u64,
+    entries: BTreeMap<SlotKey, Entry>,
+    by_tx: HashMap<B256, SlotKey>,
+}
+
+/// Opportunity states by count, and lifecycle events
+#[derive(Metrics, Clone)]
+#[metrics(scope = "mev.opportunities")]
+pub struct OpportunityLifecycleMetrics {
+    /// Opportunities received and not yet validated
+    pub found: Gauge,
+    /// Opportunities being submitted
+    pub validated: Gauge,
+    /// Submissions waiting for inclusion
+    pub submitted: Gauge,
+    /// Opportunities that landed, within the retained blocks
+    pub included: Gauge,
+    /// Opportunities that failed, within the retained blocks
+    pub failed: Gauge,
+    /// Opportunities whose block went stale before they resolved
+    pub expired: Gauge,
+    /// Submitted opportunities replaced by a better one from a later flashblock
+    pub resubmissions_total: Counter,
+    /// Opportunities dropped because their slot was taken
+    pub duplicates_total: Counter,
+    /// Opportunities dropped because their block was stale
+    pub stale_total: Counter,
+    /// Opportunities that expired unresolved
+    pub expired_total: Counter,
+}
+
+/// Tracks opportunities per (block, processor) and bounds that state by block
+pub struct OpportunityLifecycle {
+    max_block_staleness: u64,
+    state: Mutex<LifecycleState>,
+    metrics: OpportunityLifecycleMetrics,
+}
+
+impl OpportunityLifecycle {
+    pub fn new(max_block_staleness: u64) -> Self {
+        Self {
+            max_block_staleness,
+            state: Mutex::new(LifecycleState::default()),
+            metrics: OpportunityLifecycleMetrics::default(),
+        }
+    }
+
+    /// Move the chain head forward, expiring unresolved opportunities and pruning old blocks
+    pub fn advance(&self, head: u64) {
+        let mut state = self.state.lock();
+        if head <= state.head {
+            return;
+        }
+        state.head = head;
+
+        let staleness = self.max_block_staleness;
+        let LifecycleState { entries, by_tx, .. } = &mut *state;
+        for ((block_number, processor), entry) in entries.iter_mut() {
+            if block_number + staleness < head
+                && matches!(entry.state, OpportunityState::Found | OpportunityState::Validated | OpportunityState::Submitted)
+            {
+                debug!(block = block_number, processor = %processor, from = ?entry.state, "Opportunity expired");
+                entry.state = OpportunityState::Expired;
+                self.metrics.expired_total.increment(1);
+            }
+        }
+        // Keep expired entries one more block so they show up in the state gauges
+        entries.retain(|(block_number, _), _| block_number + staleness + 1 >= head);
+        by_tx.retain(|_, key| entries.contains_key(key));
+
+        self.publish(&state);
+    }
+
+    /// Offer a new opportunity for its block and processor
+    pub fn admit(&self, block_number: u64, flashblock_index: u32, processor: &str, expected_profit: U256) -> Admission {
+        let mut state = self.state.lock();
+        if block_number + self.max_block_staleness < state.head {
+            self.metrics.stale_total.increment(1);
+            return Admission::Stale { head: state.head };
+        }
+
+        let key = (block_number, processor.to_string());
+        let previous = state.entries.get(&key).cloned();
+        let previous_profit = match previous {
+            Some(entry) if entry.state == OpportunityState::Submitted
+                && flashblock_index > entry.flashblock_index
+                && expected_profit > entry.expected_profit => Some(entry.expected_profit),
+            Some(entry) if !entry.state.is_resolved() => {
+                self.metrics.duplicates_total.increment(1);
+                return Admission::Duplicate(entry.state);
+            }
+            _ => None,
+        };
+
+        state.next_id += 1;
+        let id = state.next_id;
+        state.entries.insert(key, Entry {
+            id,
+            state: OpportunityState::Found,
+            flashblock_index,
+            expected_profit,
+            tx_hash: None,
+        });
+        self.publish(&state);
+
+        let ticket = OpportunityTicket { block_number, processor: processor.to_string(), id };
+        match previous_profit {
+            Some(previous_profit) => {
+                self.metrics.resubmissions_total.increment(1);
+                Admission::Resubmission { ticket, previous_profit }
+            }
+            None => Admission::New(ticket),
+        }
+    }
+
+    /// The opportunity passed validation and is being submitted
+    pub fn validated(&self, ticket: &OpportunityTicket) {
+        self.transition(ticket, &[OpportunityState::Found], OpportunityState::Validated, None);
+    }
+
+    /// The opportunity was sent; `tx_hash` is `None` when nothing went on chain (dry run)
+    pub fn submitted(&self, ticket: &OpportunityTicket, tx_hash: Option<B256>) {
+        self.transition(ticket, &[OpportunityState::Validated], OpportunityState::Submitted, tx_hash);
+    }
+
+    /// The opportunity was rejected or its submission failed; the slot is free again
+    pub fn failed(&self, ticket: &OpportunityTicket) {
+        self.transition(ticket, &[OpportunityState::Found, OpportunityState::Validated], OpportunityState::Failed, None);
+    }
+
+    /// One of our submissions landed; returns whether it belonged to a tracked opportunity
+    ///
+    /// A replaced submission landing still settles its slot: the processor captured the block.
+    pub fn included(&self, tx_hash: &B256) -> bool {
+        let mut state = self.state.lock();
+        let Some(key) = state.by_tx.remove(tx_hash) else {
+            return false;
+        };
+        let Some(entry) = state.entries.get_mut(&key) else {
+            return false;
+        };
+        entry.state = OpportunityState::Included;
+        self.publish(&state);
+        true
+    }
+
+    /// Current state of a block's opportunity for a processor
+    pub fn state_of(&self, block_number: u64, processor: &str) -> Option<OpportunityState> {
+        self.state.lock().entries.get(&(block_number, processor.to_string())).map(|entry| entry.state)
+    }
+
+    fn transition(&self, ticket: &OpportunityTicket, from: &[OpportunityState], to: OpportunityState, tx_hash: Option<B256>) {
+        let mut state = self.state.lock();
+        let key = (ticket.block_number, ticket.processor.clone());
+        let Some(entry) = state.entries.get_mut(&key).filter(|entry| entry.id == ticket.id) else {
+            debug!(block = ticket.block_number, processor = %ticket.processor, to = ?to, "Opportunity superseded or pruned - ignoring transition");
+            return;
+        };
+        if !from.contains(&entry.state) {
+            debug!(block = ticket.block_number, processor = %ticket.processor, from = ?entry.state, to = ?to, "Ignoring out-of-order opportunity transition");
+            return;
+        }
+        entry.state = to;
+        entry.tx_hash = tx_hash;
+        if let Some(tx_hash) = tx_hash {
+            state.by_tx.insert(tx_hash, key);
+        }
+        self.publish(&state);
+    }
+
+    fn publish(&self, state: &LifecycleState) {
+        let mut counts = [0usize; 6];
+        for entry in state.entries.values() {
+            counts[entry.state as usize] += 1;
+        }
+        self.metrics.found.set(counts[OpportunityState::Found as usize] as f64);
+        self.metrics.validated.set(counts[OpportunityState::Validated as usize] as f64);
+        self.metrics.submitted.set(counts[OpportunityState::Submitted as usize] as f64);
+        self.metrics.included.set(counts[OpportunityState::Included as usize] as f64);
+        self.metrics.failed.set(counts[OpportunityState::Failed as usize] as f64);
+        self.metrics.expired.set(counts[OpportunityState::Expired as usize] as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(admission: Admission) -> OpportunityTicket {
+        match admission {
+            Admission::New(ticket) | Admission::Resubmission { ticket, .. } => ticket,
+            other => panic!("expected admission, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_resubmission_only_for_strictly_better_later_flashblock() {
+        let lifecycle = OpportunityLifecycle::new(2);
+        lifecycle.advance(100);
+
+        let first = ticket(lifecycle.admit(100, 1, "Backrun_A", U256::from(10)));
+        assert_eq!(lifecycle.admit(100, 2, "Backrun_A", U256::from(50)), Admission::Duplicate(OpportunityState::Found));
+        lifecycle.validated(&first);
+        lifecycle.submitted(&first, Some(B256::with_last_byte(1)));
+
+        // Same flashblock or no better: keep the submitted one
+        assert_eq!(lifecycle.admit(100, 1, "Backrun_A", U256::from(50)), Admission::Duplicate(OpportunityState::Submitted));
+        assert_eq!(lifecycle.admit(100, 3, "Backrun_A", U256::from(10)), Admission::Duplicate(OpportunityState::Submitted));
+
+        let better = lifecycle.admit(100, 3, "Backrun_A", U256::from(11));
+        assert!(matches!(better, Admission::Resubmission { previous_profit, .. } if previous_profit == U256::from(10)));
+        let better = ticket(better);
+
+        // The superseded ticket can no longer move the slot
+        lifecycle.failed(&first);
+        assert_eq!(lifecycle.state_of(100, "Backrun_A"), Some(OpportunityState::Found));
+
+        // A failure frees the slot for the next flashblock
+        lifecycle.failed(&better);
+        assert!(matches!(lifecycle.admit(100, 4, "Backrun_A", U256::from(1)), Admission::New(_)));
+
+        // The original submission landing settles the slot
+        assert!(lifecycle.included(&B256::with_last_byte(1)));
+        assert_eq!(lifecycle.state_of(100, "Backrun_A"), Some(OpportunityState::Included));
+    }
+
+    #[test]
+    fn test_expiry_and_pruning_by_block() {
+        let lifecycle = OpportunityLifecycle::new(2);
+        lifecycle.advance(100);
+        let pending = ticket(lifecycle.admit(100, 0, "Backrun_A", U256::from(1)));
+        lifecycle.validated(&pending);
+
+        lifecycle.advance(103);
+        assert_eq!(lifecycle.state_of(100, "Backrun_A"), Some(OpportunityState::Expired));
+        assert_eq!(lifecycle.admit(100, 5, "Backrun_A", U256::from(9)), Admission::Stale { head: 103 });
+
+        lifecycle.advance(104);
+        assert_eq!(lifecycle.state_of(100, "Backrun_A"), None);
+        assert!(lifecycle.state.lock().entries.is_empty());
+    }
+}
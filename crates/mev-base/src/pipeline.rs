@@ -0,0 +1,1105 @@
+//! The MEV pipeline: flashblock intake, simulation, strategy search and submission.
+//!
+//! [`MevPipeline`] wires the stages over a [`StateSource`], a [`FlashblockSource`], a
+//! [`SubmissionSink`] and optionally [`SubmissionWallets`], so the node binary, remote searchers,
+//! replay tools and integration tests all run the same pipeline with their own inputs and outputs.
+
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types_eth::BlockId;
+use async_trait::async_trait;
+use reth_chainspec::EthChainSpec;
+use reth_optimism_chainspec::OpChainSpec;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::backrun_analyzer::{BackrunAnalyzer, TokenPairProcessorConfig};
+use crate::competitor_analyzer::CompetitorAnalyzer;
+use crate::config::{MevConfig, ProcessingSection};
+use crate::database_service::{self, DatabaseService};
+use crate::fee_multiplier_controller::FeeMultiplierController;
+use crate::flashblock_state::FlashblockStateSnapshot;
+use crate::flashblocks::FlashblocksEvent;
+use crate::gas_history_store::GasHistoryStore;
+use crate::gas_limit_estimator::GasLimitEstimator;
+use crate::mev_bundle_types::FeeQuote;
+use crate::mev_search_worker::{self, MevOpportunity};
+use crate::network::{AddressBook, Network};
+use crate::opportunity_lifecycle::{self, OpportunityLifecycle};
+use crate::shutdown::Shutdown;
+use crate::state_source::StateSource;
+use crate::transaction_service::TransactionService;
+use crate::wallet_service::WalletService;
+use crate::{lifecycle_timing, mev_task_worker, revm_flashblock_executor};
+
+/// Where the pipeline reads flashblocks from
+#[async_trait]
+pub trait FlashblockSource: Send + 'static {
+    /// Next flashblock, or `None` once the source is exhausted
+    async fn next_flashblock(&mut self) -> Option<FlashblocksEvent>;
+}
+
+/// A subscription to the live flashblocks client
+#[async_trait]
+impl FlashblockSource for broadcast::Receiver<FlashblocksEvent> {
+    async fn next_flashblock(&mut self) -> Option<FlashblocksEvent> {
+        loop {
+            match self.recv().await {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped = skipped, "Flashblock intake lagged behind the feed");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Flashblocks fed by a replay tool or a test
+#[async_trait]
+impl FlashblockSource for mpsc::Receiver<FlashblocksEvent> {
+    async fn next_flashblock(&mut self) -> Option<FlashblocksEvent> {
+        self.recv().await
+    }
+}
+
+/// Where admitted opportunities are submitted
+#[async_trait]
+pub trait SubmissionSink: Send + Sync + 'static {
+    /// Submit an opportunity; returns the hash of the submitted transaction, if one was sent
+    async fn submit(&self, opportunity: &MevOpportunity) -> eyre::Result<Option<B256>>;
+
+    /// Fees an opportunity's legs will be submitted with, so workers simulate with them
+    ///
+    /// Defaults to a fixed 100k wei tip over the base fee.
+    async fn quote_fees(
+        &self,
+        _processor: &TokenPairProcessorConfig,
+        _expected_profit: U256,
+        _gas_used: Option<u64>,
+        base_fee: u128,
+    ) -> FeeQuote {
+        FeeQuote { max_fee_per_gas: base_fee + 100_000, max_priority_fee_per_gas: 100_000 }
+    }
+
+    /// Learned gas margins legs are submitted with; `None` submits at the fixed ceiling
+    fn gas_limit_estimator(&self) -> Option<Arc<GasLimitEstimator>> {
+        None
+    }
+
+    /// A flashblock arrived, before it is executed; returns the hashes of our submissions that
+    /// landed in it
+    fn observe_flashblock(&self, _event: &FlashblocksEvent) -> Vec<B256> {
+        Vec::new()
+    }
+
+    /// The simulator moved to a new block
+    fn on_new_block(&self, _block_number: u64) {}
+
+    /// A flashblock was executed; `landed` holds its transaction hashes
+    ///
+    /// Runs as a tracked submission, so shutdown waits for it.
+    async fn on_flashblock(&self, _landed: HashSet<B256>, _snapshot: FlashblockStateSnapshot) {}
+}
+
+/// The wallets opportunities are simulated and submitted from
+#[async_trait]
+pub trait SubmissionWallets: Send + Sync + 'static {
+    /// Every wallet address, so our own backruns are not counted as competitors
+    fn addresses(&self) -> Vec<Address>;
+
+    /// Wallet the next submission for a processor will use, without claiming it
+    async fn preview_sender(&self, processor: &TokenPairProcessorConfig) -> eyre::Result<Address>;
+
+    /// Nonce the next submission from `wallet` will use, past any still in flight
+    fn next_nonce(&self, _wallet: Address, state_nonce: u64) -> u64 {
+        state_nonce
+    }
+
+    /// Track wallet balances on an executed flashblock
+    fn observe_state(&self, _snapshot: &FlashblockStateSnapshot) {}
+}
+
+/// Submits through the transaction service, reading nonces and balances from a [`StateSource`]
+pub struct TransactionServiceSink<S> {
+    service: Arc<TransactionService>,
+    wallets: Arc<WalletService>,
+    state: S,
+}
+
+impl<S> TransactionServiceSink<S> {
+    pub fn new(service: Arc<TransactionService>, wallets: Arc<WalletService>, state: S) -> Self {
+        Self { service, wallets, state }
+    }
+}
+
+#[async_trait]
+impl<S: StateSource> SubmissionSink for TransactionServiceSink<S> {
+    async fn submit(&self, opportunity: &MevOpportunity) -> eyre::Result<Option<B256>> {
+        self.service.process_opportunity(opportunity, &self.state).await
+    }
+
+    async fn quote_fees(
+        &self,
+        processor: &TokenPairProcessorConfig,
+        expected_profit: U256,
+        gas_used: Option<u64>,
+        base_fee: u128,
+    ) -> FeeQuote {
+        let strategy = format!("Backrun_{}", processor.name);
+        self.service.quote_fees(&strategy, Some(processor), expected_profit, gas_used, base_fee).await
+    }
+
+    fn gas_limit_estimator(&self) -> Option<Arc<GasLimitEstimator>> {
+        Some(self.service.gas_limit_estimator().clone())
+    }
+
+    fn observe_flashblock(&self, event: &FlashblocksEvent) -> Vec<B256> {
+        self.service.observe_flashblock(event).into_iter().map(|inclusion| inclusion.tx_hash).collect()
+    }
+
+    fn on_new_block(&self, block_number: u64) {
+        // Resync wallet balances from canonical state once per block
+        if let Err(e) = self.wallets.refresh_balances(&self.state) {
+            warn!(block = block_number, error = ?e, "Failed to refresh wallet balances");
+        }
+    }
+
+    async fn on_flashblock(&self, landed: HashSet<B256>, snapshot: FlashblockStateSnapshot) {
+        // Replace or cancel our submissions that are still not included, and refill low wallets
+        // from the treasury
+        tokio::join!(
+            self.service.watch_pending(&landed, &snapshot, &self.state),
+            self.service.top_up_wallets(&snapshot, &self.state),
+        );
+    }
+}
+
+/// Opportunity search and submission settings
+#[derive(Debug, Clone)]
+pub struct PipelineConfig {
+    pub min_profit_threshold: U256,
+    pub max_concurrent_opportunities: usize,
+    pub opportunity_timeout: Duration,
+    /// Blocks an opportunity may trail the chain head before it is dropped
+    pub max_block_staleness: u64,
+    pub worker_timeout: Duration,
+    /// Flashblocks queued between intake and simulation
+    pub flashblock_queue_size: usize,
+    /// Opportunities queued between the workers and the handler
+    pub opportunity_queue_size: usize,
+    pub search: mev_task_worker::SearchConfig,
+    /// JSON lines log of every submitted opportunity; `None` disables it
+    pub opportunity_log: Option<PathBuf>,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self::from_processing(&ProcessingSection::default())
+    }
+}
+
+impl PipelineConfig {
+    pub fn from_processing(processing: &ProcessingSection) -> Self {
+        Self {
+            min_profit_threshold: processing.min_profit_threshold(),
+            max_concurrent_opportunities: processing.max_concurrent_opportunities,
+            opportunity_timeout: processing.opportunity_timeout(),
+            max_block_staleness: processing.max_block_staleness,
+            worker_timeout: processing.worker_timeout(),
+            flashblock_queue_size: 100,
+            opportunity_queue_size: 1000,
+            search: mev_task_worker::SearchConfig::default(),
+            opportunity_log: processing.opportunity_log(),
+        }
+    }
+
+    pub fn from_config(config: &MevConfig) -> Self {
+        Self {
+            search: mev_task_worker::SearchConfig {
+                warm_start: config.warm_start_config(),
+                bounds: config.bounds_controller_config(),
+            },
+            ..Self::from_processing(&config.processing)
+        }
+    }
+}
+
+/// Service handles shared by the pipeline stages
+///
+/// Submission and wallet selection go through the [`SubmissionSink`] and
+/// [`SubmissionWallets`] instead.
+#[derive(Clone)]
+pub struct MevServices {
+    pub gas_history: Arc<GasHistoryStore>,
+    pub fee_controller: Option<Arc<FeeMultiplierController>>,
+    pub database: Option<Arc<DatabaseService>>,
+}
+
+/// Builder for [`MevPipeline`]
+pub struct MevPipelineBuilder<S> {
+    chain_spec: Arc<OpChainSpec>,
+    state: S,
+    address_book: AddressBook,
+    config: PipelineConfig,
+    source: Option<Box<dyn FlashblockSource>>,
+    sink: Option<Arc<dyn SubmissionSink>>,
+    wallets: Option<Arc<dyn SubmissionWallets>>,
+    services: Option<MevServices>,
+    shutdown: Shutdown,
+}
+
+impl<S: StateSource> MevPipelineBuilder<S> {
+    /// Processors the workers search and the competitor analysis watches; defaults from the chain id
+    pub fn address_book(mut self, address_book: AddressBook) -> Self {
+        self.address_book = address_book;
+        self
+    }
+
+    pub fn config(mut self, config: PipelineConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn flashblock_source(mut self, source: impl FlashblockSource) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    pub fn submission_sink(mut self, sink: impl SubmissionSink) -> Self {
+        self.sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Wallets workers simulate from; without them workers simulate from a funded placeholder
+    pub fn wallets(mut self, wallets: Arc<dyn SubmissionWallets>) -> Self {
+        self.wallets = Some(wallets);
+        self
+    }
+
+    pub fn services(mut self, services: MevServices) -> Self {
+        self.services = Some(services);
+        self
+    }
+
+    /// Share a shutdown signal with the caller, e.g. to drain on the node's shutdown
+    pub fn shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    pub fn build(self) -> eyre::Result<MevPipeline<S>> {
+        let source = self.source.ok_or_else(|| eyre::eyre!("MevPipeline requires a flashblock source"))?;
+        let sink = self.sink.ok_or_else(|| eyre::eyre!("MevPipeline requires a submission sink"))?;
+        let services = self.services.ok_or_else(|| eyre::eyre!("MevPipeline requires service handles"))?;
+        Ok(MevPipeline {
+            chain_spec: self.chain_spec,
+            state: self.state,
+            address_book: self.address_book,
+            config: self.config,
+            source,
+            sink,
+            wallets: self.wallets,
+            services,
+            shutdown: self.shutdown,
+        })
+    }
+}
+
+/// Flashblock intake, simulation, strategy search and submission, ready to spawn
+pub struct MevPipeline<S> {
+    chain_spec: Arc<OpChainSpec>,
+    state: S,
+    address_book: AddressBook,
+    config: PipelineConfig,
+    source: Box<dyn FlashblockSource>,
+    sink: Arc<dyn SubmissionSink>,
+    wallets: Option<Arc<dyn SubmissionWallets>>,
+    services: MevServices,
+    shutdown: Shutdown,
+}
+
+/// A running pipeline
+pub struct MevPipelineHandle {
+    shutdown: Shutdown,
+    lifecycle: Arc<OpportunityLifecycle>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MevPipelineHandle {
+    /// Signal that stops the stages and tracks in-flight submissions
+    pub fn shutdown(&self) -> &Shutdown {
+        &self.shutdown
+    }
+
+    /// Per (block, processor) opportunity states
+    pub fn lifecycle(&self) -> &Arc<OpportunityLifecycle> {
+        &self.lifecycle
+    }
+
+    /// Wait for every stage to stop, after shutdown or once the flashblock source is exhausted
+    ///
+    /// Submissions still in flight are not awaited; drain them with the shutdown pipeline.
+    pub async fn join(self) {
+        for task in self.tasks {
+            if let Err(e) = task.await {
+                error!(error = ?e, "MEV pipeline stage panicked");
+            }
+        }
+    }
+}
+
+impl<S: StateSource> MevPipeline<S> {
+    pub fn builder(chain_spec: Arc<OpChainSpec>, state: S) -> MevPipelineBuilder<S> {
+        let address_book = Network::from_chain_id(chain_spec.chain().id()).address_book();
+        MevPipelineBuilder {
+            chain_spec,
+            state,
+            address_book,
+            config: PipelineConfig::default(),
+            source: None,
+            sink: None,
+            wallets: None,
+            services: None,
+            shutdown: Shutdown::new(),
+        }
+    }
+
+    /// Start the intake, simulator and opportunity handler tasks
+    pub fn spawn(self) -> MevPipelineHandle {
+        let MevPipeline { chain_spec, state, address_book, config, mut source, sink, wallets, services, shutdown } = self;
+        let PipelineConfig {
+            min_profit_threshold,
+            max_concurrent_opportunities,
+            opportunity_timeout,
+            max_block_staleness,
+            worker_timeout,
+            flashblock_queue_size,
+            opportunity_queue_size,
+            search,
+            opportunity_log,
+        } = config;
+        let search_config = Arc::new(search);
+        let analyzer = Arc::new(BackrunAnalyzer::with_processors(address_book.backrun_processors, min_profit_threshold));
+        
+        // Create a channel for flashblock processing queue
+        let (flashblock_tx, mut flashblock_rx) = tokio::sync::mpsc::channel(flashblock_queue_size);
+        
+        // Spawn task to receive flashblocks and queue them
+        let intake_shutdown = shutdown.clone();
+        let intake = tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    _ = intake_shutdown.triggered() => {
+                        info!("Shutdown - no longer accepting flashblocks");
+                        break;
+                    }
+                    event = source.next_flashblock() => match event {
+                        Some(event) => event,
+                        None => break,
+                    },
+                };
+                debug!(
+                    block = event.block_number,
+                    flashblock = event.index,
+                    tx_count = event.transactions.len(),
+                    state_root = %event.state_root,
+                    receipts_root = %event.receipts_root,
+                    "Flashblocks event received"
+                );
+                
+                // Increment metrics
+                crate::metrics::MEV_METRICS.flashblocks_received_total.increment(1);
+                
+                // Queue the event for processing
+                if let Err(e) = flashblock_tx.send(event).await {
+                    error!(error = %e, "Failed to queue flashblock");
+                }
+            }
+        });
+        
+        // Create channel for MEV results
+        let (mev_result_tx, mut mev_result_rx) = tokio::sync::mpsc::channel::<MevOpportunity>(opportunity_queue_size);
+        
+        // Clone for the MEV handler task
+        let threshold_for_handler = min_profit_threshold;
+        let mev_state = state.clone();
+        
+        // Per (block, processor) opportunity states, shared with the flashblock loop for inclusions
+        let lifecycle = Arc::new(OpportunityLifecycle::new(max_block_staleness));
+        let lifecycle_for_handle = lifecycle.clone();
+        let lifecycle_for_flashblocks = lifecycle.clone();
+        
+        // Spawn MEV opportunity handler with parallel processing
+        let handler_shutdown = shutdown.clone();
+        let handler = tokio::spawn(async move {
+            let semaphore = Arc::new(tokio::sync::Semaphore::new(max_concurrent_opportunities));
+            
+            loop {
+                let opportunity = tokio::select! {
+                    _ = handler_shutdown.triggered() => {
+                        info!("Shutdown - no longer accepting MEV opportunities");
+                        break;
+                    }
+                    opportunity = mev_result_rx.recv() => match opportunity {
+                        Some(opportunity) => opportunity,
+                        None => break,
+                    },
+                };
+                // Get current block number for staleness check
+                let current_block = match mev_state.best_block_number() {
+                    Ok(num) => num,
+                    Err(e) => {
+                        error!(error = ?e, "Failed to get current block number");
+                        continue;
+                    }
+                };
+                
+                lifecycle.advance(current_block);
+                
+                // Admit one live submission per block and processor; a later flashblock may only
+                // replace it with a strictly better opportunity
+                let ticket = match lifecycle.admit(
+                    opportunity.block_number,
+                    opportunity.flashblock_index,
+                    &opportunity.strategy,
+                    opportunity.expected_profit,
+                ) {
+                    opportunity_lifecycle::Admission::New(ticket) => {
+                        info!(
+                            strategy = %opportunity.strategy,
+                            block = opportunity.block_number,
+                            flashblock = opportunity.flashblock_index,
+                            profit_wei = %opportunity.expected_profit,
+                            bundle_size = opportunity.bundle.transactions.len(),
+                            current_block = current_block,
+                            "MEV opportunity found (first for this block/processor)"
+                        );
+                        ticket
+                    }
+                    opportunity_lifecycle::Admission::Resubmission { ticket, previous_profit } => {
+                        info!(
+                            strategy = %opportunity.strategy,
+                            block = opportunity.block_number,
+                            flashblock = opportunity.flashblock_index,
+                            profit_wei = %opportunity.expected_profit,
+                            previous_profit_wei = %previous_profit,
+                            current_block = current_block,
+                            "MEV opportunity found (better than submitted, resubmitting)"
+                        );
+                        ticket
+                    }
+                    opportunity_lifecycle::Admission::Duplicate(state) => {
+                        debug!(
+                            strategy = %opportunity.strategy,
+                            block = opportunity.block_number,
+                            state = ?state,
+                            "Skipping - block/processor already has an opportunity in flight"
+                        );
+                        continue;
+                    }
+                    opportunity_lifecycle::Admission::Stale { head } => {
+                        warn!(
+                            strategy = %opportunity.strategy,
+                            opportunity_block = opportunity.block_number,
+                            current_block = head,
+                            blocks_behind = head - opportunity.block_number,
+                            "Skipping stale MEV opportunity"
+                        );
+                        continue;
+                    }
+                };
+                
+                // Record opportunity metrics
+                crate::metrics::MEV_METRICS.opportunities_found_total.increment(1);
+                
+                // Skip if below threshold
+                if opportunity.expected_profit <= threshold_for_handler {
+                    debug!(
+                        strategy = %opportunity.strategy,
+                        profit_wei = %opportunity.expected_profit,
+                        threshold_wei = %threshold_for_handler,
+                        "MEV opportunity below profit threshold, skipping"
+                    );
+                    lifecycle.failed(&ticket);
+                    continue;
+                }
+                
+                // Process profitable opportunities in parallel
+                let permit = semaphore.clone().acquire_owned().await.unwrap();
+                let sink = sink.clone();
+                let lifecycle = lifecycle.clone();
+                let opportunity_log = opportunity_log.clone();
+                lifecycle.validated(&ticket);
+                
+                // Tracked so a shutdown lets the submission finish
+                handler_shutdown.spawn_submission(async move {
+                    // Hold permit for duration of processing
+                    let _permit = permit;
+                    
+                    crate::metrics::MEV_METRICS.opportunities_profitable_total.increment(1);
+                    
+                    // Process with timeout
+                    let process_start = std::time::Instant::now();
+                    match tokio::time::timeout(
+                        opportunity_timeout,
+                        sink.submit(&opportunity)
+                    ).await {
+                        Ok(Ok(tx_hash)) => {
+                            lifecycle.submitted(&ticket, tx_hash);
+                            let elapsed = process_start.elapsed();
+                            info!(
+                                strategy = %opportunity.strategy,
+                                block = opportunity.block_number,
+                                elapsed_ms = elapsed.as_millis(),
+                                tx_hash = ?tx_hash,
+                                "🎊🎉 MEV OPPORTUNITY CAPTURED! 🎯💸 {} strategy executed in {}ms! 🚀⚡ Transaction: {:?} 🌟🔥",
+                                opportunity.strategy,
+                                elapsed.as_millis(),
+                                tx_hash
+                            );
+                            
+                            // Log to JSON with transaction hash
+                            if let Err(e) = log_mev_opportunity_to_json(opportunity_log.as_deref(), &opportunity, tx_hash) {
+                                error!(error = ?e, "Failed to log MEV opportunity to JSON");
+                            }
+                        }
+                        Ok(Err(e)) => {
+                            error!(
+                                strategy = %opportunity.strategy,
+                                block = opportunity.block_number,
+                                error = ?e,
+                                "Failed to process MEV opportunity"
+                            );
+                            
+                            // Log to JSON even on failure (with no tx hash)
+                            if let Err(log_err) = log_mev_opportunity_to_json(opportunity_log.as_deref(), &opportunity, None) {
+                                error!(error = ?log_err, "Failed to log MEV opportunity to JSON");
+                            }
+                            
+                            // Free the slot so another flashblock can try
+                            lifecycle.failed(&ticket);
+                        }
+                        Err(_) => {
+                            error!(
+                                strategy = %opportunity.strategy,
+                                block = opportunity.block_number,
+                                timeout_secs = opportunity_timeout.as_secs(),
+                                "MEV opportunity processing timed out"
+                            );
+                            
+                            // Log to JSON even on timeout (with no tx hash)
+                            if let Err(log_err) = log_mev_opportunity_to_json(opportunity_log.as_deref(), &opportunity, None) {
+                                error!(error = ?log_err, "Failed to log MEV opportunity to JSON");
+                            }
+                            
+                            // Free the slot so another flashblock can try
+                            lifecycle.failed(&ticket);
+                        }
+                    }
+                });
+            }
+        });
+        
+        // Clone services for flashblock thread
+        let state_for_task = state;
+        let sink_for_flashblocks = sink.clone();
+        let db_service_for_flashblocks = services.database.clone();
+        let gas_history_store_for_flashblocks = services.gas_history.clone();
+        let fee_controller_for_flashblocks = services.fee_controller.clone();
+        let shutdown_for_flashblocks = shutdown.clone();
+        let timing_tracker = lifecycle_timing::create_timing_tracker();
+        
+        // Competitor backrun detection over the same processor configs the workers use
+        let competitor_analyzer = CompetitorAnalyzer::new(
+            &analyzer,
+            wallets.as_ref().map(|wallets| wallets.addresses()).unwrap_or_default(),
+        );
+        
+        // Spawn dedicated synchronous flashblock simulator thread
+        let simulator_chain_spec = chain_spec;
+        let simulator = tokio::spawn(async move {
+            info!("Starting dedicated flashblock simulator thread");
+            
+            // Create revm executor over the state source
+            let chain_spec = simulator_chain_spec;
+            let mut revm_executor = revm_flashblock_executor::RevmFlashblockExecutor::new(chain_spec.clone());
+            let mut revm_initialized = false;
+            let mut current_block = 0u64;
+            
+            while let Some(event) = flashblock_rx.recv().await {
+                // Queued flashblocks are dropped once shutdown starts
+                if shutdown_for_flashblocks.is_triggered() {
+                    break;
+                }
+                let sim_start = std::time::Instant::now();
+                
+                // Create lifecycle timing for this flashblock
+                let mut timing = lifecycle_timing::LifecycleTiming::new(
+                    event.received_at,
+                    event.block_number,
+                    event.index,
+                );
+                timing.processing_started = Some(sim_start);
+                
+                // Record queue latency metric
+                let queue_latency = sim_start.duration_since(event.received_at).as_secs_f64();
+                crate::metrics::MEV_METRICS.flashblock_queue_latency_seconds.record(queue_latency);
+                
+                // Clone for workers
+                let timing_for_workers = Arc::new(tokio::sync::Mutex::new(Some(timing.clone())));
+                *timing_tracker.lock().await = Some(timing.clone());
+                
+                debug!(
+                    block = event.block_number,
+                    flashblock = event.index,
+                    "Processing flashblock in simulator thread"
+                );
+                
+                // Log all transaction hashes from this flashblock to database
+                if let Some(ref db_service) = db_service_for_flashblocks {
+                    let tx_logs: Vec<database_service::TransactionLog> = event.transactions.iter()
+                        .map(|tx| database_service::TransactionLog {
+                            hash: *tx.tx_hash(),
+                            source: format!("flashblock_{}", event.index),
+                            timestamp: chrono::Utc::now(),
+                            block_number: event.block_number,
+                        })
+                        .collect();
+                    
+                    if !tx_logs.is_empty() {
+                        debug!(
+                            count = tx_logs.len(),
+                            block = event.block_number,
+                            flashblock = event.index,
+                            "Logging flashblock transactions to database"
+                        );
+                        
+                        if let Err(e) = db_service.log_transactions(tx_logs).await {
+                            error!("Failed to log flashblock transactions: {}", e);
+                        }
+                    }
+                }
+                
+                // Resolve outcomes of our pending submissions for fee tuning
+                if let Some(ref controller) = fee_controller_for_flashblocks {
+                    controller.observe_flashblock(&event).await;
+                }
+                for tx_hash in sink_for_flashblocks.observe_flashblock(&event) {
+                    lifecycle_for_flashblocks.included(&tx_hash);
+                }
+                
+                // Use revm-based executor
+                debug!("Using revm-based executor");
+                
+                // Re-initialize for new block if needed
+                if !revm_initialized || event.block_number != current_block {
+                    if event.block_number != current_block {
+                        debug!(old_block = current_block, new_block = event.block_number, "New block detected");
+                        current_block = event.block_number;
+                        
+                        sink_for_flashblocks.on_new_block(current_block);
+                    }
+                    
+                    match revm_executor.initialize(state_for_task.clone(), BlockId::latest()).await {
+                        Ok(_) => {
+                            debug!("Revm executor initialized with state source");
+                            revm_initialized = true;
+                        }
+                        Err(e) => {
+                            error!(error = ?e, "Failed to initialize revm executor");
+                            continue;
+                        }
+                    }
+                }
+                
+                // Execute with revm
+                match revm_executor.execute_flashblock(&event, event.index).await {
+                    Ok(results) => {
+                        let successful = results.iter().filter(|r| r.error.is_none()).count();
+                        debug!(
+                            successful = successful,
+                            total = results.len(),
+                            "Revm execution complete"
+                        );
+                        
+                        // Update timing and record metric
+                        timing.execution_completed = Some(std::time::Instant::now());
+                        let exec_duration = timing.execution_completed.unwrap().duration_since(timing.processing_started.unwrap()).as_secs_f64();
+                        crate::metrics::MEV_METRICS.flashblock_execution_duration_seconds.record(exec_duration);
+                        
+                        // Look for other searchers' backruns on our watched accounts
+                        let observations = competitor_analyzer.analyze(
+                            &event,
+                            revm_executor.last_touched_accounts(),
+                            &results,
+                        );
+                        if let Some(ref controller) = fee_controller_for_flashblocks {
+                            controller.observe_backruns(&observations);
+                        }
+                        if let Some(ref db_service) = db_service_for_flashblocks {
+                            if let Err(e) = db_service.log_backruns(observations).await {
+                                error!("Failed to log backrun observations: {}", e);
+                            }
+                        }
+                        
+                        // Export state snapshot and trigger MEV search
+                        let export_start = std::time::Instant::now();
+                        match revm_executor.export_state_snapshot(event.index, event.transactions.clone()) {
+                            Ok(state_snapshot) => {
+                                let export_time = export_start.elapsed().as_secs_f64() * 1000.0;
+                                debug!(
+                                    accounts = state_snapshot.account_changes.len(),
+                                    time_ms = export_time,
+                                    "State snapshot exported"
+                                );
+                                
+                                // Update timing and record metric
+                                timing.state_export_completed = Some(std::time::Instant::now());
+                                let export_duration = export_start.elapsed().as_secs_f64();
+                                crate::metrics::MEV_METRICS.state_export_duration_seconds.record(export_duration);
+                                
+                                // Track wallet balances, then let the sink chase pending submissions
+                                if let Some(ref wallets) = wallets {
+                                    wallets.observe_state(&state_snapshot);
+                                }
+                                {
+                                    let sink = sink_for_flashblocks.clone();
+                                    let snapshot = state_snapshot.clone();
+                                    let landed: HashSet<B256> = event.transactions.iter()
+                                        .map(|tx| *tx.tx_hash())
+                                        .collect();
+                                    shutdown_for_flashblocks.spawn_submission(async move {
+                                        sink.on_flashblock(landed, snapshot).await;
+                                    });
+                                }
+                                
+                                // Analyze state to determine which strategies to trigger
+                                let strategies = mev_search_worker::analyze_state_for_strategies(&analyzer, &state_snapshot);
+                                timing.strategy_analysis_completed = Some(std::time::Instant::now());
+                                
+                                if !strategies.is_empty() {
+                                    debug!(
+                                        count = strategies.len(),
+                                        strategies = ?strategies,
+                                        "Triggering MEV strategies"
+                                    );
+                                    
+                                    // Spawn all MEV tasks in batch for reduced overhead
+                                    mev_task_worker::spawn_mev_tasks_batch(
+                                        chain_spec.clone(),
+                                        state_for_task.clone(),
+                                        strategies,
+                                        state_snapshot.clone(),
+                                        event.received_at,
+                                        mev_result_tx.clone(),
+                                        Some(timing_for_workers.clone()),
+                                        min_profit_threshold,
+                                        analyzer.clone(),
+                                        gas_history_store_for_flashblocks.clone(),
+                                        sink_for_flashblocks.clone(),
+                                        wallets.clone(),
+                                        search_config.clone(),
+                                        worker_timeout,
+                                    );
+                                    timing.workers_spawned = Some(std::time::Instant::now());
+                                } else {
+                                    debug!("No MEV strategies triggered for this flashblock");
+                                }
+                                
+                                // Benchmarking removed - no longer needed
+                            }
+                            Err(e) => {
+                                error!(error = ?e, "Failed to export state snapshot");
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!(error = ?e, "Revm execution failed");
+                    }
+                }
+                
+                let total_time = sim_start.elapsed().as_secs_f64() * 1000.0;
+                debug!(
+                    block = event.block_number,
+                    flashblock = event.index,
+                    time_ms = total_time,
+                    "Flashblock processing completed"
+                );
+                
+                // Update timing tracker with final timing
+                *timing_tracker.lock().await = Some(timing);
+            }
+            
+            warn!("Flashblock simulator thread exiting");
+        });
+        
+        MevPipelineHandle {
+            shutdown,
+            lifecycle: lifecycle_for_handle,
+            tasks: vec![intake, simulator, handler],
+        }
+    }
+}
+
+/// Log MEV opportunity to JSON file
+fn log_mev_opportunity_to_json(path: Option<&Path>, opportunity: &MevOpportunity, tx_hash: Option<B256>) -> eyre::Result<()> {
+    use std::fs::OpenOptions;
+    
+    let Some(path) = path else {
+        return Ok(());
+    };
+    use std::io::Write;
+    use serde::Serialize;
+    
+    #[derive(Serialize)]
+    struct MevResultLog {
+        timestamp: String,
+        block_number: u64,
+        flashblock_index: u32,
+        strategy: String,
+        expected_profit_wei: String,
+        expected_profit_eth: f64,
+        bundle_size: usize,
+        // Add first transaction details if available
+        first_tx_to: Option<String>,
+        first_tx_calldata: Option<String>,
+        // Hash of the last transaction in the flashblock
+        index_hash: Option<String>,
+        // Scan ID to link back to trigger
+        scan_id: String,
+        // Transaction hash if submitted
+        transaction_hash: Option<String>,
+    }
+    
+    let first_tx = opportunity.bundle.transactions.first();
+    
+    let (first_tx_to, first_tx_calldata) = match first_tx {
+        Some(crate::mev_bundle_types::BundleTransaction::Unsigned { to, input, .. }) => {
+            (to.map(|addr| format!("{:?}", addr)), Some(format!("0x{}", hex::encode(input))))
+        }
+        Some(crate::mev_bundle_types::BundleTransaction::Signed(_)) => {
+            // For signed transactions, we'd need to decode the envelope
+            (None, None)
+        }
+        None => (None, None),
+    };
+    
+    // Get current time with milliseconds
+    let now = chrono::Utc::now();
+    let timestamp = now.format("%Y-%m-%d %H:%M:%S%.3f UTC").to_string();
+    
+    let result = MevResultLog {
+        timestamp,
+        block_number: opportunity.block_number,
+        flashblock_index: opportunity.flashblock_index,
+        strategy: opportunity.strategy.clone(),
+        expected_profit_wei: opportunity.expected_profit.to_string(),
+        expected_profit_eth: opportunity.expected_profit.as_limbs()[0] as f64 / 1e18,
+        bundle_size: opportunity.bundle.transactions.len(),
+        first_tx_to,
+        first_tx_calldata,
+        index_hash: opportunity.last_flashblock_tx_hash.map(|h| format!("{:?}", h)),
+        scan_id: opportunity.scan_id.clone(),
+        transaction_hash: tx_hash.map(|h| format!("{:?}", h)),
+    };
+    
+    // Append to JSONL file (JSON Lines format)
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    
+    let json = serde_json::to_string(&result)?;
+    writeln!(file, "{}", json)?;
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor_codec::ExecutorCodec;
+    use crate::gas_history_store::MemoryGasHistory;
+    use crate::state_source::StateBlock;
+    use crate::transaction_service::TransactionService;
+    use crate::wallet_service::{LocalKeySigner, SharedSigner};
+    use alloy_consensus::TxEip1559;
+    use alloy_primitives::TxKind;
+    use parking_lot::Mutex;
+    use revm::bytecode::Bytecode;
+    use revm::database::{CacheDB, EmptyDB};
+    use revm::state::AccountInfo;
+    use std::collections::HashMap;
+
+    /// Processor stub: reverts every call with a profit of 1_000_000 wei, which the search
+    /// harness reads as the call's profit
+    const PROCESSOR_CODE: [u8; 12] = [0x62, 0x0f, 0x42, 0x40, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xfd];
+
+    /// Chain state held in memory
+    #[derive(Debug, Clone)]
+    struct MemoryState {
+        db: CacheDB<EmptyDB>,
+        block: StateBlock,
+    }
+
+    impl StateSource for MemoryState {
+        type Database = CacheDB<EmptyDB>;
+
+        fn best_block_number(&self) -> eyre::Result<u64> {
+            Ok(self.block.number)
+        }
+
+        fn block(&self, _block_id: BlockId) -> eyre::Result<StateBlock> {
+            Ok(self.block)
+        }
+
+        fn latest_state(&self) -> eyre::Result<Self::Database> {
+            Ok(self.db.clone())
+        }
+    }
+
+    /// Records what the pipeline hands it instead of submitting
+    struct RecordingSink {
+        submissions: mpsc::UnboundedSender<MevOpportunity>,
+        new_blocks: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl SubmissionSink for RecordingSink {
+        async fn submit(&self, opportunity: &MevOpportunity) -> eyre::Result<Option<B256>> {
+            self.submissions.send(opportunity.clone())?;
+            Ok(Some(B256::with_last_byte(1)))
+        }
+
+        fn on_new_block(&self, block_number: u64) {
+            self.new_blocks.lock().push(block_number);
+        }
+    }
+
+    fn event(block_number: u64, index: u32) -> FlashblocksEvent {
+        FlashblocksEvent {
+            block_number,
+            index,
+            transactions: vec![],
+            state_root: B256::ZERO,
+            receipts_root: B256::ZERO,
+            metadata: crate::flashblocks::Metadata {
+                receipts: HashMap::new(),
+                new_account_balances: HashMap::new(),
+                block_number,
+            },
+            received_at: std::time::Instant::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_source_skips_lag_and_ends_on_close() {
+        let (tx, mut source) = broadcast::channel(2);
+        for index in 0..4 {
+            tx.send(event(100, index)).unwrap();
+        }
+        drop(tx);
+
+        // The two oldest were overwritten; the source resumes at the oldest retained event
+        assert_eq!(source.next_flashblock().await.map(|e| e.index), Some(2));
+        assert_eq!(source.next_flashblock().await.map(|e| e.index), Some(3));
+        assert!(source.next_flashblock().await.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_flashblock_touching_processor_reaches_sink() {
+        // Hardhat account #1 (test key, do not use in production)
+        let signer: SharedSigner = Arc::new(LocalKeySigner::new(
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d".parse().unwrap(),
+        ));
+        let pool = Address::with_last_byte(0xbb);
+        let processor = Address::with_last_byte(0xaa);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(signer.address(), AccountInfo {
+            balance: U256::from(10u128.pow(18)),
+            ..Default::default()
+        });
+        let code = Bytecode::new_raw(PROCESSOR_CODE.to_vec().into());
+        db.insert_account_info(processor, AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code));
+        let state = MemoryState {
+            db,
+            block: StateBlock { number: 100, timestamp: 1_750_000_000, gas_limit: 30_000_000, base_fee_per_gas: Some(1_000_000) },
+        };
+
+        let address_book = AddressBook {
+            backrun_processors: vec![TokenPairProcessorConfig {
+                name: "Test".to_string(),
+                tokens: vec![],
+                accounts: vec![pool],
+                contract_address: processor,
+                default_value: U256::from(1_000),
+                codec: ExecutorCodec::V1_SHORT,
+                check_balance_of: None,
+                priority_fee_multiplier: None,
+                wallet_strategy: None,
+            }],
+        };
+        let (submissions, mut submitted) = mpsc::unbounded_channel();
+        let new_blocks = Arc::new(Mutex::new(Vec::new()));
+        let (flashblocks, source) = mpsc::channel(4);
+        let pipeline = MevPipeline::builder(reth_optimism_chainspec::BASE_MAINNET.clone(), state)
+            .address_book(address_book)
+            .config(PipelineConfig {
+                min_profit_threshold: U256::from(1),
+                opportunity_log: None,
+                ..Default::default()
+            })
+            .flashblock_source(source)
+            .submission_sink(RecordingSink { submissions, new_blocks: new_blocks.clone() })
+            .services(MevServices {
+                gas_history: Arc::new(GasHistoryStore::new(Arc::new(MemoryGasHistory::default()))),
+                fee_controller: None,
+                database: None,
+            })
+            .build()
+            .unwrap()
+            .spawn();
+
+        // A transfer into the watched pool triggers the processor
+        let trigger = TxEip1559 {
+            chain_id: 8453,
+            nonce: 0,
+            gas_limit: 21_000,
+            max_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(pool),
+            value: U256::from(1),
+            ..Default::default()
+        };
+        let (trigger, _) = TransactionService::sign_leg(&signer, trigger).await.unwrap();
+        let mut flashblock = event(100, 0);
+        flashblock.transactions = vec![trigger];
+        flashblocks.send(flashblock).await.unwrap();
+
+        let opportunity = tokio::time::timeout(Duration::from_secs(30), submitted.recv())
+            .await
+            .expect("opportunity submitted")
+            .unwrap();
+        assert_eq!(opportunity.strategy, "Backrun_Test");
+        assert_eq!(opportunity.block_number, 100);
+        assert_eq!(opportunity.expected_profit, U256::from(1_000_000));
+        assert_eq!(opportunity.bundle.transactions[0].to(), Some(processor));
+        assert_eq!(*new_blocks.lock(), vec![100]);
+
+        // Exhausting the source stops every stage
+        drop(flashblocks);
+        tokio::time::timeout(Duration::from_secs(30), pipeline.join()).await.expect("pipeline stopped");
+    }
+
+    #[test]
+    fn test_config_follows_processing_section() {
+        let config = PipelineConfig::default();
+        assert_eq!(config.max_block_staleness, ProcessingSection::default().max_block_staleness);
+        assert_eq!(config.opportunity_timeout, ProcessingSection::default().opportunity_timeout());
+    }
+}
@@ -0,0 +1,293 @@
+//! Shared Redis handle used by every service that keeps state in Redis.
+//!
+//! One supervisor task per instance connects with exponential backoff, so a Redis that is down
+//! at boot is picked up once it comes back instead of leaving services disconnected for the
+//! life of the process. Once connected, the connection manager re-dials dropped connections
+//! on the next command. Keys are scoped per network and per instance.
+
+use parking_lot::RwLock;
+use redis::aio::ConnectionManager;
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use std::future::Future;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::network::Network;
+
+/// Connection settings for one Redis instance
+#[derive(Debug, Clone)]
+pub struct RedisClientConfig {
+    /// Label for logs and metrics, e.g. `local`
+    pub name: String,
+    pub url: String,
+    /// Prefix every key is scoped under, see [`namespace`]
+    pub namespace: String,
+    pub connect_timeout: Duration,
+    pub command_timeout: Duration,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RedisClientConfig {
+    fn default() -> Self {
+        Self {
+            name: "local".to_string(),
+            url: "redis://localhost:6379/".to_string(),
+            namespace: "mev".to_string(),
+            connect_timeout: Duration::from_secs(2),
+            command_timeout: Duration::from_millis(500),
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Key prefix for a network and optional instance id
+///
+/// Base mainnet keeps the bare `mev` prefix existing deployments and tooling write to; other
+/// networks get their own segment so a shared Redis never mixes chains. An instance id
+/// isolates one node's state from others on the same network.
+pub fn namespace(network: Network, instance: Option<&str>) -> String {
+    let mut namespace = "mev".to_string();
+    if network != Network::BaseMainnet {
+        namespace.push(':');
+        namespace.push_str(network.name());
+    }
+    if let Some(instance) = instance.map(str::trim).filter(|i| !i.is_empty()) {
+        namespace.push(':');
+        namespace.push_str(instance);
+    }
+    namespace
+}
+
+/// Connection state of a [`RedisHandle`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RedisHealth {
+    /// Never connected yet; the supervisor is retrying
+    Connecting = 0,
+    /// Last command succeeded
+    Healthy = 1,
+    /// Connected before, but the last command failed or timed out
+    Degraded = 2,
+}
+
+impl RedisHealth {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Healthy,
+            2 => Self::Degraded,
+            _ => Self::Connecting,
+        }
+    }
+}
+
+/// Why a Redis command did not produce a value
+#[derive(Debug, thiserror::Error)]
+pub enum RedisCommandError {
+    #[error("redis not connected")]
+    Unavailable,
+    #[error("redis command timed out after {0:?}")]
+    Timeout(Duration),
+    #[error(transparent)]
+    Redis(#[from] redis::RedisError),
+}
+
+/// Per-client Redis metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "mev.redis")]
+pub struct RedisClientMetrics {
+    /// Connection attempts made by the supervisor
+    pub connect_attempts_total: Counter,
+    /// Connection attempts that failed or timed out
+    pub connect_failures_total: Counter,
+    /// Time to establish a connection
+    pub connect_duration_seconds: Histogram,
+    /// Command round-trip latency
+    pub command_duration_seconds: Histogram,
+    /// Commands that returned an error
+    pub command_errors_total: Counter,
+    /// Commands that exceeded the command timeout
+    pub command_timeouts_total: Counter,
+    /// Commands skipped because no connection was available
+    pub command_unavailable_total: Counter,
+    /// 1 when the last command succeeded, 0 otherwise
+    pub healthy: Gauge,
+}
+
+/// Cloneable handle to a self-healing Redis connection
+pub struct RedisHandle {
+    config: RedisClientConfig,
+    conn: RwLock<Option<ConnectionManager>>,
+    health: AtomicU8,
+    metrics: RedisClientMetrics,
+}
+
+impl RedisHandle {
+    /// Create the handle and start connecting in the background
+    pub fn connect(config: RedisClientConfig) -> Arc<Self> {
+        let handle = Arc::new(Self::disconnected(config));
+        tokio::spawn(handle.clone().supervise());
+        handle
+    }
+
+    /// Handle that never connects; commands fail with [`RedisCommandError::Unavailable`]
+    pub fn disconnected(config: RedisClientConfig) -> Self {
+        let metrics = RedisClientMetrics::new_with_labels(&[("client", config.name.clone())]);
+        Self {
+            config,
+            conn: RwLock::new(None),
+            health: AtomicU8::new(RedisHealth::Connecting as u8),
+            metrics,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.config.namespace
+    }
+
+    pub fn health(&self) -> RedisHealth {
+        RedisHealth::from_u8(self.health.load(Ordering::Relaxed))
+    }
+
+    /// Key scoped to this handle's network and instance
+    pub fn key(&self, suffix: &str) -> String {
+        format!("{}:{}", self.config.namespace, suffix)
+    }
+
+    /// Run a command on the shared connection, recording latency and health
+    pub async fn run<T, F, Fut>(&self, command: F) -> Result<T, RedisCommandError>
+    where
+        F: FnOnce(ConnectionManager) -> Fut,
+        Fut: Future<Output = redis::RedisResult<T>>,
+    {
+        let Some(conn) = self.conn.read().clone() else {
+            self.metrics.command_unavailable_total.increment(1);
+            return Err(RedisCommandError::Unavailable);
+        };
+
+        let start = Instant::now();
+        let result = tokio::time::timeout(self.config.command_timeout, command(conn)).await;
+        self.metrics.command_duration_seconds.record(start.elapsed().as_secs_f64());
+
+        match result {
+            Ok(Ok(value)) => {
+                self.set_health(RedisHealth::Healthy);
+                Ok(value)
+            }
+            Ok(Err(e)) => {
+                self.metrics.command_errors_total.increment(1);
+                // Server-side errors (wrong type, bad script) say nothing about the connection
+                if e.is_io_error() || e.is_connection_dropped() || e.is_connection_refusal() || e.is_timeout() {
+                    self.set_health(RedisHealth::Degraded);
+                }
+                Err(e.into())
+            }
+            Err(_) => {
+                self.metrics.command_timeouts_total.increment(1);
+                self.set_health(RedisHealth::Degraded);
+                Err(RedisCommandError::Timeout(self.config.command_timeout))
+            }
+        }
+    }
+
+    fn set_health(&self, health: RedisHealth) {
+        let previous = RedisHealth::from_u8(self.health.swap(health as u8, Ordering::Relaxed));
+        self.metrics.healthy.set(if health == RedisHealth::Healthy { 1.0 } else { 0.0 });
+        if previous != health {
+            match health {
+                RedisHealth::Healthy => info!(client = %self.config.name, "🟢 Redis healthy"),
+                RedisHealth::Degraded => warn!(client = %self.config.name, "🟠 Redis degraded - commands failing"),
+                RedisHealth::Connecting => {}
+            }
+        }
+    }
+
+    /// Retry the initial connection with exponential backoff until it succeeds
+    async fn supervise(self: Arc<Self>) {
+        let mut backoff = self.config.initial_backoff;
+        let mut attempts = 0u32;
+        loop {
+            attempts += 1;
+            self.metrics.connect_attempts_total.increment(1);
+            let start = Instant::now();
+            match self.try_connect().await {
+                Ok(conn) => {
+                    self.metrics.connect_duration_seconds.record(start.elapsed().as_secs_f64());
+                    *self.conn.write() = Some(conn);
+                    info!(
+                        client = %self.config.name,
+                        namespace = %self.config.namespace,
+                        attempts = attempts,
+                        "🔌 Connected to Redis"
+                    );
+                    self.set_health(RedisHealth::Healthy);
+                    return;
+                }
+                Err(e) => {
+                    self.metrics.connect_failures_total.increment(1);
+                    // Only the first failure is loud; a Redis that stays down would flood the log
+                    if attempts == 1 {
+                        warn!(client = %self.config.name, error = %e, "Redis unavailable - retrying in the background");
+                    } else {
+                        debug!(client = %self.config.name, error = %e, attempts = attempts, retry_in_ms = backoff.as_millis() as u64, "Redis still unavailable");
+                    }
+                    tokio::time::sleep(backoff).await;
+                    backoff = next_backoff(backoff, self.config.max_backoff);
+                }
+            }
+        }
+    }
+
+    async fn try_connect(&self) -> eyre::Result<ConnectionManager> {
+        let client = redis::Client::open(self.config.url.as_str())?;
+        let conn = tokio::time::timeout(self.config.connect_timeout, ConnectionManager::new(client))
+            .await
+            .map_err(|_| eyre::eyre!("connect timed out after {:?}", self.config.connect_timeout))??;
+        Ok(conn)
+    }
+}
+
+fn next_backoff(current: Duration, max: Duration) -> Duration {
+    current.saturating_mul(2).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_per_network_and_instance() {
+        assert_eq!(namespace(Network::BaseMainnet, None), "mev");
+        assert_eq!(namespace(Network::BaseSepolia, None), "mev:base-sepolia");
+        assert_eq!(namespace(Network::BaseMainnet, Some("node-2")), "mev:node-2");
+        assert_eq!(namespace(Network::Dev, Some(" ")), "mev:dev");
+
+        let handle = RedisHandle::disconnected(RedisClientConfig {
+            namespace: namespace(Network::BaseSepolia, Some("a")),
+            ..Default::default()
+        });
+        assert_eq!(handle.key("multiplier:Backrun_X"), "mev:base-sepolia:a:multiplier:Backrun_X");
+    }
+
+    #[tokio::test]
+    async fn test_disconnected_commands_fail_fast() {
+        let handle = RedisHandle::disconnected(RedisClientConfig::default());
+        let result = handle.run(|mut conn| async move {
+            redis::AsyncCommands::get::<_, Option<String>>(&mut conn, "k").await
+        }).await;
+        assert!(matches!(result, Err(RedisCommandError::Unavailable)));
+        assert_eq!(handle.health(), RedisHealth::Connecting);
+
+        assert_eq!(next_backoff(Duration::from_secs(20), Duration::from_secs(30)), Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,690 @@
+//! Chain state read from a JSON-RPC node instead of the embedded reth database.
+//!
+//! [`RpcStateSource`] lets the executor, workers and submission run on machines without the
+//! node. Accounts, storage and block hashes are read at a pinned block and cached per block, so
+//! every worker searching the same flashblock shares one set of reads. An account's balance, nonce and code
+//! come back in one batched request, and [`RemoteStateDb::prefetch`] batches whole read sets.
+//! Reads block the calling thread: deployability is paid for in round-trips.
+
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256, U64};
+use alloy_rpc_types_eth::{BlockId, BlockNumberOrTag};
+use moka::sync::Cache;
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use revm::bytecode::Bytecode;
+use revm::database_interface::DBErrorMarker;
+use revm::primitives::KECCAK_EMPTY;
+use revm::state::AccountInfo;
+use revm::{Database, DatabaseRef};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::state_source::{StateBlock, StateSource};
+
+/// Accounts and storage are only read for recent blocks
+const BLOCK_STATE_TTL: Duration = Duration::from_secs(30);
+
+/// Block hashes kept for `BLOCKHASH`
+const MAX_CACHED_BLOCK_HASHES: u64 = 1024;
+
+/// Configuration for reading state from a remote node
+#[derive(Debug, Clone)]
+pub struct RemoteStateConfig {
+    /// HTTP JSON-RPC endpoint of a node on the same chain
+    pub rpc_url: String,
+    pub timeout: Duration,
+    /// Calls per JSON-RPC batch request
+    pub max_batch_size: usize,
+    pub max_cached_accounts: u64,
+    pub max_cached_slots: u64,
+    /// How long the node's best block number is reused before asking again
+    pub head_ttl: Duration,
+}
+
+impl Default for RemoteStateConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: "http://127.0.0.1:8545".to_string(),
+            timeout: Duration::from_secs(2),
+            max_batch_size: 100,
+            max_cached_accounts: 100_000,
+            max_cached_slots: 1_000_000,
+            head_ttl: Duration::from_millis(250),
+        }
+    }
+}
+
+impl RemoteStateConfig {
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self { rpc_url: rpc_url.into(), ..Self::default() }
+    }
+}
+
+/// Failure to read remote state
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteStateError {
+    #[error("remote state request failed: {0}")]
+    Transport(String),
+    #[error("remote state RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    #[error("malformed remote state response: {0}")]
+    Malformed(String),
+    /// Code is only known while an account holding it is cached
+    #[error("code {0} was not read with any account")]
+    UnknownCode(B256),
+}
+
+impl DBErrorMarker for RemoteStateError {}
+
+/// Remote state reads and how many the cache absorbed
+#[derive(Metrics, Clone)]
+#[metrics(scope = "mev.remote_state")]
+pub struct RemoteStateMetrics {
+    /// Reads answered from the local cache
+    pub cache_hits_total: Counter,
+    /// Reads that went to the node
+    pub cache_misses_total: Counter,
+    /// HTTP requests sent, each carrying a batch of calls
+    pub requests_total: Counter,
+    /// JSON-RPC calls sent
+    pub calls_total: Counter,
+    /// Requests that failed or returned an error
+    pub errors_total: Counter,
+    /// HTTP round-trip time
+    pub request_duration_seconds: Histogram,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    id: Option<usize>,
+    #[serde(default)]
+    result: serde_json::Value,
+    error: Option<RpcErrorObject>,
+}
+
+#[derive(Deserialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcBlock {
+    number: U64,
+    hash: B256,
+    timestamp: U64,
+    gas_limit: U64,
+    base_fee_per_gas: Option<U64>,
+}
+
+/// Blocking JSON-RPC client speaking batch requests
+struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+    metrics: RemoteStateMetrics,
+}
+
+impl RpcClient {
+    /// Send calls as one batch request; results come back in call order
+    fn batch(&self, calls: &[(&'static str, serde_json::Value)]) -> Result<Vec<serde_json::Value>, RemoteStateError> {
+        let body = serde_json::Value::Array(
+            calls.iter().enumerate()
+                .map(|(id, (method, params))| serde_json::json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+                .collect(),
+        ).to_string();
+
+        self.metrics.requests_total.increment(1);
+        self.metrics.calls_total.increment(calls.len() as u64);
+        let started = Instant::now();
+        let response = blocking(|| {
+            self.agent.post(&self.url)
+                .set("Content-Type", "application/json")
+                .send_string(&body)
+                .map_err(|e| RemoteStateError::Transport(e.to_string()))?
+                .into_string()
+                .map_err(|e| RemoteStateError::Transport(e.to_string()))
+        });
+        self.metrics.request_duration_seconds.record(started.elapsed().as_secs_f64());
+
+        let result = response.and_then(|text| Self::decode_batch(&text, calls.len()));
+        if result.is_err() {
+            self.metrics.errors_total.increment(1);
+        }
+        result
+    }
+
+    fn decode_batch(text: &str, calls: usize) -> Result<Vec<serde_json::Value>, RemoteStateError> {
+        // Nodes that reject the whole batch answer with a single error object
+        let responses: Vec<RpcResponse> = match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(value @ serde_json::Value::Array(_)) => serde_json::from_value::<Vec<RpcResponse>>(value),
+            Ok(value) => serde_json::from_value::<RpcResponse>(value).map(|response| vec![response]),
+            Err(e) => Err(e),
+        }.map_err(|e| RemoteStateError::Malformed(e.to_string()))?;
+
+        let mut results = vec![None; calls];
+        for response in responses {
+            if let Some(error) = response.error {
+                return Err(RemoteStateError::Rpc { code: error.code, message: error.message });
+            }
+            match response.id.and_then(|id| results.get_mut(id)) {
+                Some(slot) => *slot = Some(response.result),
+                None => return Err(RemoteStateError::Malformed(format!("unexpected response id {:?}", response.id))),
+            }
+        }
+        results.into_iter()
+            .map(|result| result.ok_or_else(|| RemoteStateError::Malformed("missing response in batch".to_string())))
+            .collect()
+    }
+}
+
+/// Run a blocking read without stalling the other tasks of a multi-threaded runtime
+fn blocking<T>(read: impl FnOnce() -> T) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(read)
+        }
+        _ => read(),
+    }
+}
+
+fn decode<T: DeserializeOwned>(value: &serde_json::Value) -> Result<T, RemoteStateError> {
+    serde_json::from_value(value.clone()).map_err(|e| RemoteStateError::Malformed(format!("{}: {}", e, value)))
+}
+
+struct RemoteStateInner {
+    client: RpcClient,
+    max_batch_size: usize,
+    head_ttl: Duration,
+    head: Mutex<Option<(u64, Instant)>>,
+    accounts: Cache<(u64, Address), Option<AccountInfo>>,
+    storage: Cache<(u64, Address, U256), U256>,
+    /// Index over the code held by cached accounts
+    code: Cache<B256, Bytecode>,
+    block_hashes: Cache<u64, B256>,
+}
+
+/// State of a remote node, shared by every block's [`RemoteStateDb`]
+#[derive(Clone)]
+pub struct RpcStateSource {
+    inner: Arc<RemoteStateInner>,
+}
+
+impl std::fmt::Debug for RpcStateSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcStateSource").field("url", &self.inner.client.url).finish()
+    }
+}
+
+impl RpcStateSource {
+    pub fn new(config: &RemoteStateConfig) -> Self {
+        let agent = ureq::AgentBuilder::new().timeout(config.timeout).build();
+        Self {
+            inner: Arc::new(RemoteStateInner {
+                client: RpcClient {
+                    url: config.rpc_url.clone(),
+                    agent,
+                    metrics: RemoteStateMetrics::default(),
+                },
+                // An account read is three calls and must fit one batch
+                max_batch_size: config.max_batch_size.max(3),
+                head_ttl: config.head_ttl,
+                head: Mutex::new(None),
+                accounts: Cache::builder()
+                    .max_capacity(config.max_cached_accounts)
+                    .time_to_live(BLOCK_STATE_TTL)
+                    .build(),
+                storage: Cache::builder()
+                    .max_capacity(config.max_cached_slots)
+                    .time_to_live(BLOCK_STATE_TTL)
+                    .build(),
+                code: Cache::new(config.max_cached_accounts),
+                block_hashes: Cache::new(MAX_CACHED_BLOCK_HASHES),
+            }),
+        }
+    }
+
+    /// State as of a block
+    pub fn state_at(&self, block_number: u64) -> RemoteStateDb {
+        RemoteStateDb { block_number, inner: self.inner.clone() }
+    }
+
+    fn note_head(&self, block_number: u64) {
+        let mut head = self.inner.head.lock();
+        if head.is_none_or(|(number, _)| block_number >= number) {
+            *head = Some((block_number, Instant::now()));
+        }
+    }
+}
+
+impl StateSource for RpcStateSource {
+    type Database = RemoteStateDb;
+
+    fn best_block_number(&self) -> eyre::Result<u64> {
+        if let Some((number, at)) = *self.inner.head.lock() {
+            if at.elapsed() < self.inner.head_ttl {
+                return Ok(number);
+            }
+        }
+        let result = self.inner.client.batch(&[("eth_blockNumber", serde_json::json!([]))])?;
+        let number = decode::<U64>(&result[0])?.to::<u64>();
+        *self.inner.head.lock() = Some((number, Instant::now()));
+        Ok(number)
+    }
+
+    fn block(&self, block_id: BlockId) -> eyre::Result<StateBlock> {
+        let call = match block_id {
+            BlockId::Number(tag) => ("eth_getBlockByNumber", serde_json::json!([tag, false])),
+            BlockId::Hash(hash) => ("eth_getBlockByHash", serde_json::json!([hash.block_hash, false])),
+        };
+        let result = self.inner.client.batch(&[call])?;
+        if result[0].is_null() {
+            return Err(eyre::eyre!("Block not found for {:?}", block_id));
+        }
+        let block: RpcBlock = decode(&result[0])?;
+        let number = block.number.to::<u64>();
+        self.inner.block_hashes.insert(number, block.hash);
+        if matches!(block_id, BlockId::Number(BlockNumberOrTag::Latest)) {
+            // Pin the next state to the block the environment was built from
+            self.note_head(number);
+        }
+        Ok(StateBlock {
+            number,
+            timestamp: block.timestamp.to(),
+            gas_limit: block.gas_limit.to(),
+            base_fee_per_gas: block.base_fee_per_gas.map(|fee| fee.to()),
+        })
+    }
+
+    fn latest_state(&self) -> eyre::Result<Self::Database> {
+        Ok(self.state_at(self.best_block_number()?))
+    }
+
+    fn prefetch(&self, state: &Self::Database, accounts: &[Address]) {
+        // Reads fall back to fetching one at a time
+        if let Err(e) = state.prefetch(accounts, &[]) {
+            debug!(block = state.block_number, accounts = accounts.len(), error = %e, "Remote state prefetch failed");
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Read {
+    Account(Address),
+    Slot(Address, U256),
+}
+
+impl Read {
+    fn calls(&self) -> usize {
+        match self {
+            Self::Account(_) => 3,
+            Self::Slot(..) => 1,
+        }
+    }
+}
+
+enum Fetched {
+    Account(Option<AccountInfo>),
+    Slot(U256),
+}
+
+/// Remote state at one block
+#[derive(Clone)]
+pub struct RemoteStateDb {
+    block_number: u64,
+    inner: Arc<RemoteStateInner>,
+}
+
+impl std::fmt::Debug for RemoteStateDb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteStateDb")
+            .field("url", &self.inner.client.url)
+            .field("block_number", &self.block_number)
+            .finish()
+    }
+}
+
+impl RemoteStateDb {
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Read uncached accounts and storage slots in as few requests as the batch size allows
+    pub fn prefetch(&self, accounts: &[Address], slots: &[(Address, U256)]) -> Result<(), RemoteStateError> {
+        let mut seen_accounts = HashSet::new();
+        let mut seen_slots = HashSet::new();
+        let reads: Vec<Read> = accounts.iter()
+            .filter(|address| seen_accounts.insert(**address))
+            .filter(|address| !self.inner.accounts.contains_key(&(self.block_number, **address)))
+            .map(|address| Read::Account(*address))
+            .chain(slots.iter()
+                .filter(|key| seen_slots.insert(**key))
+                .filter(|(address, slot)| !self.inner.storage.contains_key(&(self.block_number, *address, *slot)))
+                .map(|(address, slot)| Read::Slot(*address, *slot)))
+            .collect();
+
+        let mut batch = Vec::new();
+        let mut calls = 0;
+        for read in reads {
+            if calls + read.calls() > self.inner.max_batch_size {
+                self.fetch(&batch)?;
+                batch.clear();
+                calls = 0;
+            }
+            calls += read.calls();
+            batch.push(read);
+        }
+        if !batch.is_empty() {
+            self.fetch(&batch)?;
+        }
+        Ok(())
+    }
+
+    /// Read from the node in one request and cache the results
+    fn fetch(&self, reads: &[Read]) -> Result<Vec<Fetched>, RemoteStateError> {
+        let block = serde_json::json!(U64::from(self.block_number));
+        let mut calls = Vec::new();
+        for read in reads {
+            match *read {
+                Read::Account(address) => {
+                    calls.push(("eth_getBalance", serde_json::json!([address, block])));
+                    calls.push(("eth_getTransactionCount", serde_json::json!([address, block])));
+                    calls.push(("eth_getCode", serde_json::json!([address, block])));
+                }
+                Read::Slot(address, slot) => {
+                    calls.push(("eth_getStorageAt", serde_json::json!([address, B256::from(slot), block])));
+                }
+            }
+        }
+        self.inner.client.metrics.cache_misses_total.increment(reads.len() as u64);
+        let results = self.inner.client.batch(&calls)?;
+
+        let mut results = results.iter();
+        let mut next = || results.next().ok_or_else(|| RemoteStateError::Malformed("short batch response".to_string()));
+        let mut fetched = Vec::with_capacity(reads.len());
+        for read in reads {
+            match *read {
+                Read::Account(address) => {
+                    let info = self.decode_account(next()?, next()?, next()?)?;
+                    self.inner.accounts.insert((self.block_number, address), info.clone());
+                    fetched.push(Fetched::Account(info));
+                }
+                Read::Slot(address, slot) => {
+                    let value: U256 = decode(next()?)?;
+                    self.inner.storage.insert((self.block_number, address, slot), value);
+                    fetched.push(Fetched::Slot(value));
+                }
+            }
+        }
+        Ok(fetched)
+    }
+
+    fn decode_account(
+        &self,
+        balance: &serde_json::Value,
+        nonce: &serde_json::Value,
+        code: &serde_json::Value,
+    ) -> Result<Option<AccountInfo>, RemoteStateError> {
+        let balance: U256 = decode(balance)?;
+        let nonce = decode::<U64>(nonce)?.to::<u64>();
+        let code: Bytes = decode(code)?;
+
+        if code.is_empty() {
+            // Nodes answer zeroes for accounts that do not exist
+            if balance.is_zero() && nonce == 0 {
+                return Ok(None);
+            }
+            return Ok(Some(AccountInfo::new(balance, nonce, KECCAK_EMPTY, Bytecode::default())));
+        }
+
+        let code_hash = keccak256(&code);
+        let bytecode = Bytecode::new_raw_checked(code)
+            .map_err(|e| RemoteStateError::Malformed(format!("invalid code for {}: {:?}", code_hash, e)))?;
+        self.inner.code.insert(code_hash, bytecode.clone());
+        Ok(Some(AccountInfo::new(balance, nonce, code_hash, bytecode)))
+    }
+}
+
+impl DatabaseRef for RemoteStateDb {
+    type Error = RemoteStateError;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(info) = self.inner.accounts.get(&(self.block_number, address)) {
+            self.inner.client.metrics.cache_hits_total.increment(1);
+            return Ok(info);
+        }
+        match self.fetch(&[Read::Account(address)])?.pop() {
+            Some(Fetched::Account(info)) => Ok(info),
+            _ => Err(RemoteStateError::Malformed("account read returned no account".to_string())),
+        }
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if code_hash == KECCAK_EMPTY {
+            return Ok(Bytecode::default());
+        }
+        if let Some(code) = self.inner.code.get(&code_hash) {
+            return Ok(code);
+        }
+        // Evicted code is still held by every cached account pointing at it
+        let code = self.inner.accounts.iter()
+            .find_map(|(_, info)| info.filter(|info| info.code_hash == code_hash).and_then(|info| info.code))
+            .ok_or(RemoteStateError::UnknownCode(code_hash))?;
+        self.inner.code.insert(code_hash, code.clone());
+        Ok(code)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(value) = self.inner.storage.get(&(self.block_number, address, index)) {
+            self.inner.client.metrics.cache_hits_total.increment(1);
+            return Ok(value);
+        }
+        match self.fetch(&[Read::Slot(address, index)])?.pop() {
+            Some(Fetched::Slot(value)) => Ok(value),
+            _ => Err(RemoteStateError::Malformed("storage read returned no value".to_string())),
+        }
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        if let Some(hash) = self.inner.block_hashes.get(&number) {
+            return Ok(hash);
+        }
+        let result = self.inner.client.batch(&[("eth_getBlockByNumber", serde_json::json!([U64::from(number), false]))])?;
+        if result[0].is_null() {
+            return Err(RemoteStateError::Malformed(format!("block {} not found", number)));
+        }
+        let hash: B256 = decode(&result[0]["hash"])?;
+        self.inner.block_hashes.insert(number, hash);
+        Ok(hash)
+    }
+}
+
+impl Database for RemoteStateDb {
+    type Error = RemoteStateError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+/// JSON-RPC node serving a fixed state, shared by the tests reading remote state
+#[cfg(test)]
+pub(crate) mod mock_node {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read as _, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    pub(crate) const ACCOUNT: Address = Address::repeat_byte(1);
+    pub(crate) const CONTRACT: Address = Address::repeat_byte(2);
+    /// Processor stub: reverts every call with a profit of 1_000_000 wei, which the search
+    /// harness reads as the call's profit
+    pub(crate) const CODE: [u8; 12] = [0x62, 0x0f, 0x42, 0x40, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xfd];
+
+    /// Node at block 100 holding `ACCOUNT`, `CONTRACT` and one storage slot; counts HTTP requests
+    pub(crate) fn spawn_mock_node() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let counter = counter.clone();
+                std::thread::spawn(move || serve(stream, &counter));
+            }
+        });
+        (url, requests)
+    }
+
+    fn serve(stream: TcpStream, requests: &AtomicUsize) {
+        let mut reader = BufReader::new(stream);
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            let mut content_length = 0;
+            loop {
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+                if line.trim_end().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.trim_end().split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).unwrap();
+            requests.fetch_add(1, Ordering::Relaxed);
+
+            let calls: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+            let response = serde_json::Value::Array(calls.iter().map(respond).collect()).to_string();
+            let http = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                response.len(),
+                response
+            );
+            reader.get_mut().write_all(http.as_bytes()).unwrap();
+        }
+    }
+
+    fn respond(call: &serde_json::Value) -> serde_json::Value {
+        let params = &call["params"];
+        let address: Option<Address> = params[0].as_str().and_then(|s| s.parse().ok());
+        let result = match call["method"].as_str().unwrap() {
+            "eth_blockNumber" => serde_json::json!("0x64"),
+            "eth_getBlockByNumber" => {
+                let number = decode::<U64>(&params[0]).unwrap_or(U64::from(100));
+                serde_json::json!({
+                    "number": number,
+                    "hash": B256::with_last_byte(number.to::<u8>()),
+                    "timestamp": "0x10",
+                    "gasLimit": "0x1c9c380",
+                    "baseFeePerGas": "0x7",
+                })
+            }
+            "eth_getBalance" => serde_json::json!(if address == Some(ACCOUNT) { "0x7" } else { "0x0" }),
+            "eth_getTransactionCount" if address == Some(ACCOUNT) => serde_json::json!("0x2"),
+            "eth_getTransactionCount" if address == Some(CONTRACT) => serde_json::json!("0x1"),
+            "eth_getTransactionCount" => serde_json::json!("0x0"),
+            "eth_getCode" => serde_json::json!(Bytes::from(if address == Some(CONTRACT) { CODE.to_vec() } else { vec![] })),
+            "eth_getStorageAt" if params[1] == serde_json::json!(B256::with_last_byte(0xff)) => {
+                return serde_json::json!({ "jsonrpc": "2.0", "id": call["id"], "error": { "code": -32000, "message": "missing trie node" } });
+            }
+            "eth_getStorageAt" => serde_json::json!(B256::from(U256::from(if address == Some(CONTRACT) { 42 } else { 0 }))),
+            method => panic!("unexpected method {method}"),
+        };
+        serde_json::json!({ "jsonrpc": "2.0", "id": call["id"], "result": result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock_node::{spawn_mock_node, ACCOUNT, CODE, CONTRACT};
+    use super::*;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn test_reads_are_batched_and_cached_per_block() {
+        let (url, requests) = spawn_mock_node();
+        let source = RpcStateSource::new(&RemoteStateConfig::new(url));
+
+        // The head is reused within its TTL
+        let state = source.latest_state().unwrap();
+        assert_eq!(state.block_number(), 100);
+        assert_eq!(source.best_block_number().unwrap(), 100);
+        assert_eq!(requests.load(Ordering::Relaxed), 1);
+
+        // Both accounts and the slot in one request, duplicates dropped
+        state.prefetch(&[ACCOUNT, CONTRACT, ACCOUNT], &[(CONTRACT, U256::from(1))]).unwrap();
+        assert_eq!(requests.load(Ordering::Relaxed), 2);
+
+        let account = state.basic_ref(ACCOUNT).unwrap().unwrap();
+        assert_eq!((account.balance, account.nonce, account.code_hash), (U256::from(7), 2, KECCAK_EMPTY));
+        let contract = state.basic_ref(CONTRACT).unwrap().unwrap();
+        assert_eq!(contract.code_hash, keccak256(CODE));
+        assert_eq!(state.code_by_hash_ref(contract.code_hash).unwrap().original_bytes(), Bytes::from(CODE.to_vec()));
+        assert_eq!(state.storage_ref(CONTRACT, U256::from(1)).unwrap(), U256::from(42));
+        assert_eq!(requests.load(Ordering::Relaxed), 2);
+
+        // Empty accounts read as absent; another block's state is read again
+        assert_eq!(state.basic_ref(Address::repeat_byte(9)).unwrap(), None);
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+        assert!(source.state_at(101).basic_ref(ACCOUNT).unwrap().is_some());
+        assert_eq!(requests.load(Ordering::Relaxed), 4);
+
+        // Batches split at the configured size; an account read is never split
+        let small = RpcStateSource::new(&RemoteStateConfig { max_batch_size: 4, ..RemoteStateConfig::new(source.inner.client.url.clone()) });
+        small.state_at(100).prefetch(&[ACCOUNT, CONTRACT], &[(CONTRACT, U256::from(1))]).unwrap();
+        assert_eq!(requests.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    fn test_block_environment_and_rpc_errors() {
+        let (url, _) = spawn_mock_node();
+        let source = RpcStateSource::new(&RemoteStateConfig::new(url));
+
+        let block = source.block(BlockId::latest()).unwrap();
+        assert_eq!(block, StateBlock { number: 100, timestamp: 16, gas_limit: 30_000_000, base_fee_per_gas: Some(7) });
+        assert_eq!(source.state_at(100).block_hash_ref(100).unwrap(), B256::with_last_byte(100));
+
+        let error = source.state_at(100).storage_ref(CONTRACT, U256::from(0xff)).unwrap_err();
+        assert!(matches!(error, RemoteStateError::Rpc { code: -32000, .. }));
+        assert!(matches!(source.state_at(100).code_by_hash_ref(B256::with_last_byte(1)), Err(RemoteStateError::UnknownCode(_))));
+    }
+
+    #[test]
+    fn test_evicted_code_is_served_from_cached_accounts() {
+        let (url, requests) = spawn_mock_node();
+        let source = RpcStateSource::new(&RemoteStateConfig::new(url));
+        let state = source.state_at(100);
+
+        let code_hash = state.basic_ref(CONTRACT).unwrap().unwrap().code_hash;
+        source.inner.code.invalidate(&code_hash);
+        assert_eq!(state.code_by_hash_ref(code_hash).unwrap().original_bytes(), Bytes::from(CODE.to_vec()));
+        assert!(source.inner.code.contains_key(&code_hash));
+        assert_eq!(requests.load(Ordering::Relaxed), 1);
+    }
+}
@@ -1,9 +1,8 @@
-use alloy_consensus::{TxEnvelope, Transaction as _, transaction::SignerRecoverable, BlockHeader};
+use alloy_consensus::{TxEnvelope, Transaction as _, transaction::SignerRecoverable};
 use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::TxKind;
 use alloy_rpc_types_eth::{BlockId, EthCallResponse};
-use reth_provider::{StateProvider, StateProviderFactory};
-use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+use reth_revm::db::CacheDB;
 use reth_optimism_evm::OpEvmConfig;
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_node::OpRethReceiptBuilder;
@@ -17,25 +16,30 @@ use op_revm::OpTransaction;
 use std::sync::Arc;
 use crate::flashblocks::FlashblocksEvent;
 use crate::flashblock_state::FlashblockStateSnapshot;
+use crate::state_source::StateSource;
 
 /// A flashblock executor that uses revm directly with CacheDB for optimal performance
-pub struct RevmFlashblockExecutor {
+pub struct RevmFlashblockExecutor<S: StateSource> {
     /// The chain specification
     #[allow(dead_code)]
     chain_spec: Arc<OpChainSpec>,
     /// The EVM configuration
     evm_config: OpEvmConfig,
     /// The cached database that persists state across flashblock simulations
-    cache_db: Option<CacheDB<StateProviderDatabase<Box<dyn StateProvider>>>>,
+    cache_db: Option<CacheDB<S::Database>>,
+    /// Where the cached database reads state it has not seen yet
+    source: Option<S>,
     /// The current EVM environment
     evm_env: Option<reth_evm::EvmEnv<op_revm::OpSpecId>>,
     /// Current block number being processed
     current_block: Option<u64>,
     /// Base fee for current block
     current_base_fee: u128,
+    /// Accounts touched by each transaction of the last executed flashblock (in order)
+    last_touched_accounts: Vec<Vec<alloy_primitives::Address>>,
 }
 
-impl RevmFlashblockExecutor {
+impl<S: StateSource> RevmFlashblockExecutor<S> {
     /// Create a new executor for a specific block
     pub fn new(chain_spec: Arc<OpChainSpec>) -> Self {
         let evm_config = OpEvmConfig::new(
@@ -47,46 +51,25 @@ impl RevmFlashblockExecutor {
             chain_spec,
             evm_config,
             cache_db: None,
+            source: None,
             evm_env: None,
             current_block: None,
             current_base_fee: 0,
+            last_touched_accounts: Vec::new(),
         }
     }
     
-    /// Initialize the executor with a state provider and block context
-    pub async fn initialize<P>(&mut self, provider: P, block_id: BlockId) -> eyre::Result<()> 
-    where 
-        P: StateProviderFactory + reth_provider::HeaderProvider + reth_provider::BlockReader,
-        P::Header: alloy_consensus::BlockHeader,
-    {
-        // Get the latest state provider
-        let state_provider = provider.latest()?;
-        
+    /// Initialize the executor with a state source and block context
+    pub async fn initialize(&mut self, source: S, block_id: BlockId) -> eyre::Result<()> {
         // Get the block number and header
-        let (block_number, header) = match block_id {
-            BlockId::Number(alloy_rpc_types_eth::BlockNumberOrTag::Latest) => {
-                let number = provider.best_block_number()?;
-                // println!("   🔍 Best block number from provider: {}", number);
-                let header = provider.header_by_number(number)?
-                    .ok_or_else(|| eyre::eyre!("Header not found for block {}", number))?;
-                (number, header)
-            }
-            BlockId::Number(alloy_rpc_types_eth::BlockNumberOrTag::Number(num)) => {
-                let header = provider.header_by_number(num)?
-                    .ok_or_else(|| eyre::eyre!("Header not found for block {}", num))?;
-                (num, header)
-            }
-            BlockId::Hash(hash) => {
-                let header = provider.header(&hash.block_hash)?
-                    .ok_or_else(|| eyre::eyre!("Header not found for hash {:?}", hash))?;
-                let number = header.number();
-                (number, header)
-            }
-            _ => return Err(eyre::eyre!("Unsupported block ID: {:?}", block_id)),
-        };
+        let header = source.block(block_id)?;
+        let block_number = header.number;
+        
+        // Get the latest state
+        let state = source.latest_state()?;
         
         // Create a basic header for the EVM environment (since we can't use generic header directly)
-        let base_fee = header.base_fee_per_gas();
+        let base_fee = header.base_fee_per_gas;
         // println!("   🔍 Fetched header for block {}:", header.number());
         // println!("      - Timestamp: {}", header.timestamp());
         // println!("      - Gas limit: {}", header.gas_limit());
@@ -98,15 +81,16 @@ impl RevmFlashblockExecutor {
         }
         
         let evm_header = alloy_consensus::Header {
-            number: header.number(),
-            timestamp: header.timestamp(),
-            gas_limit: header.gas_limit(),
+            number: header.number,
+            timestamp: header.timestamp,
+            gas_limit: header.gas_limit,
             base_fee_per_gas: base_fee,
             ..Default::default()
         };
         
-        // Create the CacheDB with the state provider
-        self.cache_db = Some(CacheDB::new(StateProviderDatabase::new(state_provider)));
+        // Create the CacheDB with the state
+        self.cache_db = Some(CacheDB::new(state));
+        self.source = Some(source);
         
         // Set up the EVM environment using the header
         self.evm_env = Some(self.evm_config.evm_env(&evm_header));
@@ -140,6 +124,15 @@ impl RevmFlashblockExecutor {
             })
             .collect::<Result<Vec<_>, _>>()?;
             
+        // Remote sources read every sender and recipient of the flashblock in one batch
+        if let (Some(source), Some(cache_db)) = (&self.source, &self.cache_db) {
+            let accounts: Vec<_> = converted_txs.iter()
+                .flat_map(|(tx_env, _)| std::iter::once(tx_env.caller).chain(tx_env.kind.to().copied()))
+                .filter(|address| !cache_db.cache.accounts.contains_key(address))
+                .collect();
+            source.prefetch(&cache_db.db, &accounts);
+        }
+        
         let cache_db = self.cache_db.as_mut()
             .ok_or_else(|| eyre::eyre!("Executor not initialized. Call initialize() first."))?;
         
@@ -150,6 +143,7 @@ impl RevmFlashblockExecutor {
         let start = std::time::Instant::now();
         
         let mut results = Vec::new();
+        let mut touched_accounts = Vec::with_capacity(converted_txs.len());
         
         // Process each transaction in the flashblock
         for (i, (tx_env, _tx_hash)) in converted_txs.into_iter().enumerate() {
@@ -204,6 +198,14 @@ impl RevmFlashblockExecutor {
                         }
                     };
                     
+                    // Record which accounts this transaction touched (used for competitor analysis)
+                    touched_accounts.push(
+                        exec_result.state.iter()
+                            .filter(|(_, account)| account.is_touched())
+                            .map(|(address, _)| *address)
+                            .collect()
+                    );
+                    
                     // Commit state changes if successful
                     if response.error.is_none() {
                         cache_db.commit(exec_result.state);
@@ -211,15 +213,20 @@ impl RevmFlashblockExecutor {
                     
                     response
                 }
-                Err(ref e) => EthCallResponse {
-                    value: None,
-                    error: Some(format!("EVM error: {:?}", e)),
-                    gas_used: None,
-                },
+                Err(ref e) => {
+                    touched_accounts.push(Vec::new());
+                    EthCallResponse {
+                        value: None,
+                        error: Some(format!("EVM error: {:?}", e)),
+                        gas_used: None,
+                    }
+                }
             };
             results.push(response);
         }
         
+        self.last_touched_accounts = touched_accounts;
+        
         let elapsed = start.elapsed();
         let successful = results.iter().filter(|r| r.error.is_none()).count();
         let failed = results.len() - successful;
@@ -233,63 +240,14 @@ impl RevmFlashblockExecutor {
         Ok(results)
     }
     
+    /// Accounts touched by each transaction of the last executed flashblock
+    pub fn last_touched_accounts(&self) -> &[Vec<alloy_primitives::Address>] {
+        &self.last_touched_accounts
+    }
+    
     /// Convert a transaction envelope to revm TxEnv
     fn convert_to_tx_env(&self, tx: &TxEnvelope) -> eyre::Result<TxEnv> {
-        let mut tx_env = TxEnv::default();
-        
-        // Set common fields
-        tx_env.caller = tx.recover_signer()
-            .map_err(|_| eyre::eyre!("Failed to recover transaction signer"))?;
-        tx_env.gas_limit = tx.gas_limit();
-        tx_env.value = tx.value();
-        tx_env.data = tx.input().clone();
-        tx_env.nonce = tx.nonce();
-        
-        // Set the destination
-        tx_env.kind = match tx.to() {
-            Some(to) => TxKind::Call(to),
-            None => TxKind::Create,
-        };
-        
-        // Set gas price based on transaction type
-        match tx {
-            TxEnvelope::Legacy(tx) => {
-                tx_env.gas_price = tx.gas_price().unwrap_or_default();
-            }
-            TxEnvelope::Eip2930(tx) => {
-                tx_env.gas_price = tx.gas_price().unwrap_or_default();
-                // Access list would be set here if TxEnv supported it
-            }
-            TxEnvelope::Eip1559(tx) => {
-                tx_env.gas_priority_fee = tx.max_priority_fee_per_gas();
-                tx_env.gas_price = tx.max_fee_per_gas();
-                // Access list would be set here if TxEnv supported it
-            }
-            TxEnvelope::Eip4844(tx) => {
-                // EIP-4844 blob transactions (used for data availability)
-                // Extract the actual transaction from the variant
-                match tx.tx() {
-                    alloy_consensus::TxEip4844Variant::TxEip4844(inner_tx) => {
-                        tx_env.gas_priority_fee = inner_tx.max_priority_fee_per_gas();
-                        tx_env.gas_price = inner_tx.max_fee_per_gas();
-                        // Blob transactions have blob_hashes but we don't need them for MEV simulation
-                    }
-                    alloy_consensus::TxEip4844Variant::TxEip4844WithSidecar(inner_tx) => {
-                        tx_env.gas_priority_fee = inner_tx.tx().max_priority_fee_per_gas();
-                        tx_env.gas_price = inner_tx.tx().max_fee_per_gas();
-                        // Sidecar contains the actual blob data, not needed for MEV
-                    }
-                }
-            }
-            TxEnvelope::Eip7702(tx) => {
-                // EIP-7702 is for account abstraction/delegation transactions
-                tx_env.gas_priority_fee = tx.max_priority_fee_per_gas();
-                tx_env.gas_price = tx.max_fee_per_gas();
-                // Authority list would be handled here if needed
-            }
-        }
-        
-        Ok(tx_env)
+        tx_env_from_envelope(tx)
     }
     
     /// Convert a transaction request (unsigned) to revm TxEnv
@@ -300,7 +258,7 @@ impl RevmFlashblockExecutor {
         value: alloy_primitives::U256,
         input: alloy_primitives::Bytes,
         gas_limit: u64,
-        gas_price: alloy_primitives::U256,
+        fees: crate::mev_bundle_types::FeeQuote,
         nonce: u64,
     ) -> TxEnv {
         let mut tx_env = TxEnv::default();
@@ -317,9 +275,10 @@ impl RevmFlashblockExecutor {
             None => TxKind::Create,
         };
         
-        // For simplicity, assume EIP-1559 style with gas_price as both max fee and priority fee
-        tx_env.gas_price = gas_price.try_into().unwrap_or(u128::MAX);
-        tx_env.gas_priority_fee = Some(gas_price.try_into().unwrap_or(u128::MAX));
+        // EIP-1559 fees, as the leg will be submitted
+        tx_env.tx_type = 2;
+        tx_env.gas_price = fees.max_fee_per_gas;
+        tx_env.gas_priority_fee = Some(fees.max_priority_fee_per_gas);
         
         tx_env
     }
@@ -392,9 +351,9 @@ impl RevmFlashblockExecutor {
                             let enveloped_bytes = signed_tx.encoded_2718();
                             Ok((tx_env, *tx_hash, Some(alloy_primitives::Bytes::from(enveloped_bytes))))
                         }
-                        BundleTransaction::Unsigned { from, to, value, input, gas_limit, gas_price, nonce } => {
+                        BundleTransaction::Unsigned { from, to, value, input, gas_limit, fees, nonce } => {
                             let tx_env = self.convert_unsigned_tx_to_env(
-                                *from, *to, *value, input.clone(), *gas_limit, *gas_price, *nonce
+                                *from, *to, *value, input.clone(), *gas_limit, *fees, *nonce
                             );
                             // Use zero hash for unsigned transactions
                             Ok((tx_env, alloy_primitives::B256::ZERO, None))
@@ -498,6 +457,7 @@ impl RevmFlashblockExecutor {
     
     /// Simulate a bundle of signed transactions on top of the current flashblock state
     /// This is a convenience method for bundles containing only signed transactions
+    #[allow(dead_code)]
     pub async fn simulate_bundle(
         &mut self,
         bundle_txs: Vec<TxEnvelope>,
@@ -549,6 +509,65 @@ impl RevmFlashblockExecutor {
     }
 }
 
+/// Convert a signed transaction envelope to revm TxEnv
+pub fn tx_env_from_envelope(tx: &TxEnvelope) -> eyre::Result<TxEnv> {
+    let mut tx_env = TxEnv::default();
+    
+    // Set common fields
+    tx_env.caller = tx.recover_signer()
+        .map_err(|_| eyre::eyre!("Failed to recover transaction signer"))?;
+    tx_env.gas_limit = tx.gas_limit();
+    tx_env.value = tx.value();
+    tx_env.data = tx.input().clone();
+    tx_env.nonce = tx.nonce();
+    
+    // Set the destination
+    tx_env.kind = match tx.to() {
+        Some(to) => TxKind::Call(to),
+        None => TxKind::Create,
+    };
+    
+    // Set gas price based on transaction type
+    match tx {
+        TxEnvelope::Legacy(tx) => {
+            tx_env.gas_price = tx.gas_price().unwrap_or_default();
+        }
+        TxEnvelope::Eip2930(tx) => {
+            tx_env.gas_price = tx.gas_price().unwrap_or_default();
+            // Access list would be set here if TxEnv supported it
+        }
+        TxEnvelope::Eip1559(tx) => {
+            tx_env.gas_priority_fee = tx.max_priority_fee_per_gas();
+            tx_env.gas_price = tx.max_fee_per_gas();
+            // Access list would be set here if TxEnv supported it
+        }
+        TxEnvelope::Eip4844(tx) => {
+            // EIP-4844 blob transactions (used for data availability)
+            // Extract the actual transaction from the variant
+            match tx.tx() {
+                alloy_consensus::TxEip4844Variant::TxEip4844(inner_tx) => {
+                    tx_env.gas_priority_fee = inner_tx.max_priority_fee_per_gas();
+                    tx_env.gas_price = inner_tx.max_fee_per_gas();
+                    // Blob transactions have blob_hashes but we don't need them for MEV simulation
+                }
+                alloy_consensus::TxEip4844Variant::TxEip4844WithSidecar(inner_tx) => {
+                    tx_env.gas_priority_fee = inner_tx.tx().max_priority_fee_per_gas();
+                    tx_env.gas_price = inner_tx.tx().max_fee_per_gas();
+                    // Sidecar contains the actual blob data, not needed for MEV
+                }
+            }
+        }
+        TxEnvelope::Eip7702(tx) => {
+            // EIP-7702 is for account abstraction/delegation transactions
+            tx_env.gas_priority_fee = tx.max_priority_fee_per_gas();
+            tx_env.gas_price = tx.max_fee_per_gas();
+            // Authority list would be handled here if needed
+        }
+    }
+    
+    Ok(tx_env)
+}
+
 // This is a complete implementation that:
 // 1. Uses the OpEvmConfig to create EVMs compatible with Optimism
 // 2. Maintains state in CacheDB across flashblock executions
@@ -0,0 +1,196 @@
+//! Services the MEV pipeline runs with, built once from the configuration.
+//!
+//! The node binary and the standalone `mevbase-remote` runner build the same [`MevRuntime`];
+//! they differ only in the [`StateSource`] handed to [`MevRuntime::start_pipeline`] and in what
+//! triggers the shutdown pipeline.
+
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::config::{redact_url, MevConfig};
+use crate::database_service::DatabaseService;
+use crate::fee_multiplier_controller::FeeMultiplierController;
+use crate::flashblocks::FlashblocksClient;
+use crate::gas_history_store::{GasHistoryBackendKind, GasHistoryStore};
+use crate::gas_limit_estimator::GasLimitEstimator;
+use crate::network::NetworkContext;
+use crate::pipeline::{MevPipeline, MevPipelineHandle, MevServices, PipelineConfig, TransactionServiceSink};
+use crate::redis_client::RedisHandle;
+use crate::sequencer_service::SequencerService;
+use crate::shutdown::ShutdownPipeline;
+use crate::state_source::StateSource;
+use crate::transaction_service::TransactionService;
+use crate::wallet_service::WalletService;
+
+/// Connected flashblock feed and the services built from a [`MevConfig`]
+pub struct MevRuntime {
+    pub network: NetworkContext,
+    pub transaction_service: Arc<TransactionService>,
+    pub wallet_service: Arc<WalletService>,
+    pub services: MevServices,
+    flashblocks: FlashblocksClient,
+}
+
+impl MevRuntime {
+    /// Connect the flashblock feed, Redis, Postgres and the sequencer, and build the services
+    ///
+    /// The wallet section is moved out of `config` so its keys are wiped once parsed.
+    pub async fn build(config: &mut MevConfig, network: NetworkContext) -> eyre::Result<Self> {
+        let mut flashblocks = FlashblocksClient::new(network.flashblocks_url.clone(), config.node.flashblocks_buffer);
+        flashblocks.start().await?;
+        info!(url = %redact_url(&network.flashblocks_url), "Flashblocks client connected");
+
+        // Initialize database service for transaction logging
+        let database = match DatabaseService::new(config.postgres.clone()).await {
+            Ok(service) => {
+                info!("Database service initialized for transaction logging");
+                Some(Arc::new(service))
+            }
+            Err(e) => {
+                warn!("Failed to initialize database service: {}. Transaction logging disabled.", e);
+                None
+            }
+        };
+
+        let wallet_service = match WalletService::from_config(std::mem::take(&mut config.wallet)) {
+            Ok(service) => {
+                info!("Wallet service initialized with {} wallets", service.wallet_count());
+                Arc::new(service)
+            }
+            Err(e) => {
+                warn!("Failed to initialize wallet service: {}. Transaction submission disabled.", e);
+                // Create empty wallet service
+                Arc::new(WalletService::from_signers(vec![]))
+            }
+        };
+
+        // One self-healing Redis handle shared by every service; keys are scoped per network
+        let redis = RedisHandle::connect(config.redis_client_config(&network));
+        info!(
+            host = %config.redis.host,
+            port = config.redis.port,
+            namespace = %redis.namespace(),
+            "Redis client started"
+        );
+
+        let sequencer_service = match SequencerService::new(config.sequencer_config(&network)) {
+            Ok(service) => {
+                info!("Sequencer service initialized");
+                Arc::new(service)
+            }
+            Err(e) => {
+                error!("Failed to initialize sequencer service: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        // Learned per-target gas and bounds, on the configured backend
+        let gas_history = Arc::new(GasHistoryStore::from_config(
+            config.gas_history.backend_kind().unwrap_or(GasHistoryBackendKind::Redis),
+            &config.gas_history.path,
+            std::time::Duration::from_secs(config.gas_history.ttl_secs),
+            redis.clone(),
+        )?);
+
+        // Closed-loop fee multiplier tuning shares the local Redis instance
+        let fee_controller_config = config.fee_controller_config();
+        let fee_controller = if fee_controller_config.enabled {
+            info!(
+                min = fee_controller_config.min_multiplier,
+                max = fee_controller_config.max_multiplier,
+                "Fee multiplier auto-tuning enabled"
+            );
+            Some(Arc::new(FeeMultiplierController::new(fee_controller_config, redis.clone())))
+        } else {
+            None
+        };
+
+        let tx_config = config.transaction_service_config(&network);
+
+        let gas_estimator_config = config.gas_estimator_config();
+        info!(
+            enabled = gas_estimator_config.enabled,
+            default_margin_bps = gas_estimator_config.default_margin_bps,
+            min_margin_bps = gas_estimator_config.min_margin_bps,
+            max_margin_bps = gas_estimator_config.max_margin_bps,
+            "Gas limit estimator configured"
+        );
+        let gas_limit_estimator = Arc::new(GasLimitEstimator::new(gas_estimator_config));
+
+        let transaction_service = Arc::new(TransactionService::new(
+            tx_config.clone(),
+            wallet_service.clone(),
+            sequencer_service,
+            fee_controller.clone(),
+            gas_limit_estimator,
+            redis,
+            network.chain_spec.clone(),
+        ));
+        info!(
+            enabled = tx_config.enabled,
+            dry_run = tx_config.dry_run,
+            wallet_strategy = ?tx_config.wallet_strategy,
+            "Transaction service initialized"
+        );
+
+        let min_profit_threshold = config.processing.min_profit_threshold();
+        info!(
+            threshold_wei = %min_profit_threshold,
+            threshold_eth = format!("{:.6}", min_profit_threshold.as_limbs()[0] as f64 / 1e18),
+            "MEV profit threshold configured"
+        );
+
+        Ok(Self {
+            network,
+            transaction_service,
+            wallet_service,
+            services: MevServices { gas_history, fee_controller, database },
+            flashblocks,
+        })
+    }
+
+    /// Reconcile the previous run's submissions, then start the pipeline, both over one state source
+    pub fn start_pipeline<S: StateSource>(&self, state: S, config: &MevConfig) -> eyre::Result<MevPipelineHandle> {
+        // Pick up submissions the previous run left pending at shutdown
+        if let Err(e) = self.transaction_service.reconcile_pending_state(&config.shutdown.pending_state_path, &state) {
+            warn!(
+                path = %config.shutdown.pending_state_path.display(),
+                error = ?e,
+                "Failed to reconcile pending submissions from previous run"
+            );
+        }
+
+        let sink = TransactionServiceSink::new(
+            self.transaction_service.clone(),
+            self.wallet_service.clone(),
+            state.clone(),
+        );
+        let pipeline = MevPipeline::builder(self.network.chain_spec.clone(), state)
+            .address_book(self.network.address_book.clone())
+            .config(PipelineConfig::from_config(config))
+            .flashblock_source(self.flashblocks.subscribe())
+            .submission_sink(sink)
+            .wallets(self.transaction_service.clone())
+            .services(self.services.clone())
+            .build()?
+            .spawn();
+        info!(
+            max_concurrent_opportunities = config.processing.max_concurrent_opportunities,
+            max_block_staleness = config.processing.max_block_staleness,
+            "MEV pipeline started"
+        );
+        Ok(pipeline)
+    }
+
+    /// Drain and persist steps for a running pipeline
+    pub fn shutdown_pipeline(&self, pipeline: &MevPipelineHandle, config: &MevConfig) -> ShutdownPipeline {
+        ShutdownPipeline {
+            shutdown: pipeline.shutdown().clone(),
+            drain_timeout: config.shutdown.drain_timeout(),
+            pending_state_path: config.shutdown.pending_state_path.clone(),
+            transaction_service: self.transaction_service.clone(),
+            gas_history: self.services.gas_history.clone(),
+            database: self.services.database.clone(),
+        }
+    }
+}
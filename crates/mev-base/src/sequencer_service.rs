@@ -1,13 +1,31 @@
-use alloy_primitives::{B256, keccak256};
+use alloy_primitives::{Address, Bytes, B256, U256, keccak256};
+use dashmap::DashMap;
 use eyre::Result;
+use futures::stream::{FuturesUnordered, StreamExt};
+use parking_lot::Mutex;
 use reqwest::{Client, ClientBuilder};
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
-use redis::aio::ConnectionManager;
-use redis::Client as RedisClient;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+
+use crate::flashblocks::FlashblocksEvent;
+use crate::submission_error::{SubmissionError, SubmissionPolicy};
+
+/// How long a first acknowledgement is kept around waiting for inclusion
+const INCLUSION_ATTRIBUTION_WINDOW: Duration = Duration::from_secs(30);
+
+/// Weight of the newest sample in the per-endpoint latency average
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// Pre-serialized keepalive/health request
+const BLOCK_NUMBER_REQUEST: &str = r#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}"#;
 
 /// Response from the sequencer
 #[derive(Debug, Deserialize, Serialize)]
@@ -26,128 +44,367 @@ pub struct SequencerError {
     pub message: String,
 }
 
+/// Storage slots (and the values we simulated against) per account
+pub type KnownAccounts = BTreeMap<Address, BTreeMap<U256, U256>>;
+
+/// Preconditions for `eth_sendRawTransactionConditional`
+///
+/// The sequencer drops the transaction unless every known slot still holds the given
+/// value and the block number/timestamp are within the bounds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionConditional {
+    pub known_accounts: KnownAccounts,
+    pub block_number_min: Option<u64>,
+    pub block_number_max: Option<u64>,
+    pub timestamp_min: Option<u64>,
+    pub timestamp_max: Option<u64>,
+}
+
+impl TransactionConditional {
+    /// JSON-RPC representation of the conditional options
+    pub fn to_json(&self) -> serde_json::Value {
+        let known_accounts: serde_json::Map<String, serde_json::Value> = self.known_accounts.iter()
+            .map(|(address, slots)| {
+                let slots: serde_json::Map<String, serde_json::Value> = slots.iter()
+                    .map(|(slot, value)| (
+                        format!("{:#x}", B256::from(*slot)),
+                        serde_json::Value::String(format!("{:#x}", B256::from(*value))),
+                    ))
+                    .collect();
+                (format!("{:#x}", address), serde_json::Value::Object(slots))
+            })
+            .collect();
+
+        let mut options = serde_json::Map::new();
+        options.insert("knownAccounts".to_string(), serde_json::Value::Object(known_accounts));
+        for (key, bound) in [
+            ("blockNumberMin", self.block_number_min),
+            ("blockNumberMax", self.block_number_max),
+            ("timestampMin", self.timestamp_min),
+            ("timestampMax", self.timestamp_max),
+        ] {
+            if let Some(bound) = bound {
+                options.insert(key.to_string(), serde_json::Value::String(format!("0x{:x}", bound)));
+            }
+        }
+        serde_json::Value::Object(options)
+    }
+}
+
+/// Per-endpoint submission metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "mev.submission")]
+pub struct SubmissionEndpointMetrics {
+    /// Transactions sent to this endpoint
+    pub requests_total: Counter,
+    /// Successful acknowledgements from this endpoint
+    pub acks_total: Counter,
+    /// Acknowledgements that were the first across all endpoints
+    pub first_acks_total: Counter,
+    /// First-acknowledged transactions that later landed in a flashblock
+    pub inclusions_total: Counter,
+    /// Rejections for a nonce below the account nonce
+    pub nonce_too_low_total: Counter,
+    /// Rejections for a nonce above the account nonce
+    pub nonce_gap_total: Counter,
+    /// Rejections for fees below the endpoint's minimum
+    pub underpriced_total: Counter,
+    /// Rejections of a replacement without a sufficient fee bump
+    pub replacement_underpriced_total: Counter,
+    /// Transactions the endpoint had already seen
+    pub already_known_total: Counter,
+    /// Rejections for insufficient sender balance
+    pub insufficient_funds_total: Counter,
+    /// Rate-limited requests
+    pub rate_limited_total: Counter,
+    /// Requests that timed out
+    pub timeouts_total: Counter,
+    /// Connection and response decoding failures
+    pub transport_errors_total: Counter,
+    /// Other non-2xx HTTP responses
+    pub http_errors_total: Counter,
+    /// Other JSON-RPC error responses
+    pub rpc_errors_total: Counter,
+    /// Round-trip latency of submissions
+    pub latency_seconds: Histogram,
+    /// Round-trip latency of keepalive pings
+    pub keepalive_latency_seconds: Histogram,
+    /// Keepalive pings that failed
+    pub keepalive_failures_total: Counter,
+    /// 1 while the endpoint is demoted, 0 otherwise
+    pub demoted: Gauge,
+}
+
+impl SubmissionEndpointMetrics {
+    fn record_error(&self, error: &SubmissionError) {
+        let counter = match error {
+            SubmissionError::NonceTooLow(_) => &self.nonce_too_low_total,
+            SubmissionError::NonceGap(_) => &self.nonce_gap_total,
+            SubmissionError::Underpriced(_) => &self.underpriced_total,
+            SubmissionError::ReplacementUnderpriced(_) => &self.replacement_underpriced_total,
+            SubmissionError::AlreadyKnown => &self.already_known_total,
+            SubmissionError::InsufficientFunds(_) => &self.insufficient_funds_total,
+            SubmissionError::RateLimited(_) => &self.rate_limited_total,
+            SubmissionError::Timeout(_) => &self.timeouts_total,
+            SubmissionError::Transport(_) => &self.transport_errors_total,
+            SubmissionError::Http { .. } => &self.http_errors_total,
+            SubmissionError::Rpc { .. } => &self.rpc_errors_total,
+        };
+        counter.increment(1);
+    }
+}
+
+/// A transaction submission endpoint (sequencer, builder RPC, own node)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionEndpoint {
+    pub name: String,
+    pub url: String,
+}
+
 /// Configuration for the sequencer service
 #[derive(Debug, Clone)]
 pub struct SequencerConfig {
     pub url: String,
+    /// Additional fan-out endpoints; when empty only `url` is used
+    pub endpoints: Vec<SubmissionEndpoint>,
     pub timeout: Duration,
-    pub redis_host: String,
-    pub redis_port: u16,
-    pub redis_password: String,
-    pub redis_channel: String,
+    /// Interval between keepalive pings on every endpoint; zero disables them
+    pub keepalive_interval: Duration,
+    /// Consecutive errors before an endpoint is demoted
+    pub demote_after_errors: u32,
+    /// Average latency above which an endpoint is demoted
+    pub demote_latency: Duration,
+    /// How long a demoted endpoint is skipped
+    pub demote_duration: Duration,
 }
 
 impl Default for SequencerConfig {
     fn default() -> Self {
         Self {
             url: "https://mainnet-sequencer.base.org/".to_string(),
+            endpoints: Vec::new(),
             timeout: Duration::from_secs(5),
-            redis_host: "localhost".to_string(),
-            redis_port: 6379,
-            redis_password: String::new(),
-            redis_channel: "baseTransactionBroadcast".to_string(),
+            keepalive_interval: Duration::from_secs(15),
+            demote_after_errors: 5,
+            demote_latency: Duration::from_millis(1000),
+            demote_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl SequencerConfig {
+    /// All endpoints transactions are fanned out to, primary sequencer first
+    pub fn submission_endpoints(&self) -> Vec<SubmissionEndpoint> {
+        let mut endpoints = vec![SubmissionEndpoint {
+            name: "sequencer".to_string(),
+            url: self.url.clone(),
+        }];
+        for endpoint in &self.endpoints {
+            if !endpoints.iter().any(|e| e.url == endpoint.url) {
+                endpoints.push(endpoint.clone());
+            }
+        }
+        endpoints
+    }
+}
+
+/// Parse `name=url,name=url` into submission endpoints; bare URLs are named by position
+pub fn parse_endpoints(value: &str) -> Vec<SubmissionEndpoint> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .enumerate()
+        .map(|(i, entry)| match entry.split_once('=') {
+            Some((name, url)) => SubmissionEndpoint { name: name.trim().to_string(), url: url.trim().to_string() },
+            None => SubmissionEndpoint { name: format!("endpoint{}", i), url: entry.to_string() },
+        })
+        .collect()
+}
+
+/// Rolling health of an endpoint, used for demotion
+#[derive(Debug, Default)]
+struct EndpointHealth {
+    consecutive_errors: u32,
+    latency_ewma_ms: Option<f64>,
+    demoted_until: Option<Instant>,
+}
+
+/// An endpoint together with its metrics and health
+struct EndpointState {
+    endpoint: SubmissionEndpoint,
+    metrics: SubmissionEndpointMetrics,
+    health: Mutex<EndpointHealth>,
+    /// Cleared the first time the endpoint rejects `eth_sendRawTransactionConditional`
+    supports_conditional: AtomicBool,
+}
+
+impl EndpointState {
+    fn is_demoted(&self, now: Instant) -> bool {
+        let mut health = self.health.lock();
+        match health.demoted_until {
+            Some(until) if until > now => true,
+            Some(_) => {
+                health.demoted_until = None;
+                self.metrics.demoted.set(0.0);
+                info!(endpoint = %self.endpoint.name, "Submission endpoint restored");
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn record_result(&self, result: &Result<B256, SubmissionError>, latency: Duration, config: &SequencerConfig) {
+        self.metrics.latency_seconds.record(latency.as_secs_f64());
+        let mut health = self.health.lock();
+
+        if result.is_ok() {
+            self.metrics.acks_total.increment(1);
+        }
+        // Rejecting one of our transactions is a healthy answer; only failing to answer counts
+        let reason = if !matches!(result, Err(e) if e.is_endpoint_fault()) {
+            health.consecutive_errors = 0;
+            let sample = latency.as_secs_f64() * 1000.0;
+            let ewma = health.latency_ewma_ms
+                .map(|avg| avg * (1.0 - LATENCY_EWMA_ALPHA) + sample * LATENCY_EWMA_ALPHA)
+                .unwrap_or(sample);
+            health.latency_ewma_ms = Some(ewma);
+            (ewma > config.demote_latency.as_secs_f64() * 1000.0).then_some("lagging")
+        } else {
+            health.consecutive_errors += 1;
+            (health.consecutive_errors >= config.demote_after_errors).then_some("erroring")
+        };
+
+        if let Some(reason) = reason {
+            self.demote(&mut health, reason, config);
+        }
+    }
+
+    /// Demote the endpoint straight away, e.g. when it starts rate limiting us
+    fn back_off(&self, config: &SequencerConfig) {
+        let mut health = self.health.lock();
+        self.demote(&mut health, "rate_limited", config);
+    }
+
+    fn demote(&self, health: &mut EndpointHealth, reason: &str, config: &SequencerConfig) {
+        if health.demoted_until.is_some() {
+            return;
         }
+        warn!(
+            endpoint = %self.endpoint.name,
+            reason = reason,
+            consecutive_errors = health.consecutive_errors,
+            latency_ewma_ms = ?health.latency_ewma_ms,
+            demote_secs = config.demote_duration.as_secs(),
+            "Demoting submission endpoint"
+        );
+        health.demoted_until = Some(Instant::now() + config.demote_duration);
+        health.consecutive_errors = 0;
+        health.latency_ewma_ms = None;
+        self.metrics.demoted.set(1.0);
     }
 }
 
-/// Service for submitting transactions to the Base sequencer
+/// Service for submitting transactions to the Base sequencer and any additional
+/// builder/node endpoints, fanned out concurrently
 pub struct SequencerService {
     config: SequencerConfig,
     client: Client,
-    redis_conn: Arc<RwLock<Option<ConnectionManager>>>,
+    endpoints: Vec<Arc<EndpointState>>,
+    /// First-acknowledging endpoint per submitted tx, for inclusion attribution
+    first_acks: DashMap<B256, (String, Instant)>,
 }
 
 impl SequencerService {
     /// Create a new sequencer service
     pub fn new(config: SequencerConfig) -> Result<Self> {
-        let client = ClientBuilder::new()
+        // Keep connections open indefinitely so submissions never pay for a TCP/TLS handshake;
+        // HTTP/2 is negotiated over TLS where the endpoint supports it
+        let mut builder = ClientBuilder::new()
             .timeout(config.timeout)
-            .build()?;
+            .connect_timeout(config.timeout)
+            .pool_idle_timeout(None)
+            .pool_max_idle_per_host(4)
+            .tcp_nodelay(true)
+            .tcp_keepalive(Duration::from_secs(30));
+        if !config.keepalive_interval.is_zero() {
+            builder = builder
+                .http2_keep_alive_interval(config.keepalive_interval)
+                .http2_keep_alive_while_idle(true);
+        }
+        let client = builder.build()?;
+
+        let endpoints: Vec<Arc<EndpointState>> = config.submission_endpoints()
+            .into_iter()
+            .map(|endpoint| Arc::new(EndpointState {
+                metrics: SubmissionEndpointMetrics::new_with_labels(&[("endpoint", endpoint.name.clone())]),
+                endpoint,
+                health: Mutex::new(EndpointHealth::default()),
+                supports_conditional: AtomicBool::new(true),
+            }))
+            .collect();
 
         info!(
-            url = %config.url,
+            url = %crate::config::redact_url(&config.url),
+            endpoints = ?endpoints.iter().map(|e| e.endpoint.name.as_str()).collect::<Vec<_>>(),
             timeout_secs = config.timeout.as_secs(),
-            redis_host = %config.redis_host,
-            redis_channel = %config.redis_channel,
             "Initialized sequencer service"
         );
 
         let service = Self { 
             config: config.clone(),
             client,
-            redis_conn: Arc::new(RwLock::new(None)),
+            endpoints,
+            first_acks: DashMap::new(),
         };
 
-        // Spawn task to initialize Redis connection asynchronously
-        let redis_conn_clone = service.redis_conn.clone();
-        tokio::spawn(async move {
-            match Self::init_redis_connection(&config).await {
-                Ok(conn) => {
-                    info!("Successfully connected to Redis");
-                    *redis_conn_clone.write().await = Some(conn);
+        // Warm every endpoint's connection now and keep it warm with periodic pings
+        if !config.keepalive_interval.is_zero() {
+            let client = service.client.clone();
+            let endpoints = service.endpoints.clone();
+            let interval = config.keepalive_interval;
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+                loop {
+                    ticker.tick().await;
+                    for endpoint in &endpoints {
+                        let start_time = Instant::now();
+                        match Self::ping_endpoint(&client, &endpoint.endpoint).await {
+                            Ok(()) => endpoint.metrics.keepalive_latency_seconds.record(start_time.elapsed().as_secs_f64()),
+                            Err(e) => {
+                                endpoint.metrics.keepalive_failures_total.increment(1);
+                                debug!(endpoint = %endpoint.endpoint.name, error = %e, "Keepalive ping failed");
+                            }
+                        }
+                    }
                 }
-                Err(e) => {
-                    error!("Failed to connect to Redis: {}. Transaction broadcasting disabled.", e);
-                }
-            }
-        });
+            });
+        }
 
         Ok(service)
     }
 
-    /// Initialize Redis connection
-    async fn init_redis_connection(config: &SequencerConfig) -> Result<ConnectionManager> {
-        let redis_url = format!(
-            "redis://:{}@{}:{}/",
-            config.redis_password,
-            config.redis_host,
-            config.redis_port
-        );
-
-        let client = RedisClient::open(redis_url)?;
-        let conn = ConnectionManager::new(client).await?;
-        
-        Ok(conn)
+    /// Send a signed transaction to every active endpoint concurrently
+    /// Returns the transaction hash from the first endpoint that acknowledges it,
+    /// or the primary-most endpoint's error if none do
+    pub async fn send_transaction(&self, signed_tx: &str) -> Result<B256, SubmissionError> {
+        self.submit(signed_tx, None).await
     }
 
-    /// Create from environment variables
-    pub fn from_env() -> Result<Self> {
-        let mut config = SequencerConfig::default();
-
-        if let Ok(url) = std::env::var("SEQUENCER_URL") {
-            config.url = url;
-        }
-
-        if let Ok(timeout_str) = std::env::var("SEQUENCER_TIMEOUT") {
-            if let Ok(timeout_secs) = timeout_str.parse::<u64>() {
-                config.timeout = Duration::from_secs(timeout_secs);
-            }
-        }
-
-        if let Ok(host) = std::env::var("REDIS_HOST") {
-            config.redis_host = host;
-        }
-
-        if let Ok(port_str) = std::env::var("REDIS_PORT") {
-            if let Ok(port) = port_str.parse::<u16>() {
-                config.redis_port = port;
-            }
-        }
-
-        if let Ok(password) = std::env::var("REDIS_PASSWORD") {
-            config.redis_password = password;
-        }
-
-        if let Ok(channel) = std::env::var("REDIS_CHANNEL") {
-            config.redis_channel = channel;
-        }
-
-        Self::new(config)
+    /// Send a signed transaction with `eth_sendRawTransactionConditional` preconditions.
+    /// Endpoints that do not support conditionals receive a plain `eth_sendRawTransaction`.
+    pub async fn send_transaction_conditional(
+        &self,
+        signed_tx: &str,
+        conditional: &TransactionConditional,
+    ) -> Result<B256, SubmissionError> {
+        self.submit(signed_tx, Some(conditional)).await
     }
 
-    /// Send a signed transaction to the sequencer
-    /// Returns the transaction hash if successful
-    pub async fn send_transaction(&self, signed_tx: &str) -> Result<B256> {
+    async fn submit(
+        &self,
+        signed_tx: &str,
+        conditional: Option<&TransactionConditional>,
+    ) -> Result<B256, SubmissionError> {
         // Ensure the transaction has 0x prefix
         let tx_data = if signed_tx.starts_with("0x") {
             signed_tx.to_string()
@@ -155,15 +412,17 @@ impl SequencerService {
             format!("0x{}", signed_tx)
         };
 
-        let request_body = serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": "eth_sendRawTransaction",
-            "params": [tx_data],
-            "id": 1
+        // Serialize the request envelopes once; every endpoint shares the same bytes
+        let plain_envelope = raw_transaction_envelope("eth_sendRawTransaction", &tx_data, None);
+        let conditional_envelope = conditional.map(|conditional| {
+            raw_transaction_envelope("eth_sendRawTransactionConditional", &tx_data, Some(&conditional.to_json()))
         });
 
-        info!(
-            url = %self.config.url,
+        let send_started = Instant::now();
+        let endpoints = self.active_endpoints();
+        debug!(
+            endpoints = ?endpoints.iter().map(|e| e.endpoint.name.as_str()).collect::<Vec<_>>(),
+            conditional = conditional.is_some(),
             tx_size = tx_data.len(),
             tx_preview = %format!("{}...{}",
                 &tx_data[..20.min(tx_data.len())],
@@ -172,44 +431,207 @@ impl SequencerService {
             "Sending transaction to sequencer"
         );
 
+        // Each endpoint runs in its own task so slower endpoints still finish and
+        // record their metrics after the first acknowledgement has been returned
+        let mut submissions: FuturesUnordered<_> = endpoints.into_iter()
+            .enumerate()
+            .map(|(priority, endpoint)| {
+                let client = self.client.clone();
+                let config = self.config.clone();
+                let signed_tx = signed_tx.to_string();
+                let plain_envelope = plain_envelope.clone();
+                let conditional_envelope = conditional_envelope.clone();
+                tokio::spawn(async move {
+                    let start_time = Instant::now();
+                    endpoint.metrics.requests_total.increment(1);
+                    let result = Self::post_transaction(
+                        &client,
+                        &endpoint,
+                        plain_envelope,
+                        conditional_envelope,
+                        &signed_tx,
+                    ).await;
+                    endpoint.record_result(&result, start_time.elapsed(), &config);
+                    if matches!(&result, Err(e) if e.policy() == SubmissionPolicy::Backoff) {
+                        endpoint.back_off(&config);
+                    }
+                    (priority, endpoint, result)
+                })
+            })
+            .collect();
+
+        let mut first_error: Option<(usize, SubmissionError)> = None;
+        while let Some(joined) = submissions.next().await {
+            let (priority, error) = match joined {
+                Ok((_, endpoint, Ok(hash))) => {
+                    crate::metrics::MEV_METRICS.submission_ack_duration_seconds.record(send_started.elapsed().as_secs_f64());
+                    endpoint.metrics.first_acks_total.increment(1);
+                    self.first_acks.insert(hash, (endpoint.endpoint.name.clone(), Instant::now()));
+                    debug!(endpoint = %endpoint.endpoint.name, tx_hash = %hash, "First acknowledgement");
+                    return Ok(hash);
+                }
+                Ok((priority, _, Err(e))) => (priority, e),
+                Err(e) => (usize::MAX, SubmissionError::Transport(format!("Submission task failed: {}", e))),
+            };
+            if first_error.as_ref().map(|(p, _)| priority < *p).unwrap_or(true) {
+                first_error = Some((priority, error));
+            }
+        }
+
+        Err(first_error
+            .map(|(_, e)| e)
+            .unwrap_or_else(|| SubmissionError::Transport("No submission endpoints configured".to_string())))
+    }
+
+    /// Attribute landed transactions to the endpoint that acknowledged them first
+    pub fn observe_flashblock(&self, event: &FlashblocksEvent) {
+        if self.first_acks.is_empty() {
+            return;
+        }
+
+        for tx in &event.transactions {
+            if let Some((hash, (endpoint_name, acked_at))) = self.first_acks.remove(tx.tx_hash()) {
+                if let Some(endpoint) = self.endpoints.iter().find(|e| e.endpoint.name == endpoint_name) {
+                    endpoint.metrics.inclusions_total.increment(1);
+                }
+                info!(
+                    tx_hash = %hash,
+                    endpoint = %endpoint_name,
+                    block = event.block_number,
+                    flashblock = event.index,
+                    ack_to_inclusion_ms = acked_at.elapsed().as_millis(),
+                    "Submitted transaction included"
+                );
+            }
+        }
+
+        self.first_acks.retain(|_, (_, acked_at)| acked_at.elapsed() < INCLUSION_ATTRIBUTION_WINDOW);
+    }
+
+    /// Endpoints that are not currently demoted; falls back to all endpoints
+    /// rather than going dark when every one of them is demoted
+    fn active_endpoints(&self) -> Vec<Arc<EndpointState>> {
+        let now = Instant::now();
+        let active: Vec<_> = self.endpoints.iter()
+            .filter(|endpoint| !endpoint.is_demoted(now))
+            .cloned()
+            .collect();
+        if active.is_empty() {
+            warn!("All submission endpoints demoted, submitting to all of them");
+            return self.endpoints.clone();
+        }
+        active
+    }
+
+    /// Post a raw transaction to a single endpoint, conditionally if requested and supported
+    async fn post_transaction(
+        client: &Client,
+        endpoint: &EndpointState,
+        plain_envelope: Bytes,
+        conditional_envelope: Option<Bytes>,
+        signed_tx: &str,
+    ) -> Result<B256, SubmissionError> {
+        if let Some(envelope) = conditional_envelope.filter(|_| endpoint.supports_conditional.load(Ordering::Relaxed)) {
+            match Self::post_request(client, endpoint, envelope, signed_tx).await {
+                Err(e) if e.is_method_unsupported() => {
+                    endpoint.supports_conditional.store(false, Ordering::Relaxed);
+                    warn!(
+                        endpoint = %endpoint.endpoint.name,
+                        error = %e,
+                        "Endpoint does not support conditional transactions, falling back to eth_sendRawTransaction"
+                    );
+                }
+                result => return result,
+            }
+        }
+
+        Self::post_request(client, endpoint, plain_envelope, signed_tx).await
+    }
+
+    /// Post a JSON-RPC submission request to a single endpoint
+    async fn post_request(
+        client: &Client,
+        endpoint: &EndpointState,
+        envelope: Bytes,
+        signed_tx: &str,
+    ) -> Result<B256, SubmissionError> {
         let start_time = std::time::Instant::now();
+        let result = Self::post_request_inner(client, endpoint, envelope, start_time).await;
+        let elapsed = start_time.elapsed();
 
-        // Clone tx_data for Redis broadcast
-        let _tx_for_redis = tx_data.clone();
-        let _redis_conn = self.redis_conn.clone();
-        // DISABLED: Redis broadcasting seems to be causing issues with transaction inclusion
-        // let redis_channel = self.config.redis_channel.clone();
-
-        // // Spawn Redis broadcast task to run concurrently with sequencer submission
-        // let redis_task = tokio::spawn(async move {
-        //     if let Some(mut conn) = redis_conn.write().await.as_mut() {
-        //         let payload = serde_json::json!({
-        //             "signedTx": tx_for_redis
-        //         });
-        //         
-        //         match conn.publish::<_, _, ()>(&redis_channel, payload.to_string()).await {
-        //             Ok(_) => {
-        //                 info!("🌟💫 REDIS BROADCAST COMPLETE! 📡✨ Transaction echoing across the MEV network on channel: {} 🎊🎉", redis_channel);
-        //             }
-        //             Err(e) => {
-        //                 warn!("Failed to broadcast transaction to Redis: {}", e);
-        //             }
-        //         }
-        //     } else {
-        //         warn!("Redis connection not available, skipping broadcast");
-        //     }
-        // });
-
-        let response = self.client
-            .post(&self.config.url)
+        match result {
+            Ok(hash) => {
+                info!(
+                    endpoint = %endpoint.endpoint.name,
+                    tx_hash = %hash,
+                    elapsed_ms = elapsed.as_millis(),
+                    "🎆🎇 TRANSACTION LAUNCHED TO SEQUENCER! 🚀✨ Transaction soaring through the mempool! 🎆🎇"
+                );
+                Ok(hash)
+            }
+            Err(SubmissionError::AlreadyKnown) => {
+                endpoint.metrics.record_error(&SubmissionError::AlreadyKnown);
+                // Another endpoint (or a previous attempt) already delivered it, which is
+                // as good as an acknowledgement: the hash is that of the raw bytes
+                let raw = hex::decode(signed_tx.trim_start_matches("0x"))
+                    .map_err(|e| SubmissionError::Transport(format!("Invalid signed transaction hex: {}", e)))?;
+                let hash = keccak256(&raw);
+                info!(
+                    endpoint = %endpoint.endpoint.name,
+                    tx_hash = %hash,
+                    elapsed_ms = elapsed.as_millis(),
+                    "Transaction already known to endpoint"
+                );
+                Ok(hash)
+            }
+            Err(e) if e.policy() == SubmissionPolicy::Backoff => {
+                endpoint.metrics.record_error(&e);
+                warn!(
+                    endpoint = %endpoint.endpoint.name,
+                    class = e.class(),
+                    error = %e,
+                    elapsed_ms = elapsed.as_millis(),
+                    "Submission endpoint is rate limiting - backing off"
+                );
+                Err(e)
+            }
+            Err(e) => {
+                endpoint.metrics.record_error(&e);
+                error!(
+                    endpoint = %endpoint.endpoint.name,
+                    class = e.class(),
+                    policy = ?e.policy(),
+                    error = %e,
+                    elapsed_ms = elapsed.as_millis(),
+                    "Sequencer rejected transaction"
+                );
+                Err(e)
+            }
+        }
+    }
+
+    async fn post_request_inner(
+        client: &Client,
+        endpoint: &EndpointState,
+        envelope: Bytes,
+        start_time: Instant,
+    ) -> Result<B256, SubmissionError> {
+        let response = client
+            .post(&endpoint.endpoint.url)
             .header("Content-Type", "application/json")
-            .json(&request_body)
+            .body(envelope.0)
             .send()
-            .await?;
+            .await
+            .map_err(|e| if e.is_timeout() {
+                SubmissionError::Timeout(start_time.elapsed())
+            } else {
+                SubmissionError::Transport(e.to_string())
+            })?;
 
         let elapsed = start_time.elapsed();
         
-        info!(
+        debug!(
+            endpoint = %endpoint.endpoint.name,
             status = response.status().as_u16(),
             elapsed_ms = elapsed.as_millis(),
             "⚡📨 SEQUENCER RESPONDED! Status: {} in {}ms! 🏁💫 The race is on! 🏎️💨",
@@ -218,104 +640,48 @@ impl SequencerService {
         );
 
         if !response.status().is_success() {
-            let status = response.status();
+            let status = response.status().as_u16();
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            error!(
-                status = %status,
-                error = %error_text,
-                elapsed_ms = elapsed.as_millis(),
-                "Sequencer returned error status"
-            );
-            return Err(eyre::eyre!("Sequencer error {}: {}", status, error_text));
+            return Err(SubmissionError::from_http(status, error_text));
         }
 
-        let response_text = response.text().await?;
-        info!(
+        let response_text = response.text().await
+            .map_err(|e| SubmissionError::Transport(e.to_string()))?;
+        debug!(
+            endpoint = %endpoint.endpoint.name,
             response_body = %response_text,
             "📬✨ SEQUENCER RESPONSE RECEIVED! 📡🎯 Transaction accepted into the mempool! 🌊🚀"
         );
         
         let sequencer_response: SequencerResponse = serde_json::from_str(&response_text)
-            .map_err(|e| eyre::eyre!("Failed to parse sequencer response: {}", e))?;
-
-        // DISABLED: Wait for Redis broadcast to complete (but don't fail if it errors)
-        // let redis_result = redis_task.await;
-        // let redis_broadcast_success = redis_result.is_ok();
-        let redis_broadcast_success = false; // Redis broadcast disabled
+            .map_err(|e| SubmissionError::Transport(format!("Failed to parse sequencer response: {}", e)))?;
 
         if let Some(error) = sequencer_response.error {
-            // Check if this is a "transaction already known" error
-            // Common error codes: -32000 (already known), -32003 (transaction underpriced)
-            let is_already_known = error.message.to_lowercase().contains("already known") 
-                || error.message.to_lowercase().contains("replacement transaction")
-                || error.message.to_lowercase().contains("nonce too low")
-                || error.code == -32000;
-
-            if is_already_known && redis_broadcast_success {
-                // If Redis broadcast succeeded and sequencer says already known,
-                // this is likely a race condition where another node submitted first
-                warn!(
-                    code = error.code,
-                    message = %error.message,
-                    elapsed_ms = elapsed.as_millis(),
-                    "Transaction already known to sequencer (likely submitted by another node via Redis)"
-                );
-                
-                // Try to extract tx hash from the error message if possible
-                // Some implementations include the hash in the error
-                // For now, we'll generate a placeholder hash
-                let placeholder_hash = B256::from_slice(&keccak256(signed_tx.as_bytes())[..]);
-                
-                info!(
-                    tx_hash = %placeholder_hash,
-                    elapsed_ms = elapsed.as_millis(),
-                    "Transaction broadcast via Redis (sequencer reports already known)"
-                );
-                
-                return Ok(placeholder_hash);
-            } else {
-                // This is a real error, not a race condition
-                error!(
-                    code = error.code,
-                    message = %error.message,
-                    elapsed_ms = elapsed.as_millis(),
-                    redis_broadcast = redis_broadcast_success,
-                    "Sequencer returned JSON-RPC error"
-                );
-                return Err(eyre::eyre!("Sequencer error {}: {}", error.code, error.message));
-            }
+            return Err(SubmissionError::from_rpc(error.code, &error.message));
         }
 
         let tx_hash = sequencer_response.result
-            .ok_or_else(|| eyre::eyre!("No result in sequencer response"))?;
+            .ok_or_else(|| SubmissionError::Transport("No result in sequencer response".to_string()))?;
 
         // Parse the transaction hash
-        let hash = tx_hash.parse::<B256>()
-            .map_err(|e| eyre::eyre!("Failed to parse transaction hash: {}", e))?;
-
-        info!(
-            tx_hash = %hash,
-            elapsed_ms = elapsed.as_millis(),
-            redis_broadcast = redis_broadcast_success,
-            "🎆🎇 TRANSACTION LAUNCHED TO SEQUENCER! 🚀✨ Transaction soaring through the mempool! 🎆🎇"
-        );
-
-        Ok(hash)
+        tx_hash.parse::<B256>()
+            .map_err(|e| SubmissionError::Transport(format!("Failed to parse transaction hash: {}", e)))
     }
 
     /// Check if the sequencer is healthy
     pub async fn health_check(&self) -> Result<()> {
-        let request_body = serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": "eth_blockNumber",
-            "params": [],
-            "id": 1
-        });
+        // Health is judged on the primary sequencer
+        Self::ping_endpoint(&self.client, &self.endpoints[0].endpoint).await?;
+        debug!("Sequencer health check passed");
+        Ok(())
+    }
 
-        let response = self.client
-            .post(&self.config.url)
+    /// `eth_blockNumber` round trip on a single endpoint; also keeps its connection warm
+    async fn ping_endpoint(client: &Client, endpoint: &SubmissionEndpoint) -> Result<()> {
+        let response = client
+            .post(&endpoint.url)
             .header("Content-Type", "application/json")
-            .json(&request_body)
+            .body(BLOCK_NUMBER_REQUEST)
             .send()
             .await?;
 
@@ -329,11 +695,22 @@ impl SequencerService {
             return Err(eyre::eyre!("Sequencer health check returned error"));
         }
 
-        debug!("Sequencer health check passed");
         Ok(())
     }
 }
 
+/// Pre-serialize a raw transaction submission request
+fn raw_transaction_envelope(method: &str, tx_data: &str, options: Option<&serde_json::Value>) -> Bytes {
+    let envelope = match options {
+        Some(options) => format!(
+            r#"{{"jsonrpc":"2.0","method":"{}","params":["{}",{}],"id":1}}"#,
+            method, tx_data, options
+        ),
+        None => format!(r#"{{"jsonrpc":"2.0","method":"{}","params":["{}"],"id":1}}"#, method, tx_data),
+    };
+    Bytes::from(envelope.into_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -345,6 +722,139 @@ mod tests {
         assert_eq!(config.timeout.as_secs(), 5);
     }
 
+    #[test]
+    fn test_parse_endpoints() {
+        let endpoints = parse_endpoints("builder=http://builder:8545, http://node:8545,,");
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0], SubmissionEndpoint { name: "builder".into(), url: "http://builder:8545".into() });
+        assert_eq!(endpoints[1].name, "endpoint1");
+        assert_eq!(endpoints[1].url, "http://node:8545");
+    }
+
+    #[test]
+    fn test_submission_endpoints_dedup_primary() {
+        let config = SequencerConfig {
+            endpoints: parse_endpoints("dup=https://mainnet-sequencer.base.org/,own=http://localhost:8545"),
+            ..Default::default()
+        };
+        let endpoints = config.submission_endpoints();
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].name, "sequencer");
+        assert_eq!(endpoints[1].name, "own");
+    }
+
+    #[test]
+    fn test_conditional_json() {
+        let pool = Address::repeat_byte(0x11);
+        let mut slots = BTreeMap::new();
+        slots.insert(U256::from(8), U256::from(0xabcu64));
+        let conditional = TransactionConditional {
+            known_accounts: [(pool, slots)].into_iter().collect(),
+            block_number_min: Some(100),
+            block_number_max: Some(102),
+            ..Default::default()
+        };
+
+        let json = conditional.to_json();
+        let account = &json["knownAccounts"]["0x1111111111111111111111111111111111111111"];
+        assert_eq!(
+            account["0x0000000000000000000000000000000000000000000000000000000000000008"],
+            "0x0000000000000000000000000000000000000000000000000000000000000abc"
+        );
+        assert_eq!(json["blockNumberMin"], "0x64");
+        assert_eq!(json["blockNumberMax"], "0x66");
+        assert!(json.get("timestampMax").is_none());
+    }
+
+    #[test]
+    fn test_envelope_is_valid_json_rpc() {
+        let envelope = raw_transaction_envelope("eth_sendRawTransaction", "0x02f8", None);
+        let parsed: serde_json::Value = serde_json::from_slice(&envelope).unwrap();
+        assert_eq!(parsed["method"], "eth_sendRawTransaction");
+        assert_eq!(parsed["params"][0], "0x02f8");
+
+        let options = TransactionConditional { block_number_max: Some(5), ..Default::default() }.to_json();
+        let envelope = raw_transaction_envelope("eth_sendRawTransactionConditional", "0x02f8", Some(&options));
+        let parsed: serde_json::Value = serde_json::from_slice(&envelope).unwrap();
+        assert_eq!(parsed["params"][1]["blockNumberMax"], "0x5");
+    }
+
+    fn mock_config(addr: std::net::SocketAddr) -> SequencerConfig {
+        SequencerConfig {
+            url: format!("http://{}/", addr),
+            keepalive_interval: Duration::ZERO,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submissions_reuse_warm_connection() {
+        let (addr, stats) = crate::mock_sequencer::spawn(
+            "127.0.0.1:0".parse().unwrap(),
+            crate::mock_sequencer::MockSequencerConfig::default(),
+        ).await.unwrap();
+        let service = SequencerService::new(mock_config(addr)).unwrap();
+
+        service.health_check().await.unwrap();
+        for raw in ["0x01", "0x0203", "0x040506"] {
+            let hash = service.send_transaction(raw).await.unwrap();
+            assert_eq!(hash, keccak256(hex::decode(&raw[2..]).unwrap()));
+        }
+
+        assert_eq!(stats.requests.load(Ordering::Relaxed), 4);
+        assert_eq!(stats.connections.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_falls_back_when_unsupported() {
+        let (addr, stats) = crate::mock_sequencer::spawn(
+            "127.0.0.1:0".parse().unwrap(),
+            crate::mock_sequencer::MockSequencerConfig { support_conditional: false, ..Default::default() },
+        ).await.unwrap();
+        let service = SequencerService::new(mock_config(addr)).unwrap();
+        let conditional = TransactionConditional { block_number_max: Some(10), ..Default::default() };
+
+        service.send_transaction_conditional("0x01", &conditional).await.unwrap();
+        service.send_transaction_conditional("0x02", &conditional).await.unwrap();
+
+        // Unsupported once, then plain submissions only
+        assert_eq!(stats.requests.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_errors_are_classified() {
+        let (addr, _) = crate::mock_sequencer::spawn(
+            "127.0.0.1:0".parse().unwrap(),
+            crate::mock_sequencer::MockSequencerConfig {
+                submission_error: Some((-32000, "nonce too low".to_string())),
+                ..Default::default()
+            },
+        ).await.unwrap();
+        let service = SequencerService::new(mock_config(addr)).unwrap();
+
+        let err = service.send_transaction("0x01").await.unwrap_err();
+        assert!(matches!(err, SubmissionError::NonceTooLow(_)));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_transactions_do_not_demote_endpoint() {
+        let (addr, _) = crate::mock_sequencer::spawn(
+            "127.0.0.1:0".parse().unwrap(),
+            crate::mock_sequencer::MockSequencerConfig {
+                submission_error: Some((-32000, "insufficient funds for gas * price + value".to_string())),
+                ..Default::default()
+            },
+        ).await.unwrap();
+        let config = SequencerConfig { demote_after_errors: 1, ..mock_config(addr) };
+        let service = SequencerService::new(config).unwrap();
+
+        for raw in ["0x01", "0x02"] {
+            service.send_transaction(raw).await.unwrap_err();
+        }
+        assert!(!service.endpoints[0].is_demoted(Instant::now()));
+        assert_eq!(service.endpoints[0].health.lock().consecutive_errors, 0);
+    }
+
     #[test]
     fn test_tx_prefix_handling() {
         // This would need an async test context and mock server
@@ -0,0 +1,262 @@
+//! Graceful shutdown of the MEV pipeline.
+//!
+//! The node's graceful shutdown signal triggers [`Shutdown`]: flashblock intake and the
+//! opportunity handler stop, in-flight submissions get the drain timeout to finish, then gas
+//! history and the database actor are flushed and pending nonces are written to disk so the
+//! next run can reconcile them against the chain.
+
+use alloy_primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::{info, warn};
+
+use crate::database_service::DatabaseService;
+use crate::gas_history_store::GasHistoryStore;
+use crate::transaction_service::TransactionService;
+
+/// Current [`PendingState`] layout
+pub const PENDING_STATE_VERSION: u32 = 1;
+
+/// Flushes still get this long when the drain used up the timeout
+const MIN_FLUSH_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Shutdown signal shared by the pipeline, and the submissions a shutdown waits for
+#[derive(Clone, Default)]
+pub struct Shutdown {
+    token: CancellationToken,
+    submissions: TaskTracker,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop accepting new work
+    pub fn trigger(&self) {
+        self.token.cancel();
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Resolves once shutdown has been triggered
+    pub async fn triggered(&self) {
+        self.token.cancelled().await
+    }
+
+    /// Run a submission that shutdown lets finish before persisting state
+    pub fn spawn_submission<F>(&self, submission: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.submissions.spawn(submission);
+    }
+
+    /// Wait for in-flight submissions; returns how many were still running at the timeout
+    async fn drain_submissions(&self, timeout: Duration) -> usize {
+        self.submissions.close();
+        match tokio::time::timeout(timeout, self.submissions.wait()).await {
+            Ok(()) => 0,
+            Err(_) => self.submissions.len(),
+        }
+    }
+}
+
+/// A submitted nonce that had not resolved at shutdown
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistedSubmission {
+    pub wallet: Address,
+    pub nonce: u64,
+    pub strategy: String,
+    pub block_number: u64,
+    /// Every transaction sent for this nonce, oldest first
+    pub tx_hashes: Vec<B256>,
+    pub max_fee_per_gas: u128,
+}
+
+/// Pending submissions and nonce state carried across a restart
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingState {
+    pub version: u32,
+    /// Unix seconds the state was written
+    pub saved_at: u64,
+    pub submissions: Vec<PersistedSubmission>,
+    /// Next nonce each wallet would have used, as far as this process knew
+    pub next_nonces: BTreeMap<Address, u64>,
+}
+
+impl PendingState {
+    pub fn new(submissions: Vec<PersistedSubmission>) -> Self {
+        let mut next_nonces = BTreeMap::new();
+        for submission in &submissions {
+            let next = next_nonces.entry(submission.wallet).or_insert(0);
+            *next = (*next).max(submission.nonce + 1);
+        }
+        Self {
+            version: PENDING_STATE_VERSION,
+            saved_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+            submissions,
+            next_nonces,
+        }
+    }
+
+    /// Write atomically so a crash mid-write leaves the previous state intact
+    pub fn save(&self, path: &Path) -> eyre::Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    /// State left by the previous run, if it wrote any
+    pub fn load(path: &Path) -> eyre::Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let state: Self = serde_json::from_slice(&std::fs::read(path)?)?;
+        if state.version > PENDING_STATE_VERSION {
+            return Err(eyre::eyre!("pending state version {} is newer than supported {}", state.version, PENDING_STATE_VERSION));
+        }
+        Ok(Some(state))
+    }
+
+    /// Split into submissions the chain has moved past and those still outstanding
+    pub fn reconcile<F>(self, mut on_chain_nonce: F) -> eyre::Result<(Vec<PersistedSubmission>, Vec<PersistedSubmission>)>
+    where
+        F: FnMut(&Address) -> eyre::Result<u64>,
+    {
+        let mut nonces = BTreeMap::new();
+        let mut resolved = Vec::new();
+        let mut outstanding = Vec::new();
+        for submission in self.submissions {
+            let on_chain = match nonces.get(&submission.wallet) {
+                Some(nonce) => *nonce,
+                None => {
+                    let nonce = on_chain_nonce(&submission.wallet)?;
+                    nonces.insert(submission.wallet, nonce);
+                    nonce
+                }
+            };
+            if on_chain > submission.nonce {
+                resolved.push(submission);
+            } else {
+                outstanding.push(submission);
+            }
+        }
+        Ok((resolved, outstanding))
+    }
+}
+
+/// Everything a shutdown drains and flushes
+pub struct ShutdownPipeline {
+    pub shutdown: Shutdown,
+    pub drain_timeout: Duration,
+    pub pending_state_path: PathBuf,
+    pub transaction_service: Arc<TransactionService>,
+    pub gas_history: Arc<GasHistoryStore>,
+    pub database: Option<Arc<DatabaseService>>,
+}
+
+impl ShutdownPipeline {
+    /// Stop intake, drain submissions, then flush and persist state
+    pub async fn run(self) {
+        let started = Instant::now();
+        info!(drain_timeout_ms = self.drain_timeout.as_millis() as u64, "🛑 Shutting down MEV pipeline");
+        self.shutdown.trigger();
+
+        let unfinished = self.shutdown.drain_submissions(self.drain_timeout).await;
+        if unfinished > 0 {
+            warn!(unfinished = unfinished, "Drain timed out with submissions still in flight");
+        } else {
+            info!(elapsed_ms = started.elapsed().as_millis() as u64, "In-flight submissions drained");
+        }
+        let flush_timeout = self.drain_timeout.saturating_sub(started.elapsed()).max(MIN_FLUSH_TIMEOUT);
+
+        match tokio::time::timeout(flush_timeout, self.gas_history.flush()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!(error = %e, "Failed to flush gas history"),
+            Err(_) => warn!(timeout_ms = flush_timeout.as_millis() as u64, "Gas history flush timed out"),
+        }
+
+        if let Some(database) = &self.database {
+            match tokio::time::timeout(flush_timeout, database.shutdown()).await {
+                Ok(Ok(())) => info!("Database actor flushed"),
+                Ok(Err(e)) => warn!(error = %e, "Failed to flush database actor"),
+                Err(_) => warn!(timeout_ms = flush_timeout.as_millis() as u64, "Database flush timed out"),
+            }
+        }
+
+        // Last, so submissions that finished during the drain are included
+        let state = self.transaction_service.pending_state();
+        match state.save(&self.pending_state_path) {
+            Ok(()) => info!(
+                path = %self.pending_state_path.display(),
+                pending = state.submissions.len(),
+                wallets = state.next_nonces.len(),
+                "Pending submission state persisted"
+            ),
+            Err(e) => warn!(path = %self.pending_state_path.display(), error = %e, "Failed to persist pending submission state"),
+        }
+
+        info!(elapsed_ms = started.elapsed().as_millis() as u64, "MEV pipeline shut down");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(wallet: u8, nonce: u64) -> PersistedSubmission {
+        PersistedSubmission {
+            wallet: Address::repeat_byte(wallet),
+            nonce,
+            strategy: "Backrun_test".to_string(),
+            block_number: 100,
+            tx_hashes: vec![B256::with_last_byte(nonce as u8)],
+            max_fee_per_gas: 1_000_000,
+        }
+    }
+
+    #[test]
+    fn test_pending_state_round_trip_and_reconcile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state/pending_submissions.json");
+        assert_eq!(PendingState::load(&path).unwrap(), None);
+
+        let state = PendingState::new(vec![submission(1, 4), submission(1, 5), submission(2, 9)]);
+        assert_eq!(state.next_nonces[&Address::repeat_byte(1)], 6);
+        state.save(&path).unwrap();
+
+        let loaded = PendingState::load(&path).unwrap().unwrap();
+        assert_eq!(loaded, state);
+
+        // Wallet 1 moved past nonce 4 only; wallet 2 has not moved
+        let (resolved, outstanding) = loaded.reconcile(|wallet| Ok(if *wallet == Address::repeat_byte(1) { 5 } else { 9 })).unwrap();
+        assert_eq!(resolved, vec![submission(1, 4)]);
+        assert_eq!(outstanding, vec![submission(1, 5), submission(2, 9)]);
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_submissions() {
+        let shutdown = Shutdown::new();
+        shutdown.spawn_submission(tokio::time::sleep(Duration::from_millis(10)));
+        shutdown.trigger();
+        assert!(shutdown.is_triggered());
+        assert_eq!(shutdown.drain_submissions(Duration::from_secs(1)).await, 0);
+
+        let stuck = Shutdown::new();
+        stuck.spawn_submission(std::future::pending());
+        assert_eq!(stuck.drain_submissions(Duration::from_millis(10)).await, 1);
+    }
+}
@@ -0,0 +1,96 @@
+//! Where the executor and workers read chain state from.
+//!
+//! The embedded node's provider is the fast path; [`crate::remote_state::RpcStateSource`] reads
+//! the same state from a JSON-RPC node so the pipeline can run away from the node.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::Address;
+use alloy_rpc_types_eth::{BlockId, BlockNumberOrTag};
+use reth_provider::{BlockNumReader, BlockReader, HeaderProvider, StateProvider, StateProviderFactory};
+use reth_revm::database::StateProviderDatabase;
+use revm::DatabaseRef;
+use std::fmt::Debug;
+
+/// Header fields the EVM environment is built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateBlock {
+    pub number: u64,
+    pub timestamp: u64,
+    pub gas_limit: u64,
+    pub base_fee_per_gas: Option<u64>,
+}
+
+/// Chain state for flashblock execution and strategy search
+pub trait StateSource: Clone + Send + Sync + 'static {
+    /// State at one block, wrapped in a `CacheDB` by its users
+    type Database: revm::Database<Error: Send + Sync + 'static>
+        + DatabaseRef<Error: Send + Sync + 'static>
+        + Debug
+        + Send
+        + Sync
+        + 'static;
+
+    fn best_block_number(&self) -> eyre::Result<u64>;
+
+    fn block(&self, block_id: BlockId) -> eyre::Result<StateBlock>;
+
+    /// State as of the best block
+    fn latest_state(&self) -> eyre::Result<Self::Database>;
+
+    /// Warm accounts about to be read; only sources with per-read latency need this
+    fn prefetch(&self, _state: &Self::Database, _accounts: &[Address]) {}
+}
+
+/// State from the embedded node's provider
+#[derive(Debug, Clone)]
+pub struct ProviderStateSource<P> {
+    provider: P,
+}
+
+impl<P> ProviderStateSource<P> {
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+}
+
+impl<P> StateSource for ProviderStateSource<P>
+where
+    P: StateProviderFactory + HeaderProvider + BlockReader + Clone + Send + Sync + 'static,
+    P::Header: BlockHeader,
+{
+    type Database = StateProviderDatabase<Box<dyn StateProvider>>;
+
+    fn best_block_number(&self) -> eyre::Result<u64> {
+        Ok(self.provider.best_block_number()?)
+    }
+
+    fn block(&self, block_id: BlockId) -> eyre::Result<StateBlock> {
+        let header = match block_id {
+            BlockId::Number(BlockNumberOrTag::Latest) => {
+                let number = self.provider.best_block_number()?;
+                self.provider.header_by_number(number)?
+                    .ok_or_else(|| eyre::eyre!("Header not found for block {}", number))?
+            }
+            BlockId::Number(BlockNumberOrTag::Number(number)) => {
+                self.provider.header_by_number(number)?
+                    .ok_or_else(|| eyre::eyre!("Header not found for block {}", number))?
+            }
+            BlockId::Hash(hash) => {
+                self.provider.header(&hash.block_hash)?
+                    .ok_or_else(|| eyre::eyre!("Header not found for hash {:?}", hash))?
+            }
+            _ => return Err(eyre::eyre!("Unsupported block ID: {:?}", block_id)),
+        };
+        Ok(StateBlock {
+            number: header.number(),
+            timestamp: header.timestamp(),
+            gas_limit: header.gas_limit(),
+            base_fee_per_gas: header.base_fee_per_gas(),
+        })
+    }
+
+    fn latest_state(&self) -> eyre::Result<Self::Database> {
+        // Holds a database read transaction until dropped
+        Ok(StateProviderDatabase::new(self.provider.latest()?))
+    }
+}
@@ -0,0 +1,217 @@
+//! Classification of submission endpoint failures into what the submitter does next.
+//!
+//! Sequencers and nodes report failures as free-form JSON-RPC messages or HTTP statuses;
+//! [`SubmissionError`] normalizes them and [`SubmissionError::policy`] maps each class to a
+//! retry, resync, backoff or alert decision.
+
+use std::time::Duration;
+
+/// Classified failure from a submission endpoint
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SubmissionError {
+    #[error("nonce too low: {0}")]
+    NonceTooLow(String),
+    #[error("nonce gap: {0}")]
+    NonceGap(String),
+    #[error("transaction underpriced: {0}")]
+    Underpriced(String),
+    #[error("replacement transaction underpriced: {0}")]
+    ReplacementUnderpriced(String),
+    #[error("transaction already known")]
+    AlreadyKnown,
+    #[error("insufficient funds: {0}")]
+    InsufficientFunds(String),
+    /// A conditional submission's preconditions no longer hold, i.e. we lost the race
+    #[error("precondition failed: {0}")]
+    PreconditionFailed(String),
+    #[error("rate limited: {0}")]
+    RateLimited(String),
+    #[error("transport timeout after {0:?}")]
+    Timeout(Duration),
+    #[error("transport error: {0}")]
+    Transport(String),
+    #[error("HTTP error {status}: {body}")]
+    Http { status: u16, body: String },
+    #[error("JSON-RPC error {code}: {message}")]
+    Rpc { code: i32, message: String },
+}
+
+/// What the submitter should do about a failed submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionPolicy {
+    /// Re-sign with higher fees and submit again
+    RetryWithBumpedFee,
+    /// Re-read the account nonce, re-sign and submit again
+    ResyncNonce,
+    /// Give up on this transaction
+    Drop,
+    /// Give up on this transaction and demote the endpoint until it stops throttling us
+    Backoff,
+    /// Give up and raise an operator alert
+    Alert,
+}
+
+impl SubmissionError {
+    /// Classify a JSON-RPC error returned by a sequencer or node
+    pub fn from_rpc(code: i32, message: &str) -> Self {
+        let lower = message.to_lowercase();
+        if lower.contains("nonce too low") {
+            Self::NonceTooLow(message.to_string())
+        } else if lower.contains("nonce too high") || lower.contains("nonce gap") {
+            Self::NonceGap(message.to_string())
+        } else if lower.contains("replacement transaction underpriced") {
+            Self::ReplacementUnderpriced(message.to_string())
+        } else if lower.contains("underpriced")
+            || lower.contains("fee too low")
+            || lower.contains("less than block base fee")
+        {
+            Self::Underpriced(message.to_string())
+        } else if lower.contains("already known") || lower.contains("known transaction") {
+            Self::AlreadyKnown
+        } else if lower.contains("insufficient funds") {
+            Self::InsufficientFunds(message.to_string())
+        } else if code == -32003
+            || lower.contains("mismatch")
+            || lower.contains("out of range")
+            || lower.contains("not within range")
+            || lower.contains("conditional failed")
+            || lower.contains("failed conditional")
+        {
+            Self::PreconditionFailed(message.to_string())
+        } else if code == -32005 || lower.contains("rate limit") || lower.contains("too many requests") {
+            Self::RateLimited(message.to_string())
+        } else {
+            Self::Rpc { code, message: message.to_string() }
+        }
+    }
+
+    /// Classify a non-2xx HTTP response
+    pub fn from_http(status: u16, body: String) -> Self {
+        if status == 429 {
+            Self::RateLimited(body)
+        } else {
+            Self::Http { status, body }
+        }
+    }
+
+    /// Policy for this error class
+    pub fn policy(&self) -> SubmissionPolicy {
+        match self {
+            Self::Underpriced(_) | Self::ReplacementUnderpriced(_) => SubmissionPolicy::RetryWithBumpedFee,
+            Self::NonceTooLow(_) | Self::NonceGap(_) => SubmissionPolicy::ResyncNonce,
+            Self::AlreadyKnown | Self::PreconditionFailed(_) | Self::Timeout(_) | Self::Transport(_) => {
+                SubmissionPolicy::Drop
+            }
+            Self::Http { status, .. } if *status >= 500 => SubmissionPolicy::Drop,
+            Self::RateLimited(_) => SubmissionPolicy::Backoff,
+            Self::InsufficientFunds(_) | Self::Http { .. } | Self::Rpc { .. } => {
+                SubmissionPolicy::Alert
+            }
+        }
+    }
+
+    /// Whether the failure says the endpoint is unhealthy, rather than that it rejected our
+    /// transaction; only these count toward demoting it
+    pub fn is_endpoint_fault(&self) -> bool {
+        match self {
+            Self::Timeout(_) | Self::Transport(_) | Self::RateLimited(_) => true,
+            Self::Http { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Short label for logs and metrics
+    pub fn class(&self) -> &'static str {
+        match self {
+            Self::NonceTooLow(_) => "nonce_too_low",
+            Self::NonceGap(_) => "nonce_gap",
+            Self::Underpriced(_) => "underpriced",
+            Self::ReplacementUnderpriced(_) => "replacement_underpriced",
+            Self::AlreadyKnown => "already_known",
+            Self::InsufficientFunds(_) => "insufficient_funds",
+            Self::PreconditionFailed(_) => "precondition_failed",
+            Self::RateLimited(_) => "rate_limited",
+            Self::Timeout(_) => "timeout",
+            Self::Transport(_) => "transport",
+            Self::Http { .. } => "http",
+            Self::Rpc { .. } => "rpc",
+        }
+    }
+
+    /// Whether the endpoint rejected the method itself rather than the transaction
+    pub fn is_method_unsupported(&self) -> bool {
+        match self {
+            Self::Rpc { code, message } => {
+                let lower = message.to_lowercase();
+                *code == -32601
+                    || lower.contains("method not found")
+                    || lower.contains("does not exist")
+                    || lower.contains("not available")
+                    || lower.contains("not supported")
+            }
+            Self::Http { status, .. } => *status == 404 || *status == 405,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_classification() {
+        assert_eq!(SubmissionError::from_rpc(-32000, "nonce too low").class(), "nonce_too_low");
+        assert_eq!(SubmissionError::from_rpc(-32000, "nonce too high").class(), "nonce_gap");
+        assert_eq!(
+            SubmissionError::from_rpc(-32000, "replacement transaction underpriced").class(),
+            "replacement_underpriced"
+        );
+        assert_eq!(SubmissionError::from_rpc(-32000, "transaction underpriced").class(), "underpriced");
+        assert_eq!(SubmissionError::from_rpc(-32000, "already known"), SubmissionError::AlreadyKnown);
+        assert_eq!(
+            SubmissionError::from_rpc(-32000, "insufficient funds for gas * price + value").class(),
+            "insufficient_funds"
+        );
+        assert_eq!(SubmissionError::from_rpc(-32005, "limit exceeded").class(), "rate_limited");
+        assert_eq!(SubmissionError::from_rpc(-32003, "storage slot mismatch").class(), "precondition_failed");
+        assert_eq!(
+            SubmissionError::from_rpc(-32000, "block number 105 out of range [100, 102]").class(),
+            "precondition_failed"
+        );
+        assert_eq!(SubmissionError::from_rpc(-32000, "execution reverted").class(), "rpc");
+    }
+
+    #[test]
+    fn test_policies() {
+        assert_eq!(SubmissionError::from_rpc(0, "transaction underpriced").policy(), SubmissionPolicy::RetryWithBumpedFee);
+        assert_eq!(SubmissionError::from_rpc(0, "nonce too low").policy(), SubmissionPolicy::ResyncNonce);
+        assert_eq!(SubmissionError::AlreadyKnown.policy(), SubmissionPolicy::Drop);
+        assert_eq!(SubmissionError::from_http(502, String::new()).policy(), SubmissionPolicy::Drop);
+        assert_eq!(SubmissionError::from_http(429, String::new()).policy(), SubmissionPolicy::Backoff);
+        assert_eq!(SubmissionError::from_rpc(0, "insufficient funds").policy(), SubmissionPolicy::Alert);
+        assert_eq!(SubmissionError::from_rpc(-32003, "storage slot mismatch").policy(), SubmissionPolicy::Drop);
+    }
+
+    #[test]
+    fn test_endpoint_faults() {
+        assert!(SubmissionError::Timeout(Duration::from_secs(5)).is_endpoint_fault());
+        assert!(SubmissionError::from_http(503, String::new()).is_endpoint_fault());
+        assert!(SubmissionError::from_http(429, String::new()).is_endpoint_fault());
+        assert!(!SubmissionError::from_rpc(-32000, "nonce too low").is_endpoint_fault());
+        assert!(!SubmissionError::from_rpc(-32000, "insufficient funds").is_endpoint_fault());
+        assert!(!SubmissionError::from_rpc(-32003, "storage slot mismatch").is_endpoint_fault());
+        assert!(!SubmissionError::from_http(400, String::new()).is_endpoint_fault());
+    }
+
+    #[test]
+    fn test_method_unsupported() {
+        assert!(SubmissionError::from_rpc(
+            -32601,
+            "the method eth_sendRawTransactionConditional does not exist/is not available"
+        )
+        .is_method_unsupported());
+        assert!(SubmissionError::from_http(405, String::new()).is_method_unsupported());
+        assert!(!SubmissionError::from_rpc(-32003, "storage slot mismatch").is_method_unsupported());
+    }
+}
@@ -1,21 +1,40 @@
-use alloy_primitives::{U256, B256};
-use alloy_consensus::{TxEip1559, TxEnvelope, Signed, Transaction, SignableTransaction};
-use alloy_signer_local::PrivateKeySigner;
-use alloy_network::TxSigner;
-use reth_provider::{StateProviderFactory, HeaderProvider};
-use alloy_consensus::BlockHeader;
+use alloy_primitives::{utils::format_ether, Address, U256, B256};
+use alloy_consensus::{TxEip1559, TxEnvelope, Transaction, SignableTransaction};
+use alloy_rpc_types_eth::BlockId;
+use reth_optimism_chainspec::OpChainSpec;
+use revm::DatabaseRef;
 use alloy_eips::eip2718::Encodable2718;
+use async_trait::async_trait;
 use eyre::Result;
-use redis::aio::ConnectionManager;
-use redis::{AsyncCommands, Client as RedisClient};
+use redis::AsyncCommands;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, error, info, warn};
 use rand::Rng;
+use dashmap::DashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use crate::mev_search_worker::MevOpportunity;
-use crate::wallet_service::WalletService;
-use crate::sequencer_service::SequencerService;
+use crate::mev_bundle_types::FeeQuote;
+use crate::wallet_service::{redact_address, SharedSigner, WalletService};
+use crate::sequencer_service::{SequencerService, TransactionConditional};
+use crate::submission_error::{SubmissionError, SubmissionPolicy};
+use crate::fee_multiplier_controller::{FeeMultiplierController, TrackedLeg};
+use crate::bundle_simulator::BundleSimulator;
+use crate::flashblock_state::FlashblockStateSnapshot;
+use crate::flashblocks::FlashblocksEvent;
+use crate::inclusion_tracker::{InclusionRecord, InclusionTracker};
+use crate::pipeline::SubmissionWallets;
+use crate::gas_limit_estimator::GasLimitEstimator;
+use crate::redis_client::RedisHandle;
+use crate::shutdown::{PendingState, PersistedSubmission};
+use crate::state_source::StateSource;
+
+/// Minimum fee increase (percent) the mempool accepts for a same-nonce replacement
+const MIN_REPLACEMENT_BUMP_PERCENT: u128 = 10;
 
 /// Configuration for the transaction service
 #[derive(Debug, Clone)]
@@ -26,13 +45,84 @@ pub struct TransactionServiceConfig {
     pub default_gas_limit: Option<u64>,
     pub gas_multiplier: f64,
     pub wallet_strategy: WalletStrategy,
+    /// Simulate the full signed bundle on the opportunity's state before submitting
+    pub simulate_bundles: bool,
+    /// Submit with `eth_sendRawTransactionConditional` preconditions on the simulated state
+    pub conditional_submission: bool,
+    /// Number of blocks after the opportunity's block the transaction may still land in
+    pub conditional_block_range: u64,
+    /// Resubmissions allowed per opportunity for retryable rejections
+    pub max_submission_retries: u32,
+    /// Fee increase applied when a leg is rejected as underpriced (percent)
+    pub fee_bump_percent: u128,
+    /// Flashblocks a submission may stay unincluded before the watchdog acts; 0 disables it
+    pub watchdog_flashblocks: u32,
+    /// Replacement/cancellation attempts per stuck nonce before giving up
+    pub max_replacements: u32,
+    /// How long a wallet whose submission failed is avoided by the selection strategies
+    pub wallet_failure_cooldown: Duration,
+    /// JSON lines log of every replacement and cancellation; `None` disables it
+    pub replacement_log: Option<PathBuf>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WalletStrategy {
     Default,
     Random,
     RoundRobin,
+    /// Wallet with the fewest submissions awaiting inclusion
+    LeastPending,
+    /// Random wallet, weighted by balance above the minimum
+    BalanceWeighted,
+    /// Same wallet for every submission to a target contract, so they never race on its state
+    StickyPerTarget,
+}
+
+impl WalletStrategy {
+    /// Parse a strategy name as used in `BLOCK_TX_WALLET_STRATEGY`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "default" => Some(Self::Default),
+            "random" => Some(Self::Random),
+            "round-robin" => Some(Self::RoundRobin),
+            "least-pending" => Some(Self::LeastPending),
+            "balance-weighted" => Some(Self::BalanceWeighted),
+            "sticky-per-target" => Some(Self::StickyPerTarget),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of submission made for a pending nonce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionAttemptKind {
+    Original,
+    Replacement,
+    Cancellation,
+}
+
+/// One submission made for a pending nonce
+#[derive(Debug, Clone)]
+pub struct SubmissionAttempt {
+    pub kind: SubmissionAttemptKind,
+    pub tx_hash: B256,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub block_number: u64,
+    pub flashblock_index: u32,
+    pub error: Option<String>,
+}
+
+/// A signed leg that has not been seen in a flashblock yet
+struct PendingSubmission {
+    opportunity: MevOpportunity,
+    wallet: SharedSigner,
+    /// The most recently submitted transaction for this nonce
+    tx: TxEip1559,
+    flashblocks_waited: u32,
+    attempts: Vec<SubmissionAttempt>,
+    /// The watchdog ran out of replacements; the nonce stays held until the chain passes it
+    abandoned: bool,
 }
 
 impl Default for TransactionServiceConfig {
@@ -44,6 +134,15 @@ impl Default for TransactionServiceConfig {
             default_gas_limit: None,
             gas_multiplier: 1.2,
             wallet_strategy: WalletStrategy::Default,
+            simulate_bundles: true,
+            conditional_submission: false,
+            conditional_block_range: 2,
+            max_submission_retries: 1,
+            fee_bump_percent: 15,
+            watchdog_flashblocks: 3,
+            max_replacements: 2,
+            wallet_failure_cooldown: Duration::from_secs(12),
+            replacement_log: Some(PathBuf::from("mev_replacements.jsonl")),
         }
     }
 }
@@ -54,7 +153,51 @@ pub struct TransactionService {
     wallet_service: Arc<WalletService>,
     sequencer_service: Arc<SequencerService>,
     wallet_index: Arc<RwLock<usize>>,
-    redis_conn: Arc<RwLock<Option<ConnectionManager>>>,
+    redis: Arc<RedisHandle>,
+    fee_controller: Option<Arc<FeeMultiplierController>>,
+    bundle_simulator: BundleSimulator,
+    /// Submitted legs awaiting inclusion, keyed by (wallet, nonce)
+    pending: DashMap<(Address, u64), PendingSubmission>,
+    inclusion_tracker: Arc<InclusionTracker>,
+    gas_limit_estimator: Arc<GasLimitEstimator>,
+    /// Last failed submission per wallet
+    wallet_failures: DashMap<Address, Instant>,
+    /// Wallet assigned to each target contract by the sticky-per-target strategy
+    sticky_wallets: DashMap<Address, Address>,
+    /// Nonces held by submissions that are being signed and sent, per wallet
+    nonce_reservations: DashMap<Address, BTreeSet<u64>>,
+    /// Serializes watchdog runs; each flashblock's run waits for the previous one
+    watchdog: Mutex<()>,
+}
+
+/// Nonces reserved for an in-flight submission, released on drop
+///
+/// Once the submission's legs are in `pending` they keep later allocations past them.
+struct NonceReservation<'a> {
+    service: &'a TransactionService,
+    wallet: Address,
+    nonces: Range<u64>,
+}
+
+impl NonceReservation<'_> {
+    fn start(&self) -> u64 {
+        self.nonces.start
+    }
+}
+
+impl Drop for NonceReservation<'_> {
+    fn drop(&mut self) {
+        let now_empty = match self.service.nonce_reservations.get_mut(&self.wallet) {
+            Some(mut reserved) => {
+                reserved.retain(|nonce| !self.nonces.contains(nonce));
+                reserved.is_empty()
+            }
+            None => false,
+        };
+        if now_empty {
+            self.service.nonce_reservations.remove_if(&self.wallet, |_, reserved| reserved.is_empty());
+        }
+    }
 }
 
 impl TransactionService {
@@ -62,40 +205,73 @@ impl TransactionService {
         config: TransactionServiceConfig,
         wallet_service: Arc<WalletService>,
         sequencer_service: Arc<SequencerService>,
+        fee_controller: Option<Arc<FeeMultiplierController>>,
+        gas_limit_estimator: Arc<GasLimitEstimator>,
+        redis: Arc<RedisHandle>,
+        chain_spec: Arc<OpChainSpec>,
     ) -> Self {
-        let service = Self {
+        let inclusion_tracker = Arc::new(InclusionTracker::new(wallet_service.get_addresses()));
+        Self {
             config,
             wallet_service,
             sequencer_service,
             wallet_index: Arc::new(RwLock::new(0)),
-            redis_conn: Arc::new(RwLock::new(None)),
-        };
-        
-        // Initialize Redis connection in the background
-        let redis_conn_clone = service.redis_conn.clone();
-        tokio::spawn(async move {
-            if let Ok(conn) = Self::init_redis_connection().await {
-                *redis_conn_clone.write().await = Some(conn);
-                info!("Redis connection established for dynamic multipliers");
-            } else {
-                warn!("Failed to connect to Redis for dynamic multipliers - using static values");
-            }
-        });
-        
-        service
+            redis,
+            fee_controller,
+            bundle_simulator: BundleSimulator::new(chain_spec),
+            pending: DashMap::new(),
+            inclusion_tracker,
+            gas_limit_estimator,
+            wallet_failures: DashMap::new(),
+            sticky_wallets: DashMap::new(),
+            nonce_reservations: DashMap::new(),
+            watchdog: Mutex::new(()),
+        }
+    }
+
+    /// First nonce free for `wallet`: past the state nonce, every submission still awaiting
+    /// inclusion and every nonce held by an in-flight submission
+    pub fn next_nonce(&self, wallet: Address, state_nonce: u64) -> u64 {
+        let reserved = self.nonce_reservations.get(&wallet).and_then(|reserved| reserved.last().map(|n| n + 1));
+        self.pending_nonce_floor(wallet, state_nonce).max(reserved.unwrap_or(0))
+    }
+
+    fn pending_nonce_floor(&self, wallet: Address, state_nonce: u64) -> u64 {
+        self.pending.iter()
+            .filter(|entry| entry.key().0 == wallet)
+            .map(|entry| entry.key().1 + 1)
+            .fold(state_nonce, u64::max)
+    }
+
+    /// Reserve `count` consecutive nonces starting at [`Self::next_nonce`]
+    fn reserve_nonces(&self, wallet: Address, state_nonce: u64, count: u64) -> NonceReservation<'_> {
+        // The entry lock makes concurrent submissions from one wallet take distinct nonces
+        let mut reserved = self.nonce_reservations.entry(wallet).or_default();
+        let start = reserved.last()
+            .map(|n| n + 1)
+            .unwrap_or(0)
+            .max(self.pending_nonce_floor(wallet, state_nonce));
+        reserved.extend(start..start + count);
+        NonceReservation { service: self, wallet, nonces: start..start + count }
+    }
+
+    /// Hold specific nonces, e.g. while the watchdog has their entries out of `pending`
+    fn hold_nonces(&self, wallet: Address, nonces: Range<u64>) -> NonceReservation<'_> {
+        self.nonce_reservations.entry(wallet).or_default().extend(nonces.clone());
+        NonceReservation { service: self, wallet, nonces }
     }
 
     /// Process an MEV opportunity into a transaction
     /// This is the Rust equivalent of TypeScript's processBuilder method
-    pub async fn process_opportunity<P>(
+    ///
+    /// Every leg of the bundle is signed with sequential nonces from the same wallet,
+    /// simulated as a whole on the opportunity's flashblock state, then submitted in order.
+    /// Returns the hash of the last submitted leg.
+    pub async fn process_opportunity<S: StateSource>(
         &self,
         opportunity: &MevOpportunity,
-        provider: &P,
-    ) -> Result<Option<B256>> 
-    where
-        P: StateProviderFactory + HeaderProvider + reth_provider::BlockNumReader,
-        P::Header: BlockHeader,
-    {
+        source: &S,
+    ) -> Result<Option<B256>> {
         if !self.config.enabled {
             debug!("Transaction service is disabled, skipping opportunity");
             return Ok(None);
@@ -103,75 +279,776 @@ impl TransactionService {
 
         let start_time = std::time::Instant::now();
 
+        let legs = &opportunity.bundle.transactions;
+        if legs.is_empty() {
+            return Err(eyre::eyre!("No transactions in MEV bundle"));
+        }
+
         // Get wallet for signing
-        let wallet = self.get_next_wallet().await?;
+        let wallet = self.get_next_wallet(opportunity).await?;
         let wallet_address = wallet.address();
 
-        // Get nonce from state provider
-        let state = provider.latest()?;
-        let account = state.basic_account(&wallet_address)?;
-        let nonce = account.map(|acc| acc.nonce).unwrap_or(0);
+        // State nonce from the flashblock state if our wallet was touched, else from the state
+        // source; our legs go past any nonces still pending or held by other submissions
+        let state_nonce = match opportunity.state_snapshot.as_ref()
+            .and_then(|snapshot| snapshot.account_changes.get(&wallet_address))
+        {
+            Some(account) => account.nonce,
+            None => state_nonce(source, wallet_address)?,
+        };
+        let own_legs = legs.iter()
+            .filter(|tx| matches!(tx, crate::mev_bundle_types::BundleTransaction::Unsigned { .. }))
+            .count() as u64;
+        let mut reservation = self.reserve_nonces(wallet_address, state_nonce, own_legs);
+        let nonce = reservation.start();
+
+        let simulated_nonce = legs.iter().find_map(|tx| match tx {
+            crate::mev_bundle_types::BundleTransaction::Unsigned { from, nonce, .. } if *from == wallet_address => Some(*nonce),
+            _ => None,
+        });
+        if simulated_nonce.is_some_and(|simulated| simulated != nonce) {
+            warn!(
+                wallet = %redact_address(&wallet_address),
+                simulated_nonce = ?simulated_nonce,
+                nonce = nonce,
+                "Submitting with a different nonce than the opportunity was simulated with"
+            );
+        }
 
         info!(
             block = opportunity.block_number,
             flashblock = opportunity.flashblock_index,
             strategy = %opportunity.strategy,
-            wallet = %wallet_address,
+            wallet = %redact_address(&wallet_address),
             nonce = nonce,
             expected_profit = %opportunity.expected_profit,
             simulated_gas_used = ?opportunity.simulated_gas_used,
-            bundle_size = opportunity.bundle.transactions.len(),
+            bundle_size = legs.len(),
             "Processing MEV opportunity"
         );
 
-        // Extract transaction details from the opportunity
-        // In the TypeScript version, this comes from builder.build(context)
-        // Here, we already have the built transaction in the opportunity
-        let bundle_tx = opportunity.bundle.transactions.first()
-            .ok_or_else(|| eyre::eyre!("No transactions in MEV bundle"))?;
+        // Legs carry the fees they were simulated with; quote for any that do not
+        let needs_quote = legs.iter().any(|tx| matches!(
+            tx,
+            crate::mev_bundle_types::BundleTransaction::Unsigned { fees, .. } if fees.max_fee_per_gas == 0
+        ));
+        let quoted = if needs_quote {
+            let header = source.block(BlockId::latest())?;
+            let base_fee = header.base_fee_per_gas.unwrap_or(1_000_000) as u128;
+            Some(self.quote_fees(
+                &opportunity.strategy,
+                opportunity.processor_config.as_ref(),
+                opportunity.expected_profit,
+                opportunity.simulated_gas_used,
+                base_fee,
+            ).await)
+        } else {
+            None
+        };
+
+        // Build and sign every leg; unsigned legs get sequential nonces from our wallet,
+        // pre-signed legs are submitted verbatim
+        let mut prepared: Vec<(TxEnvelope, Option<TxEip1559>)> = Vec::with_capacity(legs.len());
+        let mut next_nonce = nonce;
+        for (leg_index, bundle_tx) in legs.iter().enumerate() {
+            match bundle_tx {
+                crate::mev_bundle_types::BundleTransaction::Unsigned {
+                    to, input, value, gas_limit, fees: carried, ..
+                } => {
+                    let to_addr = to.ok_or_else(|| eyre::eyre!("Missing 'to' address in bundle leg {}", leg_index))?;
+                    let fees = match quoted {
+                        Some(quote) if carried.max_fee_per_gas == 0 => quote,
+                        _ => *carried,
+                    };
+                    let gas_limit = if *gas_limit > 0 {
+                        *gas_limit
+                    } else {
+                        self.fallback_gas_limit(opportunity, input.len(), legs.len())
+                    };
+
+                    let tx = TxEip1559 {
+                        chain_id: self.config.chain_id,
+                        nonce: next_nonce,
+                        gas_limit,
+                        max_fee_per_gas: fees.max_fee_per_gas,
+                        max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+                        to: alloy_primitives::TxKind::Call(to_addr),
+                        value: *value,
+                        access_list: Default::default(),
+                        input: input.clone(),
+                    };
+                    next_nonce += 1;
+
+                    info!(
+                        leg = leg_index,
+                        to = %to_addr,
+                        value = %value,
+                        value_hex = %format!("0x{:x}", value),
+                        gas_limit = gas_limit,
+                        nonce = tx.nonce,
+                        chain_id = self.config.chain_id,
+                        input_len = input.len(),
+                        input_hex = %hex::encode(input),
+                        "Built EIP-1559 bundle leg"
+                    );
+
+                    let (signed_tx, tx) = Self::sign_leg(&wallet, tx).await?;
+                    prepared.push((signed_tx, Some(tx)));
+                }
+                crate::mev_bundle_types::BundleTransaction::Signed(tx_envelope) => {
+                    info!(
+                        leg = leg_index,
+                        tx_hash = %tx_envelope.tx_hash(),
+                        to = ?tx_envelope.to(),
+                        "Using pre-signed bundle leg"
+                    );
+                    prepared.push((tx_envelope.clone(), None));
+                }
+            }
+        }
 
-        // Extract transaction parameters based on bundle transaction type
-        let (to, data, value) = match bundle_tx {
-            crate::mev_bundle_types::BundleTransaction::Unsigned { to, input, value, .. } => {
-                let to_addr = to.ok_or_else(|| eyre::eyre!("Missing 'to' address in bundle transaction"))?;
+        // Verify the whole sequence on the opportunity's state before anything is sent
+        if self.config.simulate_bundles {
+            if let Some(ref snapshot) = opportunity.state_snapshot {
+                let envelopes: Vec<TxEnvelope> = prepared.iter().map(|(tx, _)| tx.clone()).collect();
+                let leg_results = self.bundle_simulator.simulate(source, snapshot, &envelopes, Some((wallet_address, nonce)))
+                    .map_err(|e| {
+                        warn!(
+                            strategy = %opportunity.strategy,
+                            block = opportunity.block_number,
+                            error = %e,
+                            "Bundle simulation failed, not submitting"
+                        );
+                        e
+                    })?;
                 info!(
-                    tx_type = "unsigned",
-                    to = %to_addr,
-                    value = %value,
-                    input_len = input.len(),
-                    input_hex = %hex::encode(&input),
-                    "Extracted unsigned transaction parameters"
+                    strategy = %opportunity.strategy,
+                    legs = leg_results.len(),
+                    total_gas = leg_results.iter().map(|r| r.gas_used).sum::<u64>(),
+                    "Bundle simulation passed"
                 );
-                (to_addr, input.clone(), *value)
-            }
-            crate::mev_bundle_types::BundleTransaction::Signed(tx_envelope) => {
-                // For signed transactions, extract the fields
-                let to_addr = tx_envelope.to()
-                    .ok_or_else(|| eyre::eyre!("Missing 'to' address in signed transaction"))?;
-                let input = tx_envelope.input().clone();
-                let value = tx_envelope.value();
-                info!(
-                    tx_type = "signed",
-                    to = %to_addr,
-                    value = %value,
-                    input_len = input.len(),
-                    input_hex = %hex::encode(&input),
-                    "Extracted signed transaction parameters"
+            } else {
+                debug!(strategy = %opportunity.strategy, "No state snapshot on opportunity, skipping bundle simulation");
+            }
+        }
+
+        let mut signed_legs: Vec<(B256, String)> = prepared.iter()
+            .map(|(tx, _)| Self::encode_leg(tx))
+            .collect();
+
+        for (leg_index, (tx_hash, signed_hex)) in signed_legs.iter().enumerate() {
+            info!(
+                strategy = %opportunity.strategy,
+                leg = leg_index,
+                tx_hash = %tx_hash,
+                signed_size = (signed_hex.len() - 2) / 2,
+                signed_hex_preview = %format!("{}...{}", 
+                    &signed_hex[..20.min(signed_hex.len())],
+                    &signed_hex[signed_hex.len().saturating_sub(20)..]
+                ),
+                "Signed MEV transaction"
+            );
+        }
+
+        // Check if dry run mode
+        if self.config.dry_run {
+            info!("DRY RUN MODE - Not submitting transaction");
+            for ((_, unsigned), (_, signed_hex)) in prepared.iter().zip(signed_legs.iter()) {
+                if let Some(tx) = unsigned {
+                    self.log_dry_run(&opportunity, tx, signed_hex).await;
+                }
+            }
+            return Ok(None);
+        }
+
+        // The first leg carries the preconditions: it runs against exactly the state we
+        // simulated, later legs only depend on it landing
+        let conditional = self.build_conditional(opportunity);
+
+        // Submit legs in order; stop at the first failure since later nonces would gap
+        info!(
+            legs = signed_legs.len(),
+            conditional = conditional.is_some(),
+            "Submitting transaction to sequencer"
+        );
+        let mut last_hash = None;
+        let mut retries = 0;
+        let mut leg_index = 0;
+        let submitted = async {
+            while leg_index < signed_legs.len() {
+                let signed_hex = &signed_legs[leg_index].1;
+                let result = match (&conditional, leg_index) {
+                    (Some(conditional), 0) => self.sequencer_service.send_transaction_conditional(signed_hex, conditional).await,
+                    _ => self.sequencer_service.send_transaction(signed_hex).await,
+                };
+                let e = match result {
+                    Ok(tx_hash) => {
+                        debug!(leg = leg_index, tx_hash = %tx_hash, "Bundle leg submitted");
+                        // Recorded as soon as it is out, so its nonce stays held whatever happens
+                        // to the legs after it
+                        self.record_sent_leg(opportunity, &wallet, &prepared[leg_index], signed_legs[leg_index].0);
+                        last_hash = Some(tx_hash);
+                        leg_index += 1;
+                        continue;
+                    }
+                    Err(e) => e,
+                };
+
+                // Only legs we signed ourselves can be re-signed
+                let policy = e.policy();
+                let retryable = retries < self.config.max_submission_retries && prepared[leg_index].1.is_some();
+                match policy {
+                    SubmissionPolicy::RetryWithBumpedFee if retryable => {
+                        retries += 1;
+                        let mut tx = prepared[leg_index].1.clone().expect("checked above");
+                        tx.max_priority_fee_per_gas = tx.max_priority_fee_per_gas * (100 + self.config.fee_bump_percent) / 100;
+                        tx.max_fee_per_gas = tx.max_fee_per_gas * (100 + self.config.fee_bump_percent) / 100;
+                        warn!(
+                            leg = leg_index,
+                            class = e.class(),
+                            max_fee_per_gas = tx.max_fee_per_gas,
+                            max_priority_fee_per_gas = tx.max_priority_fee_per_gas,
+                            "Retrying bundle leg with bumped fee"
+                        );
+                        let (signed_tx, tx) = Self::sign_leg(&wallet, tx).await?;
+                        signed_legs[leg_index] = Self::encode_leg(&signed_tx);
+                        prepared[leg_index] = (signed_tx, Some(tx));
+                        continue;
+                    }
+                    SubmissionPolicy::ResyncNonce if retryable => {
+                        retries += 1;
+                        // The remaining legs go past the chain and past this bundle's sent legs
+                        let sent_floor = prepared[..leg_index].iter()
+                            .filter_map(|(_, tx)| tx.as_ref().map(|tx| tx.nonce + 1))
+                            .max()
+                            .unwrap_or(0);
+                        let resync_nonce = state_nonce(source, wallet_address)?.max(sent_floor);
+                        let remaining_legs = prepared[leg_index..].iter().filter(|(_, tx)| tx.is_some()).count() as u64;
+                        drop(reservation);
+                        reservation = self.reserve_nonces(wallet_address, resync_nonce, remaining_legs);
+                        let fresh_nonce = reservation.start();
+                        let stale_nonce = prepared[leg_index].1.as_ref().map(|tx| tx.nonce).unwrap_or_default();
+                        if fresh_nonce != stale_nonce {
+                            warn!(
+                                leg = leg_index,
+                                class = e.class(),
+                                stale_nonce = stale_nonce,
+                                fresh_nonce = fresh_nonce,
+                                "Resynced nonce, re-signing remaining bundle legs"
+                            );
+                            let mut next_nonce = fresh_nonce;
+                            for index in leg_index..prepared.len() {
+                                let Some(mut tx) = prepared[index].1.clone() else { continue };
+                                tx.nonce = next_nonce;
+                                next_nonce += 1;
+                                let (signed_tx, tx) = Self::sign_leg(&wallet, tx).await?;
+                                signed_legs[index] = Self::encode_leg(&signed_tx);
+                                prepared[index] = (signed_tx, Some(tx));
+                            }
+                            continue;
+                        }
+                    }
+                    _ => {}
+                }
+
+                error!(
+                    block = opportunity.block_number,
+                    flashblock = opportunity.flashblock_index,
+                    strategy = %opportunity.strategy,
+                    leg = leg_index,
+                    legs_submitted = leg_index,
+                    class = e.class(),
+                    policy = ?policy,
+                    alert = policy == SubmissionPolicy::Alert,
+                    retries = retries,
+                    error = ?e,
+                    error_message = %e,
+                    "Failed to submit MEV transaction"
                 );
-                (to_addr, input, value)
+                // A throttling endpoint or a lost race says nothing about the wallet
+                if policy != SubmissionPolicy::Backoff && !matches!(e, SubmissionError::PreconditionFailed(_)) {
+                    self.wallet_failures.insert(wallet_address, Instant::now());
+                }
+                return Err(e.into());
+            }
+            Ok::<_, eyre::Report>(())
+        }.await;
+        // Legs already sent may still land and spend their fees, even if a later one failed
+        self.track_sent_bundle(opportunity, &prepared, &signed_legs, leg_index);
+        submitted?;
+
+        let tx_hash = last_hash.ok_or_else(|| eyre::eyre!("No bundle legs submitted"))?;
+        let elapsed = start_time.elapsed();
+        info!(
+            block = opportunity.block_number,
+            flashblock = opportunity.flashblock_index,
+            strategy = %opportunity.strategy,
+            tx_hash = %tx_hash,
+            legs = signed_legs.len(),
+            elapsed_ms = elapsed.as_millis(),
+            expected_profit = %opportunity.expected_profit,
+            "🎯💰 MEV JACKPOT SUBMITTED! 🎰🚀 Profit incoming: {} wei! 💎🔥 Strategy {} STRIKES GOLD! 🏆✨",
+            opportunity.expected_profit,
+            opportunity.strategy
+        );
+
+        Ok(Some(tx_hash))
+    }
+
+    /// Watch a sent leg for inclusion; our own legs hold their nonces until they land, and the
+    /// watchdog replaces or cancels them if they get stuck
+    fn record_sent_leg(
+        &self,
+        opportunity: &MevOpportunity,
+        wallet: &SharedSigner,
+        (_, unsigned): &(TxEnvelope, Option<TxEip1559>),
+        leg_hash: B256,
+    ) {
+        self.inclusion_tracker.track(
+            leg_hash,
+            &opportunity.strategy,
+            opportunity.state_snapshot.as_ref().map(|snapshot| snapshot.snapshot_time),
+            opportunity.last_flashblock_tx_hash,
+        );
+
+        let Some(tx) = unsigned else { return };
+        self.inclusion_tracker.track_nonce(tx.nonce);
+        self.pending.insert((wallet.address(), tx.nonce), PendingSubmission {
+            opportunity: opportunity.clone(),
+            wallet: wallet.clone(),
+            tx: tx.clone(),
+            flashblocks_waited: 0,
+            attempts: vec![SubmissionAttempt {
+                kind: SubmissionAttemptKind::Original,
+                tx_hash: leg_hash,
+                max_fee_per_gas: tx.max_fee_per_gas,
+                max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+                block_number: opportunity.block_number,
+                flashblock_index: opportunity.flashblock_index,
+                error: None,
+            }],
+            abandoned: false,
+        });
+    }
+
+    /// Hand the first `sent` legs of a bundle to the gas margins and the fee controller
+    fn track_sent_bundle(
+        &self,
+        opportunity: &MevOpportunity,
+        prepared: &[(TxEnvelope, Option<TxEip1559>)],
+        signed_legs: &[(B256, String)],
+        sent: usize,
+    ) {
+        // Feed the landed gas of the backrun leg back into its config's gas margin
+        if let (Some(config), Some(simulated_gas)) = (&opportunity.processor_config, opportunity.simulated_gas_used) {
+            let backrun_leg = prepared.iter().zip(signed_legs).enumerate()
+                .filter_map(|(index, ((_, unsigned), (leg_hash, _)))| unsigned.as_ref().map(|tx| (index, *leg_hash, tx.gas_limit)))
+                .last();
+            if let Some((_, leg_hash, gas_limit)) = backrun_leg.filter(|(index, ..)| *index < sent) {
+                self.gas_limit_estimator.track(leg_hash, &config.name, simulated_gas, gas_limit);
             }
+        }
+
+        // Hand every leg we paid for to the fee controller so the bundle's outcome tunes the multiplier
+        if let Some(controller) = &self.fee_controller {
+            let legs: Vec<TrackedLeg> = prepared[..sent].iter().zip(signed_legs)
+                .filter_map(|((_, unsigned), (tx_hash, _))| {
+                    unsigned.as_ref().map(|tx| TrackedLeg { tx_hash: *tx_hash, max_fee_per_gas: tx.max_fee_per_gas })
+                })
+                .collect();
+            controller.track_submission(&legs, &opportunity.strategy, opportunity.block_number, opportunity.expected_profit);
+        }
+    }
+
+    /// Tracker matching the flashblock stream against our submissions
+    pub fn inclusion_tracker(&self) -> &Arc<InclusionTracker> {
+        &self.inclusion_tracker
+    }
+    
+    /// Gas limit estimator whose margins learn from our landed transactions
+    pub fn gas_limit_estimator(&self) -> &Arc<GasLimitEstimator> {
+        &self.gas_limit_estimator
+    }
+
+    /// Feed a received flashblock to sequencer tracking, the inclusion tracker and the gas
+    /// margins; returns our submissions that landed in it
+    pub fn observe_flashblock(&self, event: &FlashblocksEvent) -> Vec<InclusionRecord> {
+        self.sequencer_service.observe_flashblock(event);
+        let inclusions = self.inclusion_tracker.observe_flashblock(event);
+        self.gas_limit_estimator.observe_flashblock(event);
+        inclusions
+    }
+
+    /// Submissions still awaiting inclusion, for persisting across a restart
+    pub fn pending_state(&self) -> PendingState {
+        let mut submissions: Vec<PersistedSubmission> = self.pending.iter()
+            .map(|entry| {
+                let (wallet, nonce) = *entry.key();
+                let pending = entry.value();
+                PersistedSubmission {
+                    wallet,
+                    nonce,
+                    strategy: pending.opportunity.strategy.clone(),
+                    block_number: pending.opportunity.block_number,
+                    tx_hashes: pending.attempts.iter().map(|attempt| attempt.tx_hash).collect(),
+                    max_fee_per_gas: pending.tx.max_fee_per_gas,
+                }
+            })
+            .collect();
+        submissions.sort_by_key(|submission| (submission.wallet, submission.nonce));
+        PendingState::new(submissions)
+    }
+
+    /// Reconcile the pending submissions a previous run persisted against the chain
+    ///
+    /// Nonces the chain has moved past are done. Outstanding ones may still land, so their
+    /// transactions are watched for inclusion and their wallets sit out the failure cooldown
+    /// instead of racing the old transactions for the same nonce.
+    pub fn reconcile_pending_state<S: StateSource>(&self, path: &Path, source: &S) -> Result<()> {
+        let Some(state) = PendingState::load(path)? else {
+            return Ok(());
         };
+        let saved_at = state.saved_at;
+        let next_nonces = state.next_nonces.clone();
+        let latest = source.latest_state()?;
+        let (resolved, outstanding) = state.reconcile(|wallet| {
+            Ok(latest.basic_ref(*wallet)?.map(|account| account.nonce).unwrap_or(0))
+        })?;
 
-        // Get current block header for gas estimation
-        let latest_block = provider.best_block_number()?;
-        let header = provider.header_by_number(latest_block)?
-            .ok_or_else(|| eyre::eyre!("Failed to get latest block header"))?;
+        for submission in &outstanding {
+            warn!(
+                wallet = %redact_address(&submission.wallet),
+                nonce = submission.nonce,
+                strategy = %submission.strategy,
+                block = submission.block_number,
+                attempts = submission.tx_hashes.len(),
+                next_nonce = ?next_nonces.get(&submission.wallet),
+                "Submission from previous run still pending"
+            );
+            for tx_hash in &submission.tx_hashes {
+                self.inclusion_tracker.track(*tx_hash, &submission.strategy, None, None);
+            }
+            self.inclusion_tracker.track_nonce(submission.nonce);
+            self.wallet_failures.insert(submission.wallet, Instant::now());
+        }
+        info!(
+            path = %path.display(),
+            saved_at = saved_at,
+            resolved = resolved.len(),
+            outstanding = outstanding.len(),
+            "Reconciled pending submissions from previous run"
+        );
+
+        // Reconciled once; a later shutdown writes a fresh state
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    /// Re-evaluate submissions that have not landed after the latest flashblock
+    ///
+    /// A nonce is resolved once any of its attempts lands or the wallet's nonce moves past it.
+    /// Once it has waited `watchdog_flashblocks` (0 only resolves), the opportunity's stuck legs
+    /// are fee-bumped and simulated together on the fresh state: if they still succeed within
+    /// the expected profit they replace the originals, otherwise each nonce is released with a
+    /// zero-value self-transfer.
+    pub async fn watch_pending<S: StateSource>(
+        &self,
+        landed: &HashSet<B256>,
+        snapshot: &FlashblockStateSnapshot,
+        source: &S,
+    ) {
+        // Overlapping runs would race on taking entries out of `pending` and putting them back
+        let _watchdog = self.watchdog.lock().await;
+
+        let mut keys: Vec<(Address, u64)> = self.pending.iter().map(|entry| *entry.key()).collect();
+        keys.sort_unstable();
+
+        let mut due = Vec::new();
+        for key in keys {
+            let (wallet_address, nonce) = key;
+            let Some(mut pending) = self.pending.get_mut(&key) else { continue };
+
+            let landed_attempt = pending.attempts.iter().find(|a| landed.contains(&a.tx_hash)).map(|a| a.kind);
+            let nonce_consumed = snapshot.account_changes.get(&wallet_address)
+                .map(|account| account.nonce > nonce)
+                .unwrap_or(false);
+            if landed_attempt.is_some() || nonce_consumed {
+                debug!(
+                    wallet = %redact_address(&wallet_address),
+                    nonce = nonce,
+                    landed = ?landed_attempt,
+                    attempts = pending.attempts.len(),
+                    "Pending submission resolved"
+                );
+                drop(pending);
+                self.pending.remove(&key);
+                continue;
+            }
+
+            if self.config.watchdog_flashblocks == 0 || pending.abandoned {
+                continue;
+            }
+            pending.flashblocks_waited += 1;
+            if pending.flashblocks_waited >= self.config.watchdog_flashblocks {
+                due.push(key);
+            }
+        }
+
+        let mut handled = HashSet::new();
+        for key in due {
+            if handled.contains(&key) {
+                continue;
+            }
+            let (wallet_address, nonce) = key;
+            let Some(scan_id) = self.pending.get(&key).map(|pending| pending.opportunity.scan_id.clone()) else {
+                continue;
+            };
 
-        // Determine gas limit
-        let gas_limit = if let crate::mev_bundle_types::BundleTransaction::Unsigned { gas_limit, .. } = bundle_tx {
-            info!(gas_limit = *gas_limit, source = "bundle", "Using gas limit from bundle");
-            *gas_limit
-        } else if let Some(simulated_gas) = opportunity.simulated_gas_used {
+            // Our later legs of the same opportunity depend on this one, so they are
+            // re-evaluated with it
+            let mut end = nonce + 1;
+            while self.pending.get(&(wallet_address, end)).is_some_and(|pending| pending.opportunity.scan_id == scan_id) {
+                end += 1;
+            }
+            handled.extend((nonce..end).map(|n| (wallet_address, n)));
+
+            // Keep new submissions off these nonces while their entries are out of the map
+            let _held = self.hold_nonces(wallet_address, nonce..end);
+            let (group_keys, mut legs): (Vec<(Address, u64)>, Vec<PendingSubmission>) = (nonce..end)
+                .filter_map(|n| self.pending.remove(&(wallet_address, n)))
+                .unzip();
+
+            let attempts_made = legs[0].attempts.len();
+            if attempts_made > self.config.max_replacements as usize {
+                warn!(
+                    wallet = %redact_address(&wallet_address),
+                    nonce = nonce,
+                    legs = legs.len(),
+                    strategy = %legs[0].opportunity.strategy,
+                    attempts = attempts_made,
+                    "Giving up on stuck submission"
+                );
+                // Any attempt may still land, so the nonces stay held until the chain passes them
+                for (key, mut pending) in group_keys.into_iter().zip(legs) {
+                    pending.abandoned = true;
+                    self.pending.insert(key, pending);
+                }
+                continue;
+            }
+
+            for pending in &mut legs {
+                pending.flashblocks_waited = 0;
+            }
+            let attempts = self.replace_or_cancel(&mut legs, snapshot, source).await;
+            for ((key, mut pending), attempt) in group_keys.into_iter().zip(legs).zip(attempts) {
+                self.log_submission_attempt(&pending, &attempt);
+                pending.attempts.push(attempt);
+                self.pending.insert(key, pending);
+            }
+        }
+    }
+
+    /// Submit replacements for an opportunity's stuck legs if they are still profitable
+    /// together, else a cancellation for each of their nonces
+    async fn replace_or_cancel<S: StateSource>(
+        &self,
+        legs: &mut [PendingSubmission],
+        snapshot: &FlashblockStateSnapshot,
+        source: &S,
+    ) -> Vec<SubmissionAttempt> {
+        let bump = self.config.fee_bump_percent.max(MIN_REPLACEMENT_BUMP_PERCENT);
+        let bumped_fees: Vec<(u128, u128)> = legs.iter()
+            .map(|pending| {
+                let bumped_priority = pending.tx.max_priority_fee_per_gas * (100 + bump) / 100 + 1;
+                let bumped_max_fee = (pending.tx.max_fee_per_gas * (100 + bump) / 100 + 1)
+                    .max(snapshot.base_fee + bumped_priority);
+                (bumped_priority, bumped_max_fee)
+            })
+            .collect();
+
+        let already_cancelling = legs[0].attempts.last()
+            .map(|a| a.kind == SubmissionAttemptKind::Cancellation)
+            .unwrap_or(false);
+
+        // Candidate replacement: the same legs with bumped fees, run in order on the fresh state
+        let mut replacement = None;
+        if !already_cancelling {
+            let mut signed = Vec::with_capacity(legs.len());
+            for (pending, (bumped_priority, bumped_max_fee)) in legs.iter().zip(&bumped_fees) {
+                let mut tx = pending.tx.clone();
+                tx.max_priority_fee_per_gas = *bumped_priority;
+                tx.max_fee_per_gas = *bumped_max_fee;
+                match Self::sign_leg(&pending.wallet, tx).await {
+                    Ok(leg) => signed.push(leg),
+                    Err(e) => {
+                        debug!(error = %e, "Failed to sign replacement");
+                        break;
+                    }
+                }
+            }
+
+            if signed.len() == legs.len() {
+                let envelopes: Vec<TxEnvelope> = signed.iter().map(|(signed_tx, _)| signed_tx.clone()).collect();
+                let sender = (legs[0].wallet.address(), legs[0].tx.nonce);
+                match self.bundle_simulator.simulate(source, snapshot, &envelopes, Some(sender)) {
+                    Ok(results) => {
+                        let fee = results.iter().zip(&signed)
+                            .map(|(result, (_, tx))| U256::from(result.gas_used as u128 * tx.max_fee_per_gas))
+                            .fold(U256::ZERO, |total, fee| total + fee);
+                        let expected_profit = legs[0].opportunity.expected_profit;
+                        if fee < expected_profit {
+                            replacement = Some(signed);
+                        } else {
+                            debug!(fee = %fee, expected_profit = %expected_profit, "Replacement no longer profitable");
+                        }
+                    }
+                    Err(e) => debug!(error = %e, "Replacement fails on fresh state"),
+                }
+            }
+        }
+
+        let (kind, signed) = match replacement {
+            Some(signed) => (SubmissionAttemptKind::Replacement, signed.into_iter().map(Ok).collect::<Vec<_>>()),
+            None => {
+                let mut cancellations = Vec::with_capacity(legs.len());
+                for (pending, (bumped_priority, bumped_max_fee)) in legs.iter().zip(&bumped_fees) {
+                    let cancel = TxEip1559 {
+                        chain_id: pending.tx.chain_id,
+                        nonce: pending.tx.nonce,
+                        gas_limit: 21_000,
+                        max_fee_per_gas: *bumped_max_fee,
+                        max_priority_fee_per_gas: *bumped_priority,
+                        to: alloy_primitives::TxKind::Call(pending.wallet.address()),
+                        value: U256::ZERO,
+                        access_list: Default::default(),
+                        input: Default::default(),
+                    };
+                    cancellations.push(Self::sign_leg(&pending.wallet, cancel).await);
+                }
+                (SubmissionAttemptKind::Cancellation, cancellations)
+            }
+        };
+
+        // Sent in nonce order so no leg is queued behind a gap
+        let mut attempts = Vec::with_capacity(legs.len());
+        for ((pending, (bumped_priority, bumped_max_fee)), signed) in legs.iter_mut().zip(&bumped_fees).zip(signed) {
+            let mut attempt = SubmissionAttempt {
+                kind,
+                tx_hash: B256::ZERO,
+                max_fee_per_gas: *bumped_max_fee,
+                max_priority_fee_per_gas: *bumped_priority,
+                block_number: snapshot.block_number,
+                flashblock_index: snapshot.flashblock_index,
+                error: None,
+            };
+
+            match signed {
+                Ok((signed_tx, tx)) => {
+                    let (tx_hash, signed_hex) = Self::encode_leg(&signed_tx);
+                    attempt.tx_hash = tx_hash;
+                    if let Err(e) = self.sequencer_service.send_transaction(&signed_hex).await {
+                        attempt.error = Some(e.to_string());
+                    }
+                    pending.tx = tx;
+                }
+                Err(e) => attempt.error = Some(e.to_string()),
+            }
+            attempts.push(attempt);
+        }
+
+        attempts
+    }
+
+    /// Record a watchdog attempt against the original opportunity
+    fn log_submission_attempt(&self, pending: &PendingSubmission, attempt: &SubmissionAttempt) {
+        use std::fs::OpenOptions;
+        use std::io::Write;
+
+        let original = &pending.attempts[0];
+        info!(
+            strategy = %pending.opportunity.strategy,
+            scan_id = %pending.opportunity.scan_id,
+            original_tx_hash = %original.tx_hash,
+            kind = ?attempt.kind,
+            tx_hash = %attempt.tx_hash,
+            nonce = pending.tx.nonce,
+            attempt = pending.attempts.len(),
+            max_fee_per_gas = attempt.max_fee_per_gas,
+            max_priority_fee_per_gas = attempt.max_priority_fee_per_gas,
+            error = ?attempt.error,
+            "Watchdog acted on stuck submission"
+        );
+
+        let Some(path) = &self.config.replacement_log else {
+            return;
+        };
+        let record = serde_json::json!({
+            "timestamp": chrono::Utc::now().format("%Y-%m-%d %H:%M:%S%.3f UTC").to_string(),
+            "scan_id": pending.opportunity.scan_id,
+            "strategy": pending.opportunity.strategy,
+            "opportunity_block_number": pending.opportunity.block_number,
+            "original_tx_hash": format!("{:?}", original.tx_hash),
+            "attempt": pending.attempts.len(),
+            "kind": format!("{:?}", attempt.kind),
+            "tx_hash": format!("{:?}", attempt.tx_hash),
+            "nonce": pending.tx.nonce,
+            "max_fee_per_gas": attempt.max_fee_per_gas.to_string(),
+            "max_priority_fee_per_gas": attempt.max_priority_fee_per_gas.to_string(),
+            "block_number": attempt.block_number,
+            "flashblock_index": attempt.flashblock_index,
+            "error": attempt.error,
+        });
+
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", record));
+        if let Err(e) = result {
+            error!(error = ?e, "Failed to log submission attempt");
+        }
+    }
+
+    /// Sign an EIP-1559 leg, returning the envelope and the transaction as signed
+    pub(crate) async fn sign_leg(wallet: &SharedSigner, mut tx: TxEip1559) -> Result<(TxEnvelope, TxEip1559)> {
+        let signature = wallet.sign_transaction(&mut tx).await?;
+        // The signer may fill fields (chain id) in place; the envelope must wrap exactly the
+        // transaction that was signed so its hash is the one the chain will report
+        let signed_tx = TxEnvelope::Eip1559(tx.clone().into_signed(signature));
+        Ok((signed_tx, tx))
+    }
+
+    /// Hash and 0x-prefixed raw encoding of a signed leg
+    fn encode_leg(tx: &TxEnvelope) -> (B256, String) {
+        (*tx.tx_hash(), format!("0x{}", hex::encode(tx.encoded_2718())))
+    }
+
+    /// Preconditions for the opportunity's first leg, if conditional submission is enabled
+    fn build_conditional(&self, opportunity: &MevOpportunity) -> Option<TransactionConditional> {
+        if !self.config.conditional_submission || opportunity.known_accounts.is_empty() {
+            return None;
+        }
+
+        let conditional = TransactionConditional {
+            known_accounts: opportunity.known_accounts.clone(),
+            block_number_min: Some(opportunity.block_number),
+            block_number_max: Some(opportunity.block_number + self.config.conditional_block_range),
+            ..Default::default()
+        };
+        debug!(
+            strategy = %opportunity.strategy,
+            accounts = conditional.known_accounts.len(),
+            slots = conditional.known_accounts.values().map(|slots| slots.len()).sum::<usize>(),
+            block_number_max = ?conditional.block_number_max,
+            "Built transaction conditional"
+        );
+        Some(conditional)
+    }
+
+    /// Gas limit for an unsigned leg that did not carry one
+    fn fallback_gas_limit(&self, opportunity: &MevOpportunity, data_len: usize, leg_count: usize) -> u64 {
+        // Simulated gas covers the whole bundle, so it only applies to single-leg bundles
+        if let (Some(simulated_gas), 1) = (opportunity.simulated_gas_used, leg_count) {
             // Use simulated gas with a buffer
             let buffered_gas = (simulated_gas as f64 * 1.2) as u64;
             info!(
@@ -187,7 +1064,7 @@ impl TransactionService {
         } else {
             // Estimate gas based on the transaction complexity
             // For MEV transactions, we typically need more gas than simple transfers
-            let estimated = match data.len() {
+            let estimated = match data_len {
                 0..=4 => 21_000u64,           // Simple transfer
                 5..=100 => 100_000u64,        // Simple contract call
                 101..=500 => 200_000u64,      // Complex contract call
@@ -196,20 +1073,30 @@ impl TransactionService {
             info!(
                 gas_limit = estimated,
                 source = "estimated",
-                data_len = data.len(),
+                data_len = data_len,
                 "Estimated gas limit based on calldata size"
             );
             estimated
-        };
+        }
+    }
 
-        // Calculate gas pricing from actual block header
-        let base_fee = header.base_fee_per_gas().unwrap_or(1_000_000) as u128;
-        
+    /// EIP-1559 fees for an opportunity: 5% of the expected profit per simulated gas, scaled
+    /// by the strategy's multiplier and a profit-based modifier
+    ///
+    /// Workers quote once and simulate with the result; the submission sends the same fees.
+    pub async fn quote_fees(
+        &self,
+        strategy: &str,
+        processor_config: Option<&crate::backrun_analyzer::TokenPairProcessorConfig>,
+        expected_profit: U256,
+        simulated_gas_used: Option<u64>,
+        base_fee: u128,
+    ) -> FeeQuote {
         // Dynamic priority fee: allocate 5% of profit to gas fees
-        let base_priority_fee = if let Some(simulated_gas) = opportunity.simulated_gas_used {
+        let base_priority_fee = if let Some(simulated_gas) = simulated_gas_used {
             if simulated_gas > 0 {
                 // 5% of profit allocated to fees, divided by gas used = priority fee per gas
-                let profit_wei = opportunity.expected_profit.as_limbs()[0] as u128;
+                let profit_wei = expected_profit.saturating_to::<u128>();
                 let fee_budget = profit_wei * 5 / 100; // 5%
                 let priority_per_gas = fee_budget / (simulated_gas as u128);
                 
@@ -229,17 +1116,17 @@ impl TransactionService {
         };
         
         // Apply dynamic multiplier from Redis, fallback to static config
-        let dynamic_multiplier = self.get_dynamic_multiplier(&opportunity.strategy).await;
+        let dynamic_multiplier = self.get_dynamic_multiplier(strategy).await;
         let base_multiplier = if let Some(multiplier) = dynamic_multiplier {
             multiplier
-        } else if let Some(ref config) = opportunity.processor_config {
+        } else if let Some(config) = processor_config {
             config.priority_fee_multiplier.unwrap_or(10000)
         } else {
             10000 // Default 1x
         };
         
         // Calculate profit-based modifier (1x to 10x based on profit)
-        let profit_eth = opportunity.expected_profit.as_limbs()[0] as f64 / 1e18;
+        let profit_eth: f64 = format_ether(expected_profit).parse().unwrap_or_default();
         let profit_modifier = if profit_eth < 0.00001 {
             10000  // 1.0x for tiny profits (< 0.00001 ETH)
         } else if profit_eth < 0.0001 {
@@ -267,204 +1154,307 @@ impl TransactionService {
         let max_priority_fee_per_gas = priority_fee;
         let max_fee_per_gas = (base_fee * multiplier / 100) + priority_fee;
         
-        info!(
+        debug!(
+            strategy = strategy,
             base_fee_wei = base_fee,
-            base_fee_gwei = base_fee as f64 / 1e9,
             base_priority_fee_wei = base_priority_fee,
-            base_priority_fee_gwei = base_priority_fee as f64 / 1e9,
             priority_fee_wei = priority_fee,
-            priority_fee_gwei = priority_fee as f64 / 1e9,
             multiplier_source = if dynamic_multiplier.is_some() { "Redis" } else { "Config" },
             base_multiplier = format!("{}x", base_multiplier as f64 / 10000.0),
             profit_modifier = format!("{}x", profit_modifier as f64 / 10000.0),
             profit_eth = format!("{:.6}", profit_eth),
             final_multiplier = format!("{}x", final_multiplier as f64 / 10000.0),
             max_fee_per_gas_wei = max_fee_per_gas,
-            max_fee_per_gas_gwei = max_fee_per_gas as f64 / 1e9,
-            profit_allocation = "5%",
-            simulated_gas = ?opportunity.simulated_gas_used,
-            expected_profit_wei = %opportunity.expected_profit,
+            simulated_gas = ?simulated_gas_used,
+            expected_profit_wei = %expected_profit,
             "Calculated dynamic gas pricing with profit-based adjustment"
         );
 
-        // Build the transaction
-        let tx = TxEip1559 {
-            chain_id: self.config.chain_id,
-            nonce,
-            gas_limit,
-            max_fee_per_gas,
-            max_priority_fee_per_gas,
-            to: alloy_primitives::TxKind::Call(to),
-            value,
-            access_list: Default::default(),
-            input: data.clone(),
-        };
-
-        info!(
-            to = %to,
-            value = %value,
-            value_hex = %format!("0x{:x}", value),
-            gas_limit = gas_limit,
-            max_fee_per_gas = max_fee_per_gas,
-            max_priority_fee_per_gas = max_priority_fee_per_gas,
-            nonce = nonce,
-            chain_id = self.config.chain_id,
-            input_len = data.len(),
-            "Built EIP-1559 transaction"
-        );
-
-        // Sign the transaction
-        info!("Starting transaction signing");
-        let mut tx_mut = tx.clone();
-        let signature = wallet.sign_transaction(&mut tx_mut).await?;
-        let tx_hash = tx.signature_hash();
-        let signed_tx = TxEnvelope::Eip1559(Signed::new_unchecked(tx, signature, tx_hash));
-        // Use EIP-2718 encoding for typed transactions
-        let signed_bytes = signed_tx.encoded_2718();
-        let signed_hex = format!("0x{}", hex::encode(&signed_bytes));
-
-        info!(
-            strategy = %opportunity.strategy,
-            tx_hash = %signed_tx.tx_hash(),
-            signed_size = signed_bytes.len(),
-            signed_hex_preview = %format!("{}...{}", 
-                &signed_hex[..20.min(signed_hex.len())],
-                &signed_hex[signed_hex.len().saturating_sub(20)..]
-            ),
-            "Signed MEV transaction"
-        );
-
-        // Check if dry run mode
-        if self.config.dry_run {
-            info!("DRY RUN MODE - Not submitting transaction");
-            self.log_dry_run(&opportunity, &tx_mut, &signed_hex).await;
-            return Ok(None);
-        }
+        FeeQuote { max_fee_per_gas, max_priority_fee_per_gas }
+    }
 
-        // Submit to sequencer
-        info!("Submitting transaction to sequencer");
-        match self.sequencer_service.send_transaction(&signed_hex).await {
-            Ok(tx_hash) => {
-                let elapsed = start_time.elapsed();
+    /// Get dynamic multiplier from Redis for a strategy
+    async fn get_dynamic_multiplier(&self, strategy_name: &str) -> Option<u32> {
+        let key = self.redis.key(&format!("multiplier:{}", strategy_name));
+        let read_key = key.clone();
+        
+        let multiplier = match self.redis.run(|mut conn| async move { conn.get::<_, Option<u32>>(&read_key).await }).await {
+            Ok(Some(multiplier)) => {
+                // Sanity check: ensure multiplier is within reasonable bounds
+                // Min: 2000 (0.2x), Max: 100000 (10x)
+                if multiplier >= 2000 && multiplier <= 100000 {
+                    info!(
+                        strategy = strategy_name,
+                        multiplier = multiplier,
+                        multiplier_x = format!("{}x", multiplier as f64 / 10000.0),
+                        "Using dynamic multiplier from Redis"
+                    );
+                    return Some(multiplier);
+                }
+                warn!(
+                    strategy = strategy_name,
+                    invalid_multiplier = multiplier,
+                    "Invalid multiplier in Redis, using default"
+                );
+                10000
+            }
+            Ok(None) => {
+                // Key doesn't exist, initialize with default
                 info!(
-                    block = opportunity.block_number,
-                    flashblock = opportunity.flashblock_index,
-                    strategy = %opportunity.strategy,
-                    tx_hash = %tx_hash,
-                    elapsed_ms = elapsed.as_millis(),
-                    expected_profit = %opportunity.expected_profit,
-                    "🎯💰 MEV JACKPOT SUBMITTED! 🎰🚀 Profit incoming: {} wei! 💎🔥 Strategy {} STRIKES GOLD! 🏆✨",
-                    opportunity.expected_profit,
-                    opportunity.strategy
+                    strategy = strategy_name,
+                    "No multiplier in Redis, initializing with default 1x"
                 );
-                Ok(Some(tx_hash))
+                10000
             }
             Err(e) => {
-                error!(
-                    block = opportunity.block_number,
-                    flashblock = opportunity.flashblock_index,
-                    strategy = %opportunity.strategy,
-                    error = ?e,
-                    error_message = %e,
-                    "Failed to submit MEV transaction"
+                debug!(
+                    strategy = strategy_name,
+                    error = %e,
+                    "Failed to get multiplier from Redis"
                 );
-                Err(e)
+                return None;
             }
-        }
-    }
-
-    /// Initialize Redis connection for dynamic multipliers
-    async fn init_redis_connection() -> Result<ConnectionManager> {
-        let redis_host = std::env::var("REDIS_LOCAL_HOST").unwrap_or_else(|_| "localhost".to_string());
-        let redis_port = std::env::var("REDIS_LOCAL_PORT")
-            .unwrap_or_else(|_| "6379".to_string())
-            .parse::<u16>()
-            .unwrap_or(6379);
-        let redis_password = std::env::var("REDIS_LOCAL_PASSWORD").unwrap_or_default();
-        
-        let redis_url = if redis_password.is_empty() {
-            format!("redis://{}:{}/", redis_host, redis_port)
-        } else {
-            format!("redis://:{}@{}:{}/", redis_password, redis_host, redis_port)
         };
         
-        let client = RedisClient::open(redis_url)?;
-        let conn = ConnectionManager::new(client).await?;
-        Ok(conn)
+        // Set default in Redis
+        let _ = self.redis.run(|mut conn| async move { conn.set::<_, _, ()>(&key, multiplier).await }).await;
+        Some(multiplier)
     }
     
-    /// Get dynamic multiplier from Redis for a strategy
-    async fn get_dynamic_multiplier(&self, strategy_name: &str) -> Option<u32> {
-        let conn_guard = self.redis_conn.read().await;
-        if let Some(conn) = conn_guard.as_ref() {
-            let mut conn = conn.clone();
-            let key = format!("mev:multiplier:{}", strategy_name);
-            
-            match conn.get::<_, Option<u32>>(&key).await {
-                Ok(Some(multiplier)) => {
-                    // Sanity check: ensure multiplier is within reasonable bounds
-                    // Min: 2000 (0.2x), Max: 100000 (10x)
-                    if multiplier >= 2000 && multiplier <= 100000 {
-                        info!(
-                            strategy = strategy_name,
-                            multiplier = multiplier,
-                            multiplier_x = format!("{}x", multiplier as f64 / 10000.0),
-                            "Using dynamic multiplier from Redis"
-                        );
-                        Some(multiplier)
-                    } else {
-                        warn!(
-                            strategy = strategy_name,
-                            invalid_multiplier = multiplier,
-                            "Invalid multiplier in Redis, using default"
-                        );
-                        // Set default in Redis
-                        let _ = conn.set::<_, _, ()>(&key, 10000).await;
-                        Some(10000)
+    /// Wallet for an opportunity: the one its bundle was simulated from, else a fresh selection
+    async fn get_next_wallet(&self, opportunity: &MevOpportunity) -> Result<SharedSigner> {
+        let simulated_from = opportunity.bundle.transactions.iter().find_map(|tx| match tx {
+            crate::mev_bundle_types::BundleTransaction::Unsigned { from, .. } => Some(*from),
+            _ => None,
+        });
+        let target = opportunity.bundle.transactions.first().and_then(|tx| tx.to());
+        if let Some(wallet) = simulated_from.and_then(|from| self.wallet_service.wallet_by_address(&from)) {
+            // The worker only previewed the selection; it takes effect once we submit
+            self.commit_wallet(opportunity.processor_config.as_ref(), target, &wallet).await;
+            return Ok(wallet);
+        }
+
+        self.select_wallet(opportunity.processor_config.as_ref(), target).await
+    }
+
+    /// Select a wallet using the processor's strategy (or the global one), skipping wallets
+    /// below the minimum balance
+    pub async fn select_wallet(
+        &self,
+        processor_config: Option<&crate::backrun_analyzer::TokenPairProcessorConfig>,
+        target: Option<Address>,
+    ) -> Result<SharedSigner> {
+        let wallet = self.preview_wallet(processor_config, target).await?;
+        self.commit_wallet(processor_config, target, &wallet).await;
+        Ok(wallet)
+    }
+
+    fn wallet_strategy(&self, processor_config: Option<&crate::backrun_analyzer::TokenPairProcessorConfig>) -> WalletStrategy {
+        processor_config
+            .and_then(|config| config.wallet_strategy)
+            .unwrap_or(self.config.wallet_strategy)
+    }
+
+    /// The wallet [`Self::select_wallet`] would pick, without advancing the round-robin index
+    /// or assigning a sticky wallet
+    ///
+    /// Workers simulate from this wallet on every search, profitable or not.
+    pub async fn preview_wallet(
+        &self,
+        processor_config: Option<&crate::backrun_analyzer::TokenPairProcessorConfig>,
+        target: Option<Address>,
+    ) -> Result<SharedSigner> {
+        match self.wallet_strategy(processor_config) {
+            WalletStrategy::Random => {
+                self.wallet_service.get_random_wallet()
+            }
+            WalletStrategy::RoundRobin => {
+                let index = *self.wallet_index.read().await;
+                self.wallet_service.next_funded_wallet(index).map(|(_, wallet)| wallet)
+            }
+            WalletStrategy::Default => {
+                self.wallet_service.next_funded_wallet(0).map(|(_, wallet)| wallet)
+            }
+            WalletStrategy::LeastPending => {
+                let mut pending_counts: HashMap<Address, usize> = HashMap::new();
+                for entry in self.pending.iter() {
+                    *pending_counts.entry(entry.key().0).or_default() += 1;
+                }
+                self.wallet_service.funded_wallets()
+                    .into_iter()
+                    .min_by_key(|wallet| {
+                        let address = wallet.address();
+                        (self.recently_failed(&address), pending_counts.get(&address).copied().unwrap_or(0))
+                    })
+                    .ok_or_else(|| eyre::eyre!("No wallets above the minimum balance"))
+            }
+            WalletStrategy::BalanceWeighted => {
+                let candidates = self.healthy_wallets();
+                self.wallet_service.pick_balance_weighted(&candidates)
+                    .ok_or_else(|| eyre::eyre!("No wallets above the minimum balance"))
+            }
+            WalletStrategy::StickyPerTarget => {
+                let target = processor_config
+                    .map(|config| config.contract_address)
+                    .or(target)
+                    .ok_or_else(|| eyre::eyre!("Opportunity has no target contract"))?;
+                let candidates = self.healthy_wallets();
+
+                if let Some(assigned) = self.sticky_wallets.get(&target).map(|entry| *entry) {
+                    if let Some(wallet) = candidates.iter().find(|wallet| wallet.address() == assigned) {
+                        return Ok(wallet.clone());
                     }
                 }
-                Ok(None) => {
-                    // Key doesn't exist, initialize with default
-                    info!(
-                        strategy = strategy_name,
-                        "No multiplier in Redis, initializing with default 1x"
-                    );
-                    let _ = conn.set::<_, _, ()>(&key, 10000).await;
-                    Some(10000)
+
+                // Spread targets: take the wallet holding the fewest assignments
+                candidates.into_iter()
+                    .min_by_key(|wallet| {
+                        let address = wallet.address();
+                        self.sticky_wallets.iter().filter(|entry| *entry.value() == address).count()
+                    })
+                    .ok_or_else(|| eyre::eyre!("No wallets above the minimum balance"))
+            }
+        }
+    }
+
+    /// Apply the side effects of selecting `wallet`: advance the round-robin index past it,
+    /// or assign it to the target under the sticky strategy
+    async fn commit_wallet(
+        &self,
+        processor_config: Option<&crate::backrun_analyzer::TokenPairProcessorConfig>,
+        target: Option<Address>,
+        wallet: &SharedSigner,
+    ) {
+        let address = wallet.address();
+        match self.wallet_strategy(processor_config) {
+            WalletStrategy::RoundRobin => {
+                if let Some(position) = self.wallet_service.get_addresses().iter().position(|a| *a == address) {
+                    *self.wallet_index.write().await = (position + 1) % self.wallet_service.wallet_count();
                 }
-                Err(e) => {
+            }
+            WalletStrategy::StickyPerTarget => {
+                let Some(target) = processor_config.map(|config| config.contract_address).or(target) else {
+                    return;
+                };
+                let healthy = self.healthy_wallets();
+                let reassign = self.sticky_wallets.get(&target)
+                    .map(|assigned| !healthy.iter().any(|w| w.address() == *assigned))
+                    .unwrap_or(true);
+                if reassign {
                     debug!(
-                        strategy = strategy_name,
-                        error = ?e,
-                        "Failed to get multiplier from Redis"
+                        target = %target,
+                        wallet = %redact_address(&address),
+                        "Assigned wallet to target"
                     );
-                    None
+                    self.sticky_wallets.insert(target, address);
                 }
             }
-        } else {
-            None
+            _ => {}
         }
     }
-    
-    /// Get the next wallet based on the configured strategy
-    async fn get_next_wallet(&self) -> Result<PrivateKeySigner> {
-        match self.config.wallet_strategy {
-            WalletStrategy::Random => {
-                self.wallet_service.get_random_wallet()
-            }
-            WalletStrategy::RoundRobin => {
-                let mut index = self.wallet_index.write().await;
-                let wallet = self.wallet_service.get_wallet(*index)?;
-                *index = (*index + 1) % self.wallet_service.wallet_count();
-                Ok(wallet)
+
+    /// Whether a wallet's last submission failed within the cooldown
+    fn recently_failed(&self, address: &Address) -> bool {
+        self.wallet_failures.get(address)
+            .map(|failed_at| failed_at.elapsed() < self.config.wallet_failure_cooldown)
+            .unwrap_or(false)
+    }
+
+    /// Funded wallets without a recent failure, or all funded wallets if every one failed recently
+    fn healthy_wallets(&self) -> Vec<SharedSigner> {
+        let funded = self.wallet_service.funded_wallets();
+        let healthy: Vec<SharedSigner> = funded.iter()
+            .filter(|wallet| !self.recently_failed(&wallet.address()))
+            .cloned()
+            .collect();
+        if healthy.is_empty() { funded } else { healthy }
+    }
+
+    /// Send top-up transfers from the treasury to wallets that fell below the minimum balance
+    pub async fn top_up_wallets<S: StateSource>(&self, snapshot: &FlashblockStateSnapshot, source: &S) {
+        let Some(treasury) = self.wallet_service.treasury().cloned() else {
+            return;
+        };
+        // Claiming starts each wallet's cooldown before anything is sent, so a concurrent
+        // flashblock cannot pick the same wallets
+        let top_ups = self.wallet_service.claim_top_ups();
+        if top_ups.is_empty() {
+            return;
+        }
+
+        let treasury_address = treasury.address();
+        let treasury_nonce = match snapshot.account_changes.get(&treasury_address) {
+            Some(account) => account.nonce,
+            None => match state_nonce(source, treasury_address) {
+                Ok(nonce) => nonce,
+                Err(e) => {
+                    error!(error = ?e, "Failed to read treasury nonce");
+                    for (wallet, _) in top_ups {
+                        self.wallet_service.record_top_up(wallet, false);
+                    }
+                    return;
+                }
+            },
+        };
+        // Concurrent top-ups take distinct treasury nonces
+        let reservation = self.reserve_nonces(treasury_address, treasury_nonce, top_ups.len() as u64);
+        let mut nonce = reservation.start();
+        let max_priority_fee_per_gas = 1_000_000u128; // 0.001 gwei
+        let max_fee_per_gas = snapshot.base_fee * 2 + max_priority_fee_per_gas;
+
+        for (wallet, amount) in top_ups {
+            let transfer = TxEip1559 {
+                chain_id: self.config.chain_id,
+                nonce,
+                gas_limit: 21_000,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to: alloy_primitives::TxKind::Call(wallet),
+                value: amount,
+                access_list: Default::default(),
+                input: Default::default(),
+            };
+
+            if self.config.dry_run {
+                info!(
+                    wallet = %redact_address(&wallet),
+                    amount_eth = %format_ether(amount),
+                    "🧪 DRY RUN: Would top up wallet from treasury"
+                );
+                continue;
             }
-            WalletStrategy::Default => {
-                self.wallet_service.get_wallet(0)
+
+            let result = match Self::sign_leg(&treasury, transfer).await {
+                Ok((signed_tx, _)) => {
+                    let (_, raw_tx) = Self::encode_leg(&signed_tx);
+                    self.sequencer_service.send_transaction(&raw_tx).await.map_err(eyre::Report::from)
+                }
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(tx_hash) => {
+                    info!(
+                        wallet = %redact_address(&wallet),
+                        treasury = %redact_address(&treasury_address),
+                        amount_eth = %format_ether(amount),
+                        tx_hash = %tx_hash,
+                        "💸 Sent wallet top-up"
+                    );
+                    self.wallet_service.record_top_up(wallet, true);
+                    nonce += 1;
+                }
+                Err(e) => {
+                    error!(
+                        wallet = %redact_address(&wallet),
+                        error = %e,
+                        "Failed to send wallet top-up"
+                    );
+                    self.wallet_service.record_top_up(wallet, false);
+                }
             }
         }
     }
 
-
     /// Log transaction details in dry run mode
     async fn log_dry_run(
         &self,
@@ -503,8 +1493,146 @@ impl TransactionService {
     }
 }
 
-/// Format wei value to ETH string
-fn format_ether(wei: U256) -> String {
-    let eth = wei.as_limbs()[0] as f64 / 1e18;
-    format!("{:.6}", eth)
-}
\ No newline at end of file
+/// Nonce of an account on the source's latest state
+fn state_nonce<S: StateSource>(source: &S, address: Address) -> Result<u64> {
+    Ok(source.latest_state()?.basic_ref(address)?.map(|account| account.nonce).unwrap_or(0))
+}
+
+#[async_trait]
+impl SubmissionWallets for TransactionService {
+    fn addresses(&self) -> Vec<Address> {
+        self.wallet_service.get_addresses()
+    }
+
+    async fn preview_sender(&self, processor_config: &crate::backrun_analyzer::TokenPairProcessorConfig) -> Result<Address> {
+        let wallet = self.preview_wallet(Some(processor_config), Some(processor_config.contract_address)).await?;
+        Ok(wallet.address())
+    }
+
+    fn next_nonce(&self, wallet: Address, state_nonce: u64) -> u64 {
+        TransactionService::next_nonce(self, wallet, state_nonce)
+    }
+
+    fn observe_state(&self, snapshot: &FlashblockStateSnapshot) {
+        self.wallet_service.observe_state(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas_limit_estimator::GasEstimatorConfig;
+    use crate::sequencer_service::SequencerConfig;
+    use crate::wallet_service::LocalKeySigner;
+    use alloy_consensus::transaction::SignerRecoverable;
+    use alloy_primitives::{keccak256, TxKind};
+
+    #[tokio::test]
+    async fn test_leg_hash_matches_encoded_transaction() {
+        // Hardhat account #0 (test key, do not use in production)
+        let signer: SharedSigner = Arc::new(LocalKeySigner::new(
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap(),
+        ));
+        let tx = TxEip1559 {
+            chain_id: 8453,
+            nonce: 7,
+            gas_limit: 300_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000,
+            to: TxKind::Call(Address::with_last_byte(0x42)),
+            value: U256::ZERO,
+            access_list: Default::default(),
+            input: vec![0x01, 0x02].into(),
+        };
+
+        let (signed_tx, signed) = TransactionService::sign_leg(&signer, tx).await.unwrap();
+        let (hash, raw) = TransactionService::encode_leg(&signed_tx);
+
+        assert_eq!(hash, keccak256(signed_tx.encoded_2718()));
+        assert_eq!(raw, format!("0x{}", hex::encode(signed_tx.encoded_2718())));
+        assert_ne!(hash, signed.signature_hash());
+        assert_eq!(signed_tx.recover_signer().unwrap(), signer.address());
+    }
+
+    fn test_service() -> TransactionService {
+        test_service_with(TransactionServiceConfig::default())
+    }
+
+    fn test_service_with(config: TransactionServiceConfig) -> TransactionService {
+        let redis = Arc::new(RedisHandle::disconnected(Default::default()));
+        let sequencer = SequencerService::new(
+            SequencerConfig { keepalive_interval: Duration::ZERO, ..Default::default() },
+        ).unwrap();
+        let wallets = WalletService::new(vec![
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string(),
+        ]).unwrap();
+        TransactionService::new(
+            config,
+            Arc::new(wallets),
+            Arc::new(sequencer),
+            None,
+            Arc::new(GasLimitEstimator::new(GasEstimatorConfig::default())),
+            redis,
+            reth_optimism_chainspec::BASE_MAINNET.clone(),
+        )
+    }
+
+    #[test]
+    fn test_concurrent_submissions_take_distinct_nonces() {
+        let service = test_service();
+        let wallet = Address::with_last_byte(1);
+
+        let first = service.reserve_nonces(wallet, 5, 2);
+        let second = service.reserve_nonces(wallet, 5, 1);
+        assert_eq!(first.nonces, 5..7);
+        assert_eq!(second.start(), 7);
+        assert_eq!(service.next_nonce(wallet, 5), 8);
+
+        // A nonce the watchdog is working on stays taken
+        drop(first);
+        let held = service.hold_nonces(wallet, 5..6);
+        assert_eq!(service.reserve_nonces(wallet, 5, 1).start(), 8);
+
+        drop(second);
+        drop(held);
+        assert_eq!(service.next_nonce(wallet, 5), 5);
+        assert!(service.nonce_reservations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sent_leg_holds_nonce_until_the_chain_passes_it() {
+        // The watchdog is off, yet the sent leg still keeps later submissions off its nonce
+        let service = test_service_with(TransactionServiceConfig { watchdog_flashblocks: 0, ..Default::default() });
+        let wallet = service.wallet_service.get_wallet(0).unwrap();
+        let tx = TxEip1559 { chain_id: 8453, nonce: 7, gas_limit: 21_000, ..Default::default() };
+        let leg = TransactionService::sign_leg(&wallet, tx).await.unwrap();
+        let leg_hash = *leg.0.tx_hash();
+        let opportunity = MevOpportunity {
+            block_number: 100,
+            flashblock_index: 0,
+            bundle: crate::mev_bundle_types::MevBundle::new(vec![], 100),
+            expected_profit: U256::from(1),
+            strategy: "Backrun_Test".to_string(),
+            simulated_gas_used: None,
+            last_flashblock_tx_hash: None,
+            scan_id: "100-0".to_string(),
+            processor_config: None,
+            state_snapshot: None,
+            known_accounts: Default::default(),
+        };
+
+        service.record_sent_leg(&opportunity, &wallet, &leg, leg_hash);
+        assert_eq!(service.next_nonce(wallet.address(), 7), 8);
+        assert_eq!(service.pending_state().submissions.len(), 1);
+
+        // Not resolved while the chain nonce is behind it, released once the nonce moves past
+        let source = crate::remote_state::RpcStateSource::new(&Default::default());
+        let mut snapshot = FlashblockStateSnapshot::new(100, 1, 0);
+        service.watch_pending(&HashSet::new(), &snapshot, &source).await;
+        assert_eq!(service.next_nonce(wallet.address(), 7), 8);
+        snapshot.account_changes.insert(wallet.address(), revm::state::AccountInfo { nonce: 8, ..Default::default() });
+        service.watch_pending(&HashSet::new(), &snapshot, &source).await;
+        assert_eq!(service.next_nonce(wallet.address(), 8), 8);
+        assert!(service.pending_state().submissions.is_empty());
+    }
+}
@@ -1,38 +1,268 @@
+use alloy_consensus::{transaction::SignerRecoverable, TxEip1559, TxEnvelope};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_network::TxSigner;
+use alloy_primitives::{Address, Signature, U256};
 use alloy_signer_local::PrivateKeySigner;
+use async_trait::async_trait;
+use dashmap::{mapref::entry::Entry, DashMap};
 use eyre::Result;
 use rand::Rng;
-use tracing::{debug, info};
+use revm::DatabaseRef;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+use zeroize::Zeroizing;
+
+use crate::config::WalletSection;
+use crate::flashblock_state::FlashblockStateSnapshot;
+use crate::state_source::StateSource;
+
+/// Shorten an address for logs, e.g. `0xf39F…2266`
+pub fn redact_address(address: &Address) -> String {
+    let full = address.to_string();
+    format!("{}…{}", &full[..6], &full[full.len() - 4..])
+}
+
+/// Signs transactions for a single address
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Address transactions are signed for
+    fn address(&self) -> Address;
+
+    /// Sign an EIP-1559 transaction
+    async fn sign_transaction(&self, tx: &mut TxEip1559) -> Result<Signature>;
+}
+
+/// Shared handle to a wallet's signer
+pub type SharedSigner = Arc<dyn Signer>;
+
+/// In-process key, from a raw private key or a decrypted keystore
+///
+/// The underlying signing key is zeroized on drop.
+pub struct LocalKeySigner {
+    signer: PrivateKeySigner,
+}
+
+impl LocalKeySigner {
+    pub fn new(signer: PrivateKeySigner) -> Self {
+        Self { signer }
+    }
+}
+
+impl std::fmt::Debug for LocalKeySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalKeySigner")
+            .field("address", &redact_address(&self.signer.address()))
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Signer for LocalKeySigner {
+    fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    async fn sign_transaction(&self, tx: &mut TxEip1559) -> Result<Signature> {
+        Ok(TxSigner::sign_transaction(&self.signer, tx).await?)
+    }
+}
+
+/// Signer that holds no key material, delegating to `eth_signTransaction` over HTTP JSON-RPC
+#[derive(Debug)]
+pub struct RemoteSigner {
+    url: String,
+    address: Address,
+    client: reqwest::Client,
+}
+
+impl RemoteSigner {
+    pub fn new(url: String, address: Address) -> Result<Self> {
+        let client = reqwest::ClientBuilder::new()
+            .timeout(Duration::from_secs(2))
+            .pool_idle_timeout(None)
+            .build()?;
+        Ok(Self { url, address, client })
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(&self, tx: &mut TxEip1559) -> Result<Signature> {
+        let request_body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_signTransaction",
+            "params": [{
+                "from": self.address,
+                "to": tx.to.to(),
+                "gas": format!("0x{:x}", tx.gas_limit),
+                "maxFeePerGas": format!("0x{:x}", tx.max_fee_per_gas),
+                "maxPriorityFeePerGas": format!("0x{:x}", tx.max_priority_fee_per_gas),
+                "value": format!("{:#x}", tx.value),
+                "input": format!("0x{}", hex::encode(&tx.input)),
+                "nonce": format!("0x{:x}", tx.nonce),
+                "chainId": format!("0x{:x}", tx.chain_id),
+                "type": "0x2",
+            }],
+            "id": 1
+        });
+
+        let response: serde_json::Value = self.client
+            .post(&self.url)
+            .json(&request_body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(eyre::eyre!("Remote signer error: {}", error));
+        }
+
+        // Clef/geth return `{ raw, tx }`, web3signer returns the raw transaction directly
+        let result = &response["result"];
+        let raw = result.as_str()
+            .or_else(|| result["raw"].as_str())
+            .ok_or_else(|| eyre::eyre!("Remote signer returned no raw transaction"))?;
+        let raw = hex::decode(raw.trim_start_matches("0x"))?;
+
+        let envelope = TxEnvelope::decode_2718(&mut raw.as_slice())
+            .map_err(|e| eyre::eyre!("Remote signer returned undecodable transaction: {}", e))?;
+        let signer = envelope.recover_signer()
+            .map_err(|e| eyre::eyre!("Remote signer returned invalid signature: {}", e))?;
+        if signer != self.address {
+            return Err(eyre::eyre!(
+                "Remote signer signed for {} instead of {}",
+                redact_address(&signer),
+                redact_address(&self.address)
+            ));
+        }
+
+        match envelope {
+            TxEnvelope::Eip1559(signed) if signed.tx() == tx => Ok(*signed.signature()),
+            _ => Err(eyre::eyre!("Remote signer returned a different transaction than requested")),
+        }
+    }
+}
+
+/// Per-wallet balance metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "mev.wallet")]
+pub struct WalletMetrics {
+    /// Last observed ETH balance
+    pub balance_eth: Gauge,
+    /// 1 while the wallet is below the minimum balance and excluded from selection
+    pub below_minimum: Gauge,
+    /// Times the wallet dropped below the minimum balance
+    pub low_balance_alerts_total: Counter,
+    /// Top-up transfers sent to this wallet
+    pub top_ups_total: Counter,
+    /// Top-up transfers that failed to sign or submit
+    pub top_up_failures_total: Counter,
+}
+
+/// Balance thresholds and treasury top-ups
+#[derive(Clone)]
+pub struct WalletBalanceConfig {
+    /// Wallets below this balance are excluded from selection
+    pub min_balance: U256,
+    /// Balance a top-up refills a wallet to
+    pub target_balance: U256,
+    /// Send top-ups from the treasury when a wallet falls below the minimum
+    pub top_up_enabled: bool,
+    /// Wallet that funds top-ups; never used for MEV submissions
+    pub treasury: Option<SharedSigner>,
+    /// Minimum time between top-ups of the same wallet, so an in-flight transfer is not repeated
+    pub top_up_cooldown: Duration,
+}
+
+impl Default for WalletBalanceConfig {
+    fn default() -> Self {
+        Self {
+            min_balance: U256::from(2_000_000_000_000_000u64), // 0.002 ETH
+            target_balance: U256::from(10_000_000_000_000_000u64), // 0.01 ETH
+            top_up_enabled: false,
+            treasury: None,
+            top_up_cooldown: Duration::from_secs(60),
+        }
+    }
+}
+
+impl WalletBalanceConfig {
+    /// Balance settings from the wallet section; a treasury keystore is decrypted with the
+    /// section's keystore password
+    pub fn from_config(wallet: &mut WalletSection) -> Result<Self> {
+        let treasury: Option<SharedSigner> = if let Some(key) = wallet.treasury_private_key.take() {
+            let signer = key.trim().trim_start_matches("0x").parse::<PrivateKeySigner>()
+                .map_err(|e| eyre::eyre!("Failed to parse treasury private key: {}", e))?;
+            Some(Arc::new(LocalKeySigner::new(signer)))
+        } else if let Some(path) = &wallet.treasury_keystore {
+            let password = wallet.keystore_password()?;
+            WalletService::load_keystores(std::slice::from_ref(path), password.as_bytes())?.into_iter().next()
+        } else {
+            None
+        };
+
+        Ok(Self {
+            min_balance: wallet.min_balance_wei,
+            target_balance: wallet.target_balance_wei,
+            top_up_enabled: wallet.top_up_enabled,
+            treasury,
+            top_up_cooldown: Duration::from_secs(wallet.top_up_cooldown_secs),
+        })
+    }
+}
+
+/// Convert wei to ETH for gauges and logs
+fn wei_to_eth(wei: U256) -> f64 {
+    u128::try_from(wei).unwrap_or(u128::MAX) as f64 / 1e18
+}
 
 /// Service for managing wallets and signing transactions
 pub struct WalletService {
-    wallets: Vec<PrivateKeySigner>,
+    wallets: Vec<SharedSigner>,
+    balance_config: WalletBalanceConfig,
+    /// Last known balance per wallet; wallets not yet observed are assumed funded
+    balances: DashMap<Address, U256>,
+    /// Last top-up sent per wallet
+    top_ups: DashMap<Address, Instant>,
+    metrics: HashMap<Address, WalletMetrics>,
 }
 
 impl WalletService {
     /// Create a new wallet service from private keys
-    pub fn new(private_keys: Vec<String>) -> Result<Self> {
+    pub fn new<K: Into<Zeroizing<String>>>(private_keys: Vec<K>) -> Result<Self> {
+        // Wipe the key strings once parsed
+        let private_keys: Vec<Zeroizing<String>> = private_keys.into_iter().map(Into::into).collect();
         if private_keys.is_empty() {
             return Err(eyre::eyre!("No private keys provided"));
         }
 
-        let mut wallets = Vec::new();
-        
+        let mut wallets: Vec<SharedSigner> = Vec::new();
+
         for (index, key) in private_keys.iter().enumerate() {
             // Remove 0x prefix if present
-            let clean_key = if key.starts_with("0x") {
-                &key[2..]
-            } else {
-                key
-            };
+            let clean_key = key.strip_prefix("0x").unwrap_or(key);
 
             match clean_key.parse::<PrivateKeySigner>() {
                 Ok(wallet) => {
                     info!(
                         index = index,
-                        address = %wallet.address(),
+                        address = %redact_address(&wallet.address()),
                         "Initialized wallet"
                     );
-                    wallets.push(wallet);
+                    wallets.push(Arc::new(LocalKeySigner::new(wallet)));
                 }
                 Err(e) => {
                     return Err(eyre::eyre!("Failed to parse private key at index {}: {}", index, e));
@@ -41,48 +271,303 @@ impl WalletService {
         }
 
         info!(count = wallets.len(), "Initialized wallets");
-        
-        Ok(Self { wallets })
-    }
 
-    /// Initialize from environment variables
-    pub fn from_env() -> Result<Self> {
-        let private_keys_env = std::env::var("WALLET_PRIVATE_KEYS")
-            .map_err(|_| eyre::eyre!("WALLET_PRIVATE_KEYS environment variable is required"))?;
+        Ok(Self::from_signers(wallets))
+    }
 
-        // Parse comma-separated private keys
-        let private_keys: Vec<String> = private_keys_env
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
+    /// Create from already constructed signers (may be empty)
+    pub fn from_signers(wallets: Vec<SharedSigner>) -> Self {
+        let metrics = wallets.iter()
+            .map(|wallet| {
+                let address = wallet.address();
+                (address, WalletMetrics::new_with_labels(&[("wallet", redact_address(&address))]))
+            })
             .collect();
+        Self {
+            wallets,
+            balance_config: WalletBalanceConfig::default(),
+            balances: DashMap::new(),
+            top_ups: DashMap::new(),
+            metrics,
+        }
+    }
 
-        if private_keys.is_empty() {
-            return Err(eyre::eyre!("No valid private keys found in WALLET_PRIVATE_KEYS"));
+    /// Replace the balance thresholds and top-up settings
+    pub fn with_balance_config(mut self, balance_config: WalletBalanceConfig) -> Self {
+        if let Some(ref treasury) = balance_config.treasury {
+            info!(
+                treasury = %redact_address(&treasury.address()),
+                min_balance_eth = wei_to_eth(balance_config.min_balance),
+                target_balance_eth = wei_to_eth(balance_config.target_balance),
+                top_up_enabled = balance_config.top_up_enabled,
+                "Configured wallet top-ups"
+            );
+        }
+        self.balance_config = balance_config;
+        self
+    }
+
+    /// Decrypt JSON keystores; directories are expanded to the files they contain
+    pub fn load_keystores(paths: &[PathBuf], password: &[u8]) -> Result<Vec<SharedSigner>> {
+        let mut files = Vec::new();
+        for path in paths {
+            if path.is_dir() {
+                let mut entries: Vec<PathBuf> = std::fs::read_dir(path)?
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|p| p.is_file())
+                    .collect();
+                entries.sort();
+                files.extend(entries);
+            } else {
+                files.push(path.clone());
+            }
+        }
+
+        let mut wallets: Vec<SharedSigner> = Vec::with_capacity(files.len());
+        for file in files {
+            let wallet = PrivateKeySigner::decrypt_keystore(&file, password)
+                .map_err(|e| eyre::eyre!("Failed to decrypt keystore {}: {}", file.display(), e))?;
+            info!(
+                keystore = %file.display(),
+                address = %redact_address(&wallet.address()),
+                "Initialized wallet from keystore"
+            );
+            wallets.push(Arc::new(LocalKeySigner::new(wallet)));
+        }
+        Ok(wallets)
+    }
+
+    /// Initialize from the wallet section of the node configuration
+    ///
+    /// Wallets are collected from every configured source:
+    /// - `private_keys`: raw private keys
+    /// - `keystore_paths`: keystore files or directories, decrypted with the password in
+    ///   `keystore_password_file` or `keystore_password`
+    /// - `remote_signer_url` with `remote_signer_addresses`
+    ///
+    /// Takes the section so the private keys are moved out and wiped once parsed.
+    pub fn from_config(mut config: WalletSection) -> Result<Self> {
+        let mut wallets: Vec<SharedSigner> = Vec::new();
+
+        if !config.private_keys.is_empty() {
+            wallets.extend(Self::new(std::mem::take(&mut config.private_keys))?.wallets);
+        }
+
+        if !config.keystore_paths.is_empty() {
+            let password = config.keystore_password()?;
+            wallets.extend(Self::load_keystores(&config.keystore_paths, password.as_bytes())?);
+        }
+
+        if let Some(url) = &config.remote_signer_url {
+            let addresses = config.remote_signer_addresses()?;
+            if addresses.is_empty() {
+                return Err(eyre::eyre!("wallet.remote_signer_addresses is required with wallet.remote_signer_url"));
+            }
+            for address in addresses {
+                info!(address = %redact_address(&address), "Initialized remote signer wallet");
+                wallets.push(Arc::new(RemoteSigner::new(url.clone(), address)?));
+            }
         }
 
-        Self::new(private_keys)
+        if wallets.is_empty() {
+            return Err(eyre::eyre!(
+                "No wallets configured: set wallet.private_keys, wallet.keystore_paths or wallet.remote_signer_url"
+            ));
+        }
+
+        info!(count = wallets.len(), "Initialized wallets");
+        Ok(Self::from_signers(wallets).with_balance_config(WalletBalanceConfig::from_config(&mut config)?))
     }
 
     /// Get a wallet by index
-    pub fn get_wallet(&self, index: usize) -> Result<PrivateKeySigner> {
+    pub fn get_wallet(&self, index: usize) -> Result<SharedSigner> {
         self.wallets
             .get(index)
             .cloned()
-            .ok_or_else(|| eyre::eyre!("Invalid wallet index: {}. Available: 0-{}", index, self.wallets.len() - 1))
+            .ok_or_else(|| eyre::eyre!("Invalid wallet index: {}. Available: {}", index, self.wallets.len()))
+    }
+
+    /// Get the wallet signing for an address
+    pub fn wallet_by_address(&self, address: &Address) -> Option<SharedSigner> {
+        self.wallets.iter().find(|wallet| wallet.address() == *address).cloned()
     }
 
-    /// Get a random wallet
-    pub fn get_random_wallet(&self) -> Result<PrivateKeySigner> {
+    /// Get a random wallet among those above the minimum balance
+    pub fn get_random_wallet(&self) -> Result<SharedSigner> {
         if self.wallets.is_empty() {
             return Err(eyre::eyre!("No wallets available"));
         }
 
+        let funded: Vec<&SharedSigner> = self.wallets.iter()
+            .filter(|wallet| self.is_funded(&wallet.address()))
+            .collect();
+        if funded.is_empty() {
+            return Err(eyre::eyre!("No wallets above the minimum balance"));
+        }
+
         let mut rng = rand::rng();
-        let index = rng.random_range(0..self.wallets.len());
-        
+        let index = rng.random_range(0..funded.len());
+
         debug!(index = index, "Selected random wallet");
-        Ok(self.wallets[index].clone())
+        Ok(funded[index].clone())
+    }
+
+    /// All wallets above the minimum balance
+    pub fn funded_wallets(&self) -> Vec<SharedSigner> {
+        self.wallets.iter()
+            .filter(|wallet| self.is_funded(&wallet.address()))
+            .cloned()
+            .collect()
+    }
+
+    /// Random pick weighted by balance above the minimum; unobserved wallets weigh as if at target
+    pub fn pick_balance_weighted(&self, candidates: &[SharedSigner]) -> Option<SharedSigner> {
+        let weights: Vec<u128> = candidates.iter()
+            .map(|wallet| {
+                let balance = self.balance(&wallet.address()).unwrap_or(self.balance_config.target_balance);
+                let spare = balance.saturating_sub(self.balance_config.min_balance);
+                // Keep every candidate selectable, even one sitting exactly at the minimum
+                u128::try_from(spare).unwrap_or(u128::MAX / candidates.len() as u128).max(1)
+            })
+            .collect();
+        let total: u128 = weights.iter().fold(0u128, |acc, w| acc.saturating_add(*w));
+        if total == 0 {
+            return None;
+        }
+
+        let mut point = rand::rng().random_range(0..total);
+        for (wallet, weight) in candidates.iter().zip(weights) {
+            if point < weight {
+                return Some(wallet.clone());
+            }
+            point -= weight;
+        }
+        candidates.last().cloned()
+    }
+
+    /// First wallet at or after `start` (wrapping) that is above the minimum balance
+    pub fn next_funded_wallet(&self, start: usize) -> Result<(usize, SharedSigner)> {
+        let count = self.wallets.len();
+        (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&index| self.is_funded(&self.wallets[index].address()))
+            .map(|index| (index, self.wallets[index].clone()))
+            .ok_or_else(|| eyre::eyre!("No wallets above the minimum balance"))
+    }
+
+    /// Whether a wallet may be selected; wallets with no observed balance yet are
+    pub fn is_funded(&self, address: &Address) -> bool {
+        self.balances.get(address)
+            .map(|balance| *balance >= self.balance_config.min_balance)
+            .unwrap_or(true)
+    }
+
+    /// Last observed balance of a wallet
+    pub fn balance(&self, address: &Address) -> Option<U256> {
+        self.balances.get(address).map(|balance| *balance)
+    }
+
+    /// Record a wallet's balance, alerting when it crosses the minimum
+    pub fn update_balance(&self, address: Address, balance: U256) {
+        let Some(metrics) = self.metrics.get(&address) else {
+            return;
+        };
+        let was_funded = self.is_funded(&address);
+        self.balances.insert(address, balance);
+        let funded = balance >= self.balance_config.min_balance;
+
+        metrics.balance_eth.set(wei_to_eth(balance));
+        metrics.below_minimum.set(if funded { 0.0 } else { 1.0 });
+
+        if was_funded && !funded {
+            metrics.low_balance_alerts_total.increment(1);
+            warn!(
+                wallet = %redact_address(&address),
+                balance_eth = wei_to_eth(balance),
+                min_balance_eth = wei_to_eth(self.balance_config.min_balance),
+                "🚨 Wallet balance below minimum, excluding from selection"
+            );
+        } else if !was_funded && funded {
+            info!(
+                wallet = %redact_address(&address),
+                balance_eth = wei_to_eth(balance),
+                "✅ Wallet balance restored"
+            );
+        }
+    }
+
+    /// Update balances of our wallets touched by a flashblock
+    pub fn observe_state(&self, snapshot: &FlashblockStateSnapshot) {
+        for wallet in &self.wallets {
+            let address = wallet.address();
+            if let Some(account) = snapshot.account_changes.get(&address) {
+                self.update_balance(address, account.balance);
+            }
+        }
+    }
+
+    /// Read every wallet's balance from the latest canonical state
+    pub fn refresh_balances<S: StateSource>(&self, source: &S) -> Result<()> {
+        let state = source.latest_state()?;
+        for wallet in &self.wallets {
+            let address = wallet.address();
+            let balance = state.basic_ref(address)?
+                .map(|account| account.balance)
+                .unwrap_or_default();
+            self.update_balance(address, balance);
+        }
+        Ok(())
+    }
+
+    /// Treasury signer, if top-ups are enabled
+    pub fn treasury(&self) -> Option<&SharedSigner> {
+        self.balance_config.treasury.as_ref()
+            .filter(|_| self.balance_config.top_up_enabled)
+    }
+
+    /// Wallets below the minimum balance with no recent top-up, and the amount bringing each to target
+    ///
+    /// Starts each returned wallet's cooldown, so concurrent callers never claim the same top-up.
+    pub fn claim_top_ups(&self) -> Vec<(Address, U256)> {
+        if self.treasury().is_none() {
+            return Vec::new();
+        }
+        let cooldown = self.balance_config.top_up_cooldown;
+        self.wallets.iter()
+            .map(|wallet| wallet.address())
+            .filter(|address| !self.is_funded(address))
+            .filter_map(|address| {
+                let balance = self.balance(&address)?;
+                let amount = self.balance_config.target_balance.saturating_sub(balance);
+                (!amount.is_zero()).then_some((address, amount))
+            })
+            .filter(|(address, _)| {
+                // Check and set under the entry lock
+                match self.top_ups.entry(*address) {
+                    Entry::Occupied(mut sent_at) => {
+                        if sent_at.get().elapsed() < cooldown {
+                            return false;
+                        }
+                        sent_at.insert(Instant::now());
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(Instant::now());
+                    }
+                }
+                true
+            })
+            .collect()
+    }
+
+    /// Record the outcome of a claimed top-up transfer
+    pub fn record_top_up(&self, address: Address, success: bool) {
+        if let Some(metrics) = self.metrics.get(&address) {
+            if success {
+                metrics.top_ups_total.increment(1);
+            } else {
+                metrics.top_up_failures_total.increment(1);
+            }
+        }
     }
 
     /// Get the number of wallets
@@ -105,10 +590,10 @@ mod tests {
         // Test with valid private key (test key, do not use in production)
         let test_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
         let keys = vec![test_key.to_string()];
-        
+
         let service = WalletService::new(keys).unwrap();
         assert_eq!(service.wallet_count(), 1);
-        
+
         let wallet = service.get_wallet(0).unwrap();
         assert_eq!(
             wallet.address().to_string(),
@@ -122,12 +607,97 @@ mod tests {
             "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string(),
             "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d".to_string(),
         ];
-        
+
         let service = WalletService::new(test_keys).unwrap();
         assert_eq!(service.wallet_count(), 2);
-        
+
         // Get random wallet should work
         let wallet = service.get_random_wallet().unwrap();
         assert!(service.get_addresses().contains(&wallet.address()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_redact_address() {
+        let address: Address = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse().unwrap();
+        assert_eq!(redact_address(&address), "0xf39F…2266");
+    }
+
+    #[test]
+    fn test_low_balance_wallets_are_excluded_and_topped_up() {
+        let test_keys = vec![
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string(),
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d".to_string(),
+        ];
+        let treasury: PrivateKeySigner = "5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a"
+            .parse()
+            .unwrap();
+        let service = WalletService::new(test_keys).unwrap().with_balance_config(WalletBalanceConfig {
+            min_balance: U256::from(100),
+            target_balance: U256::from(1_000),
+            top_up_enabled: true,
+            treasury: Some(Arc::new(LocalKeySigner::new(treasury))),
+            top_up_cooldown: Duration::from_secs(60),
+        });
+        let addresses = service.get_addresses();
+
+        service.update_balance(addresses[0], U256::from(40));
+        service.update_balance(addresses[1], U256::from(500));
+
+        assert!(!service.is_funded(&addresses[0]));
+        assert_eq!(service.next_funded_wallet(0).unwrap().0, 1);
+        assert_eq!(service.get_random_wallet().unwrap().address(), addresses[1]);
+        assert_eq!(service.claim_top_ups(), vec![(addresses[0], U256::from(960))]);
+
+        // No repeat while the transfer is in flight
+        assert!(service.claim_top_ups().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_matches_local_signature() {
+        let key: PrivateKeySigner = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+            .parse()
+            .unwrap();
+        let (addr, _) = crate::mock_sequencer::spawn(
+            "127.0.0.1:0".parse().unwrap(),
+            crate::mock_sequencer::MockSequencerConfig { signer: Some(key.clone()), ..Default::default() },
+        ).await.unwrap();
+
+        let remote = RemoteSigner::new(format!("http://{}/", addr), key.address()).unwrap();
+        let local = LocalKeySigner::new(key);
+        let tx = TxEip1559 {
+            chain_id: 8453,
+            nonce: 7,
+            gas_limit: 100_000,
+            max_fee_per_gas: 2_000_000,
+            max_priority_fee_per_gas: 1_000,
+            to: alloy_primitives::TxKind::Call(Address::repeat_byte(0x22)),
+            value: alloy_primitives::U256::from(5),
+            access_list: Default::default(),
+            input: alloy_primitives::Bytes::from(vec![0xde, 0xad]),
+        };
+
+        let remote_signature = remote.sign_transaction(&mut tx.clone()).await.unwrap();
+        let local_signature = local.sign_transaction(&mut tx.clone()).await.unwrap();
+        assert_eq!(remote_signature, local_signature);
+    }
+
+    #[test]
+    fn test_keystore_round_trip() {
+        use rand_chacha::rand_core::SeedableRng;
+
+        let key: PrivateKeySigner = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+            .parse()
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(7);
+        PrivateKeySigner::encrypt_keystore(dir.path(), &mut rng, key.to_bytes(), b"hunter2", Some("wallet-1"))
+            .unwrap();
+
+        // Directories are expanded to the keystore files inside them
+        let wallets = WalletService::load_keystores(&[dir.path().to_path_buf()], b"hunter2").unwrap();
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].address(), key.address());
+
+        assert!(WalletService::load_keystores(&[dir.path().join("wallet-1")], b"wrong").is_err());
+    }
+}
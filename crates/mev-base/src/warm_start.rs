@@ -0,0 +1,154 @@
+//! Warm-started quantity search.
+//!
+//! A target's optimum usually moves little between flashblocks, so instead of searching the
+//! full cold range (20% to 1000x of the default quantity) the search is seeded with a bracket
+//! around the recent winning quantities. The cold search still runs periodically, and whenever
+//! the warm result lands on an open edge of its bracket or finds no profit.
+
+use alloy_primitives::U256;
+
+use crate::gas_history_store::GasHistoryRecord;
+
+/// Configuration for warm-started searches
+#[derive(Debug, Clone)]
+pub struct WarmStartConfig {
+    pub enabled: bool,
+    /// Bracket widening on each side of the recent winners (basis points)
+    pub bracket_bps: u32,
+    /// Winners older than this many blocks are ignored
+    pub max_age_blocks: u64,
+    /// Every Nth search of a target runs cold to re-explore the full range (0 disables)
+    pub explore_every: u64,
+    /// A best quantity this close to an open bracket edge triggers a cold search (basis points of the bracket)
+    pub edge_bps: u32,
+}
+
+impl Default for WarmStartConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bracket_bps: 5_000,
+            max_age_blocks: 5,
+            explore_every: 10,
+            edge_bps: 100,
+        }
+    }
+}
+
+/// Narrowed search range seeded from recent winners
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmBracket {
+    /// Latest winning quantity, the search's starting point
+    pub initial: U256,
+    pub lower: U256,
+    pub upper: U256,
+    /// The bracket stops short of the cold lower bound, so the optimum may lie below it
+    pub open_below: bool,
+    /// The bracket stops short of the cold upper bound, so the optimum may lie above it
+    pub open_above: bool,
+}
+
+/// Bracket for this search, or `None` to search the cold range
+pub fn warm_bracket(
+    record: &GasHistoryRecord,
+    block_number: u64,
+    cold_lower: U256,
+    cold_upper: U256,
+    config: &WarmStartConfig,
+) -> Option<WarmBracket> {
+    if !config.enabled {
+        return None;
+    }
+    if config.explore_every > 0 && record.sample_count % config.explore_every == 0 {
+        return None;
+    }
+
+    let recent: Vec<U256> = record.recent_wins.iter()
+        .filter(|win| win.profit > 0 && block_number.saturating_sub(win.block_number) <= config.max_age_blocks)
+        .map(|win| win.qty)
+        .collect();
+    let latest = *recent.last()?;
+    let lowest = recent.iter().copied().min()?;
+    let highest = recent.iter().copied().max()?;
+
+    let bps = U256::from(config.bracket_bps);
+    let lower = lowest.saturating_sub(lowest * bps / U256::from(10_000)).max(cold_lower);
+    let upper = highest.saturating_add(highest * bps / U256::from(10_000)).min(cold_upper);
+    if lower >= upper {
+        return None;
+    }
+
+    Some(WarmBracket {
+        initial: latest.clamp(lower, upper),
+        lower,
+        upper,
+        open_below: lower > cold_lower,
+        open_above: upper < cold_upper,
+    })
+}
+
+/// Whether a warm result should be re-checked with a cold search
+pub fn needs_exploration(bracket: &WarmBracket, best_qty: U256, profit: i128, config: &WarmStartConfig) -> bool {
+    if profit <= 0 {
+        return true;
+    }
+    let edge = (bracket.upper - bracket.lower) * U256::from(config.edge_bps) / U256::from(10_000);
+    (bracket.open_below && best_qty <= bracket.lower + edge)
+        || (bracket.open_above && best_qty >= bracket.upper - edge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas_history_store::QtySample;
+
+    fn record(wins: &[(u64, u64)], sample_count: u64) -> GasHistoryRecord {
+        GasHistoryRecord {
+            version: 3,
+            gas: 1_000_000,
+            multiplier: Some(1000),
+            bounds: None,
+            last_best_qty: None,
+            recent_wins: wins.iter().map(|&(qty, block_number)| QtySample {
+                qty: U256::from(qty),
+                profit: 1,
+                block_number,
+                flashblock_index: 0,
+            }).collect(),
+            sample_count,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_bracket_around_recent_winners() {
+        let config = WarmStartConfig::default();
+        let cold = (U256::from(20), U256::from(100_000));
+
+        let bracket = warm_bracket(&record(&[(1_000, 99), (1_200, 100)], 3), 100, cold.0, cold.1, &config).unwrap();
+        assert_eq!((bracket.lower, bracket.upper, bracket.initial), (U256::from(500), U256::from(1_800), U256::from(1_200)));
+        assert!(bracket.open_below && bracket.open_above);
+
+        // Stale winners, periodic exploration and disabled warm starts all search cold
+        assert!(warm_bracket(&record(&[(1_000, 90)], 3), 100, cold.0, cold.1, &config).is_none());
+        assert!(warm_bracket(&record(&[(1_000, 100)], 10), 100, cold.0, cold.1, &config).is_none());
+        let disabled = WarmStartConfig { enabled: false, ..Default::default() };
+        assert!(warm_bracket(&record(&[(1_000, 100)], 3), 100, cold.0, cold.1, &disabled).is_none());
+    }
+
+    #[test]
+    fn test_exploration_on_open_edges() {
+        let config = WarmStartConfig::default();
+        let bracket = WarmBracket {
+            initial: U256::from(1_000),
+            lower: U256::from(20),
+            upper: U256::from(2_000),
+            open_below: false,
+            open_above: true,
+        };
+        assert!(!needs_exploration(&bracket, U256::from(1_000), 5, &config));
+        assert!(!needs_exploration(&bracket, U256::from(20), 5, &config));
+        assert!(needs_exploration(&bracket, U256::from(1_990), 5, &config));
+        assert!(needs_exploration(&bracket, U256::from(1_000), 0, &config));
+    }
+}